@@ -15,6 +15,36 @@ error_chain! {
             description("Invalid identifier value.")
             display("Invalid identifier value: '{}'.", identifier)
         }
+        #[doc = "A field, or parameter, requires an explicit field identifier."]
+        MissingFieldId(owner: String, field: String) {
+            description("A field, or parameter, requires an explicit field identifier.")
+            display("Missing field identifier for '{}' in '{}'.", field, owner)
+        }
+        #[doc = "A field identifier was used more than once."]
+        DuplicateFieldId(owner: String, id: u32) {
+            description("A field identifier was used more than once.")
+            display("Duplicate field identifier {} in '{}'.", id, owner)
+        }
+        #[doc = "A field identifier is outside the range supported by the target language."]
+        InvalidFieldId(owner: String, id: u32) {
+            description("A field identifier is outside the range supported by the target language.")
+            display("Invalid field identifier {} in '{}'.", id, owner)
+        }
+        #[doc = "Two modules would be written to the same file."]
+        DuplicateModulePath(path: String) {
+            description("Two modules would be written to the same file.")
+            display("More than one module would be written to '{}'.", path)
+        }
+        #[doc = "A protected region name was used more than once in the same file."]
+        DuplicateRegionName(name: String) {
+            description("A protected region name was used more than once in the same file.")
+            display("Duplicate protected region name: '{}'.", name)
+        }
+        #[doc = "A generated file has been edited, or was not generated, and will not be overwritten."]
+        ModifiedGeneratedFile(path: String) {
+            description("A generated file has been edited, or was not generated, and will not be overwritten.")
+            display("The file '{}' has been edited since it was generated; use force to overwrite.", path)
+        }
         #[doc = "Unsupported model element kind."]
         UnsupportedElementKind(kind: String) {
             description("Unsupported model element kind.")
@@ -25,5 +55,6 @@ error_chain! {
     foreign_links {
         Fmt(::std::fmt::Error);
         Io(::std::io::Error);
+        Utf8(::std::string::FromUtf8Error);
     }
 }
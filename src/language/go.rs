@@ -0,0 +1,846 @@
+/*!
+Writes modules as Go source files, one package per module.
+
+Structures, classes and exceptions are written as `struct` types with `json` tags, and the
+interface and service kinds as `interface` types. Enumerations are written as a named type with a
+block of constants, using `iota` when no variant has a value. All of a module's imports are
+grouped into the `import` block that follows the `package` clause.
+
+A declaration is exported, its name starting with an upper case letter, only if its visibility is
+`Visibility::Public`; declared names are converted to camel case to match. Type references are
+written as given, so they should be spelled the way the referenced declaration is written.
+
+Code is indented according to the writer's formatting policy; use `go_formatting_policy` to indent
+with the tabs `gofmt` expects.
+
+# Example
+
+```rust
+use code_writer::language::go::GoWriter;
+use code_writer::model::{
+    Builder, Field, HasVisibility, Identifier, IsOptional, Module, StructuredType, ValueType,
+    Visibility,
+};
+use code_writer::writer::module_to_string;
+
+let module = Module::new(Identifier::new("address"))
+    .add_structure(
+        StructuredType::structure(Identifier::new("Country"))
+            .set_visibility(Visibility::Public)
+            .add_field(
+                Field::new(Identifier::new("code"), ValueType::string())
+                    .set_visibility(Visibility::Public)
+                    .build(),
+            )
+            .add_field(
+                Field::new(Identifier::new("long_name"), ValueType::string())
+                    .set_visibility(Visibility::Public)
+                    .optional()
+                    .build(),
+            )
+            .build(),
+    )
+    .build();
+
+assert_eq!(
+    module_to_string(&GoWriter::default(), &module).unwrap(),
+    r#"package address
+
+type Country struct {
+    Code     string  `json:"code"`
+    LongName *string `json:"long_name,omitempty"`
+}
+"#
+);
+```
+*/
+
+use crate::error::{ErrorKind, Result};
+use crate::language::helpers::{integer_value, not_generic, string_literal};
+use crate::model::identity::Namespace;
+use crate::model::{
+    Comment, Enumeration, Field, FunctionDecl, HasDocumentation, HasGenericParameters, HasName,
+    HasOptionalType, HasOptionalValue, HasType, HasValue, HasVisibility, Identifier, Import,
+    IsOptional, KnownType, Module, ModuleContent, NamedValue, ProtectedRegion, StructuredType,
+    StructuredTypeKind, TypeAlias, Value, ValueType, Visibility,
+};
+use crate::project::ProjectLayout;
+use crate::writer::{CodeWriter, FormattingPolicy, List, ListWrapping, ModuleWriter};
+use std::io::Write;
+use std::path::PathBuf;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Default)]
+pub struct GoWriter {
+    module_path: Option<String>,
+}
+
+/// Every module is a package in a directory for its namespace, written to a file named after the
+/// package; `api/users/users.go` for example.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GoLayout {}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+/// One line in a block of aligned declarations, such as struct fields or enumeration constants.
+struct Declaration {
+    separated: bool,
+    documentation: Option<String>,
+    columns: Vec<String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// The default formatting policy, but indenting with tabs as `gofmt` does.
+pub fn go_formatting_policy() -> FormattingPolicy {
+    let mut policy = FormattingPolicy::default();
+    let _ = policy.set_indent("\t");
+    policy
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<W> ModuleWriter<W> for GoWriter
+where
+    W: Write,
+{
+    fn write_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        if let Some(documentation) = module.documentation() {
+            writer.write_doc_text("// ", documentation)?;
+        }
+        writer.write_str(&format!("package {}", module.name()))?;
+        writer.new_line()?;
+
+        let mut paths: Vec<String> = Default::default();
+        for content in module.content() {
+            if let ModuleContent::Import(import) = content {
+                let path = self.import_path(import)?;
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        match paths.as_slice() {
+            [] => {}
+            [path] => {
+                writer.blank_line()?;
+                writer.write_str(&format!("import {}", path))?;
+                writer.new_line()?;
+            }
+            paths => {
+                writer.blank_line()?;
+                writer.write_str("import (")?;
+                writer.new_line()?;
+                writer.indent();
+                for path in paths {
+                    writer.write_str(path)?;
+                    writer.new_line()?;
+                }
+                writer.outdent();
+                writer.write_str(")")?;
+                writer.new_line()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_sub_module(&self, _: &mut CodeWriter<W>, _: &Module) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_import(&self, _: &mut CodeWriter<W>, _: &Import) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_comment(&self, writer: &mut CodeWriter<W>, comment: &Comment) -> Result<()> {
+        writer.write_doc_text("// ", comment.text())
+    }
+
+    fn write_structured_type(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        if !record.associated_types().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("associated_type".to_string()).into());
+        }
+        if !record.associated_constants().is_empty() {
+            return Err(
+                ErrorKind::UnsupportedElementKind("associated_constant".to_string()).into(),
+            );
+        }
+        match record.kind() {
+            StructuredTypeKind::Structure
+            | StructuredTypeKind::Class
+            | StructuredTypeKind::Exception => self.write_struct(writer, record),
+            StructuredTypeKind::Interface | StructuredTypeKind::Service => {
+                self.write_interface(writer, record)
+            }
+            StructuredTypeKind::Union => {
+                Err(ErrorKind::UnsupportedElementKind("union".to_string()).into())
+            }
+        }
+    }
+
+    fn write_enumeration(
+        &self,
+        writer: &mut CodeWriter<W>,
+        enumeration: &Enumeration,
+    ) -> Result<()> {
+        not_generic(enumeration)?;
+        let type_name = go_name(enumeration.name(), enumeration);
+        let variants = enumeration.variants();
+        if variants.iter().any(|variant| variant.has_payload()) {
+            return Err(ErrorKind::UnsupportedElementKind(
+                "enumeration_variant_payload".to_string(),
+            )
+            .into());
+        }
+        let is_string = variants
+            .iter()
+            .any(|variant| matches!(variant.value(), Some(Value::String(_))));
+        let values = if is_string {
+            variants
+                .iter()
+                .map(|variant| match variant.value() {
+                    None => Ok(Some(string_literal(variant.name().as_ref()))),
+                    Some(Value::String(value)) => Ok(Some(string_literal(value))),
+                    Some(_) => Err(enumeration_value_error()),
+                })
+                .collect::<Result<Vec<Option<String>>>>()?
+        } else if variants.iter().all(|variant| !variant.has_value()) {
+            (0..variants.len())
+                .map(|i| {
+                    if i == 0 {
+                        Some("iota".to_string())
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        } else {
+            // As with iota, a variant without a value follows on from the one before it.
+            let mut next = 0;
+            variants
+                .iter()
+                .map(|variant| {
+                    let value = match variant.value() {
+                        None => next,
+                        Some(value) => integer_value(value).ok_or_else(enumeration_value_error)?,
+                    };
+                    next = value + 1;
+                    Ok(Some(value.to_string()))
+                })
+                .collect::<Result<Vec<Option<String>>>>()?
+        };
+
+        self.write_documentation(writer, enumeration)?;
+        writer.write_str(&format!(
+            "type {} {}",
+            type_name,
+            if is_string { "string" } else { "int" }
+        ))?;
+        writer.new_line()?;
+        if variants.is_empty() {
+            return Ok(());
+        }
+        let declarations = variants
+            .iter()
+            .zip(values)
+            .map(|(variant, value)| Declaration {
+                separated: false,
+                documentation: variant.documentation().clone(),
+                columns: match value {
+                    None => vec![format!("{}{}", type_name, upper_camel_case(variant.name()))],
+                    Some(value) => vec![
+                        format!("{}{}", type_name, upper_camel_case(variant.name())),
+                        format!("{} = {}", type_name, value),
+                    ],
+                },
+            })
+            .collect::<Vec<Declaration>>();
+        writer.blank_line()?;
+        writer.write_str("const (")?;
+        writer.new_line()?;
+        writer.indent();
+        for (variant, declaration) in variants.iter().zip(aligned(declarations)) {
+            writer.begin_element(variant.name());
+            self.write_declaration(writer, declaration)?;
+            writer.end_element();
+        }
+        writer.outdent();
+        writer.write_str(")")?;
+        writer.new_line()
+    }
+
+    fn write_constant(&self, writer: &mut CodeWriter<W>, constant: &NamedValue) -> Result<()> {
+        match constant.value() {
+            Value::Values(_) | Value::NamedValues(_) => {
+                return Err(ErrorKind::UnsupportedElementKind("constant_value".to_string()).into())
+            }
+            _ => {}
+        }
+        self.write_documentation(writer, constant)?;
+        writer.write_str(&format!(
+            "const {} {} = {}",
+            go_name(constant.name(), constant),
+            value_type(constant.value_type())?,
+            literal(constant.value())?
+        ))?;
+        writer.new_line()
+    }
+
+    fn write_variable(&self, writer: &mut CodeWriter<W>, variable: &NamedValue) -> Result<()> {
+        self.write_documentation(writer, variable)?;
+        writer.write_str(&format!(
+            "var {} {} = {}",
+            go_name(variable.name(), variable),
+            value_type(variable.value_type())?,
+            typed_literal(variable.value(), variable.value_type())?
+        ))?;
+        writer.new_line()
+    }
+
+    fn write_type_alias(&self, writer: &mut CodeWriter<W>, type_alias: &TypeAlias) -> Result<()> {
+        not_generic(type_alias)?;
+        let line = format!(
+            "type {} = {}",
+            go_name(type_alias.name(), type_alias),
+            value_type(type_alias.value_type())?
+        );
+        self.write_documentation(writer, type_alias)?;
+        writer.write_str(&line)?;
+        writer.new_line()
+    }
+
+    fn write_region(&self, writer: &mut CodeWriter<W>, region: &ProtectedRegion) -> Result<()> {
+        writer.write_region_markers("// ", region.name().as_ref())
+    }
+}
+
+impl ProjectLayout for GoLayout {
+    fn module_file_path(&self, namespace: &Namespace) -> PathBuf {
+        let mut path: PathBuf = namespace.path().iter().map(|id| id.as_ref()).collect();
+        let name = namespace
+            .path()
+            .last()
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+        path.push(format!("{}.go", name));
+        path
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl GoWriter {
+    pub fn module_path(&self) -> &Option<String> {
+        &self.module_path
+    }
+
+    /// Prefix the path of every import, usually the Go module path such as `example.com/app`.
+    pub fn set_module_path(&mut self, module_path: &str) -> &mut Self {
+        self.module_path = Some(module_path.trim_end_matches('/').to_string());
+        self
+    }
+
+    fn import_path(&self, import: &Import) -> Result<String> {
+        if import.items().iter().any(|item| item.alias().is_some()) {
+            return Err(ErrorKind::UnsupportedElementKind("import_alias".to_string()).into());
+        }
+        let path = import
+            .namespace()
+            .path()
+            .iter()
+            .map(|id| id.as_ref())
+            .collect::<Vec<&str>>()
+            .join("/");
+        Ok(match &self.module_path {
+            None => string_literal(&path),
+            Some(module_path) => string_literal(&format!("{}/{}", module_path, path)),
+        })
+    }
+
+    fn write_struct<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        if !record.methods().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("method".to_string()).into());
+        }
+        let declarations = record
+            .fields()
+            .iter()
+            .map(field_declaration)
+            .collect::<Result<Vec<Declaration>>>()?;
+
+        self.write_documentation(writer, record)?;
+        writer.write_str(&format!(
+            "type {}{} struct",
+            go_name(record.name(), record),
+            type_parameters(record)?
+        ))?;
+        if record.extends().is_empty() && declarations.is_empty() {
+            writer.write_str(" {}")?;
+            return writer.new_line();
+        }
+        writer.write_str(" {")?;
+        writer.new_line()?;
+        writer.indent();
+        for extends in record.extends() {
+            writer.write_str(&value_type(extends)?)?;
+            writer.new_line()?;
+        }
+        if !record.extends().is_empty() && !declarations.is_empty() {
+            writer.blank_line()?;
+        }
+        for (field, declaration) in record.fields().iter().zip(aligned(declarations)) {
+            writer.begin_element(field.name());
+            self.write_declaration(writer, declaration)?;
+            writer.end_element();
+        }
+        writer.outdent();
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn write_interface<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        if !record.fields().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("interface_field".to_string()).into());
+        }
+        self.write_documentation(writer, record)?;
+        writer.write_str(&format!(
+            "type {}{} interface",
+            go_name(record.name(), record),
+            type_parameters(record)?
+        ))?;
+        if record.extends().is_empty() && record.methods().is_empty() {
+            writer.write_str(" {}")?;
+            return writer.new_line();
+        }
+        writer.write_str(" {")?;
+        writer.new_line()?;
+        writer.indent();
+        for extends in record.extends() {
+            writer.write_str(&value_type(extends)?)?;
+            writer.new_line()?;
+        }
+        for method in record.methods() {
+            writer.begin_element(method.name());
+            self.write_method_signature(writer, method)?;
+            writer.end_element();
+        }
+        writer.outdent();
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn write_method_signature<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        method: &FunctionDecl,
+    ) -> Result<()> {
+        not_generic(method)?;
+        if method.has_body() {
+            return Err(ErrorKind::UnsupportedElementKind("function_body".to_string()).into());
+        }
+        let parameters = method
+            .parameters()
+            .iter()
+            .map(|parameter| {
+                Ok(format!(
+                    "{} {}",
+                    lower_camel_case(parameter.name()),
+                    optional_type(parameter.value_type(), parameter.is_optional())?
+                ))
+            })
+            .collect::<Result<Vec<String>>>()?;
+        let mut results: Vec<String> = Default::default();
+        match method.value_type() {
+            None | Some(ValueType::Unit) => {}
+            Some(ValueType::Tuple(vts)) => {
+                for vt in vts {
+                    results.push(value_type(vt)?);
+                }
+            }
+            Some(ValueType::Result(vt, _)) => {
+                if !matches!(**vt, ValueType::Unit) {
+                    results.push(value_type(vt)?);
+                }
+                results.push("error".to_string());
+            }
+            Some(vt) => results.push(value_type(vt)?),
+        }
+        if !method.throws().is_empty() && !results.iter().any(|result| result == "error") {
+            results.push("error".to_string());
+        }
+        let results = match results.as_slice() {
+            [] => String::new(),
+            [result] => format!(" {}", result),
+            results => format!(" ({})", results.join(", ")),
+        };
+
+        self.write_documentation(writer, method)?;
+        writer.write_str(&go_name(method.name(), method))?;
+        writer.write_list_then(
+            &List::new("(", ")", ListWrapping::OnePerLine),
+            &parameters,
+            &results,
+        )?;
+        writer.new_line()
+    }
+
+    fn write_declaration<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        declaration: Declaration,
+    ) -> Result<()> {
+        if declaration.separated {
+            writer.blank_line()?;
+        }
+        if let Some(documentation) = &declaration.documentation {
+            writer.write_doc_text("// ", documentation)?;
+        }
+        writer.write_str(&declaration.columns.join(" "))?;
+        writer.new_line()
+    }
+
+    fn write_documentation<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasDocumentation,
+    ) -> Result<()> {
+        if let Some(documentation) = item.documentation() {
+            writer.write_doc_text("// ", documentation)?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn enumeration_value_error() -> crate::error::Error {
+    ErrorKind::UnsupportedElementKind("enumeration_value".to_string()).into()
+}
+
+fn is_exported(item: &dyn HasVisibility) -> bool {
+    item.visibility() == &Some(Visibility::Public)
+}
+
+/// The declared name of an item, exported only if the item is public.
+fn go_name(name: &Identifier, item: &dyn HasVisibility) -> String {
+    if is_exported(item) {
+        upper_camel_case(name)
+    } else {
+        lower_camel_case(name)
+    }
+}
+
+fn upper_camel_case(name: &Identifier) -> String {
+    let name = name.as_ref();
+    // A SCREAMING_SNAKE_CASE name is read as snake case, rather than as a run of initialisms.
+    let name = if name.contains('_') && !name.chars().any(char::is_lowercase) {
+        name.to_lowercase()
+    } else {
+        name.to_string()
+    };
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+            }
+        })
+        .collect()
+}
+
+fn lower_camel_case(name: &Identifier) -> String {
+    let name = upper_camel_case(name);
+    let mut chars = name.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+    }
+}
+
+fn type_parameters(item: &dyn HasGenericParameters) -> Result<String> {
+    if item.has_where_predicates() {
+        return Err(ErrorKind::UnsupportedElementKind("where_clause".to_string()).into());
+    }
+    let parameters = item
+        .generic_parameters()
+        .iter()
+        .filter(|parameter| !parameter.is_lifetime())
+        .map(|parameter| {
+            if parameter.has_default() {
+                return Err(ErrorKind::UnsupportedElementKind(
+                    "generic_parameter_default".to_string(),
+                )
+                .into());
+            }
+            let bounds = parameter
+                .bounds()
+                .iter()
+                .filter(|bound| !matches!(bound, ValueType::Lifetime(_)))
+                .map(value_type)
+                .collect::<Result<Vec<String>>>()?;
+            Ok(format!(
+                "{} {}",
+                parameter.name(),
+                match bounds.as_slice() {
+                    [] => "any".to_string(),
+                    [bound] => bound.clone(),
+                    bounds => format!("interface{{ {} }}", bounds.join("; ")),
+                }
+            ))
+        })
+        .collect::<Result<Vec<String>>>()?;
+    Ok(if parameters.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]", parameters.join(", "))
+    })
+}
+
+fn field_declaration(field: &Field) -> Result<Declaration> {
+    let optional = field.is_optional() || field.value_type().is_optional();
+    let mut columns = vec![
+        go_name(field.name(), field),
+        optional_type(field.value_type(), field.is_optional())?,
+    ];
+    if field.has_value() {
+        return Err(ErrorKind::UnsupportedElementKind("field_value".to_string()).into());
+    }
+    // Unexported fields are ignored by encoding/json, and tagging them is flagged by go vet.
+    if is_exported(field) {
+        columns.push(format!(
+            "`json:\"{}{}\"`",
+            field.name(),
+            if optional { ",omitempty" } else { "" }
+        ));
+    }
+    Ok(Declaration {
+        separated: false,
+        documentation: field.documentation().clone(),
+        columns,
+    })
+}
+
+/// Pad the columns of each declaration to align with its neighbours, as gofmt does. A documented
+/// declaration starts a new aligned section, and is preceded by a blank line to show it.
+fn aligned(declarations: Vec<Declaration>) -> Vec<Declaration> {
+    let mut result: Vec<Declaration> = Vec::with_capacity(declarations.len());
+    let mut section: Vec<Declaration> = Default::default();
+    for declaration in declarations {
+        if declaration.documentation.is_some() && !section.is_empty() {
+            result.append(&mut align_section(std::mem::take(&mut section)));
+        }
+        section.push(declaration);
+    }
+    result.append(&mut align_section(section));
+    for declaration in result.iter_mut().skip(1) {
+        declaration.separated = declaration.documentation.is_some();
+    }
+    result
+}
+
+fn align_section(mut section: Vec<Declaration>) -> Vec<Declaration> {
+    let columns = section
+        .iter()
+        .map(|declaration| declaration.columns.len())
+        .max()
+        .unwrap_or_default();
+    for column in 0..columns.saturating_sub(1) {
+        // Only lines with a following column take part in the alignment of this one.
+        let width = section
+            .iter()
+            .filter(|declaration| declaration.columns.len() > column + 1)
+            .map(|declaration| declaration.columns[column].chars().count())
+            .max()
+            .unwrap_or_default();
+        for declaration in section
+            .iter_mut()
+            .filter(|declaration| declaration.columns.len() > column + 1)
+        {
+            let cell = &mut declaration.columns[column];
+            let padding = width - cell.chars().count();
+            cell.push_str(&" ".repeat(padding));
+        }
+    }
+    section
+}
+
+/// The type of an optional field or parameter is a pointer, unless the type can already be nil.
+fn optional_type(vt: &ValueType, optional: bool) -> Result<String> {
+    let type_name = value_type(vt)?;
+    Ok(
+        if optional
+            && !matches!(
+                vt,
+                ValueType::Optional(_)
+                    | ValueType::Pointer(_)
+                    | ValueType::PointerMut(_)
+                    | ValueType::Boxed(_)
+                    | ValueType::Slice(_)
+                    | ValueType::Array(_)
+                    | ValueType::Set(_)
+                    | ValueType::Map(_, _)
+                    | ValueType::Function(_, _)
+            )
+        {
+            format!("*{}", type_name)
+        } else {
+            type_name
+        },
+    )
+}
+
+fn value_type(vt: &ValueType) -> Result<String> {
+    Ok(match vt {
+        ValueType::Known(kt) => match kt {
+            KnownType::I8 => "int8",
+            KnownType::U8 => "uint8",
+            KnownType::I16 => "int16",
+            KnownType::U16 => "uint16",
+            KnownType::I32 => "int32",
+            KnownType::U32 => "uint32",
+            KnownType::I64 => "int64",
+            KnownType::U64 => "uint64",
+            KnownType::F32 => "float32",
+            KnownType::F64 => "float64",
+            KnownType::Boolean => "bool",
+            KnownType::Char => "rune",
+            KnownType::String | KnownType::StringSlice => "string",
+        }
+        .to_string(),
+        ValueType::Reference(t) => t.to_string(),
+        ValueType::Borrowed(_, t) | ValueType::BorrowedMut(_, t) => value_type(t)?,
+        ValueType::Pointer(t)
+        | ValueType::PointerMut(t)
+        | ValueType::Boxed(t)
+        | ValueType::Optional(t) => format!("*{}", value_type(t)?),
+        ValueType::Slice(t) | ValueType::Array(t) => format!("[]{}", value_type(t)?),
+        ValueType::FixedArray(t, size) => format!("[{}]{}", size, value_type(t)?),
+        ValueType::Set(t) => format!("map[{}]struct{{}}", value_type(t)?),
+        ValueType::Map(kt, vt) => format!("map[{}]{}", value_type(kt)?, value_type(vt)?),
+        ValueType::Generic(t, ts) => format!(
+            "{}[{}]",
+            t,
+            ts.iter()
+                .map(value_type)
+                .collect::<Result<Vec<String>>>()?
+                .join(", ")
+        ),
+        ValueType::Function(pts, rt) => format!(
+            "func({}){}",
+            pts.iter()
+                .map(value_type)
+                .collect::<Result<Vec<String>>>()?
+                .join(", "),
+            match rt {
+                None => String::new(),
+                Some(rt) if matches!(**rt, ValueType::Unit) => String::new(),
+                Some(rt) => format!(" {}", value_type(rt)?),
+            }
+        ),
+        ValueType::Unit => "struct{}".to_string(),
+        ValueType::Tuple(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("tuple".to_string()).into())
+        }
+        ValueType::Lifetime(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("lifetime".to_string()).into())
+        }
+        ValueType::Result(_, _) => {
+            return Err(ErrorKind::UnsupportedElementKind("result".to_string()).into())
+        }
+    })
+}
+
+/// A literal for a value of the given type; composite values take the type as a prefix.
+fn typed_literal(value: &Value, vt: &ValueType) -> Result<String> {
+    Ok(match (value, vt) {
+        (Value::Values(vs), ValueType::Slice(t))
+        | (Value::Values(vs), ValueType::Array(t))
+        | (Value::Values(vs), ValueType::FixedArray(t, _)) => format!(
+            "{}{{{}}}",
+            value_type(vt)?,
+            vs.iter()
+                .map(|v| typed_literal(v, t))
+                .collect::<Result<Vec<String>>>()?
+                .join(", ")
+        ),
+        (Value::NamedValues(vs), ValueType::Map(kt, t)) => {
+            let mut entries = vs
+                .iter()
+                .map(|(k, v)| {
+                    Ok(format!(
+                        "{}: {}",
+                        typed_literal(k, kt)?,
+                        typed_literal(v, t)?
+                    ))
+                })
+                .collect::<Result<Vec<String>>>()?;
+            entries.sort();
+            format!("{}{{{}}}", value_type(vt)?, entries.join(", "))
+        }
+        (Value::Values(_), _) | (Value::NamedValues(_), _) => {
+            return Err(ErrorKind::UnsupportedElementKind("value".to_string()).into())
+        }
+        (value, _) => literal(value)?,
+    })
+}
+
+fn literal(value: &Value) -> Result<String> {
+    Ok(match value {
+        Value::I8(v) => v.to_string(),
+        Value::U8(v) => v.to_string(),
+        Value::I16(v) => v.to_string(),
+        Value::U16(v) => v.to_string(),
+        Value::I32(v) => v.to_string(),
+        Value::U32(v) => v.to_string(),
+        Value::I64(v) => v.to_string(),
+        Value::U64(v) => v.to_string(),
+        Value::F32(v) if v.is_finite() => format!("{:?}", v),
+        Value::F64(v) if v.is_finite() => format!("{:?}", v),
+        Value::Boolean(v) => v.to_string(),
+        Value::Char(v) => format!("'{}'", escaped(&v.to_string(), '\'')),
+        Value::String(v) => string_literal(v),
+        Value::Identifier(v) => v.to_string(),
+        _ => return Err(ErrorKind::UnsupportedElementKind("value".to_string()).into()),
+    })
+}
+
+fn escaped(s: &str, quote: char) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c == quote => {
+                result.push('\\');
+                result.push(c);
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
@@ -0,0 +1,100 @@
+/*!
+Helper functions shared by the language writers.
+*/
+
+use crate::error::{ErrorKind, Result};
+use crate::model::{HasGenericParameters, Identifier, Value};
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Fail with `generic_parameters` for a language that has no generics.
+pub(crate) fn not_generic(item: &dyn HasGenericParameters) -> Result<()> {
+    if item.is_generic() {
+        Err(ErrorKind::UnsupportedElementKind("generic_parameters".to_string()).into())
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn is_integer(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::I8(_)
+            | Value::U8(_)
+            | Value::I16(_)
+            | Value::U16(_)
+            | Value::I32(_)
+            | Value::U32(_)
+            | Value::I64(_)
+            | Value::U64(_)
+    )
+}
+
+/// The value of an integer that fits in an `i64`.
+pub(crate) fn integer_value(value: &Value) -> Option<i64> {
+    match value {
+        Value::I8(v) => Some(*v as i64),
+        Value::U8(v) => Some(*v as i64),
+        Value::I16(v) => Some(*v as i64),
+        Value::U16(v) => Some(*v as i64),
+        Value::I32(v) => Some(*v as i64),
+        Value::U32(v) => Some(*v as i64),
+        Value::I64(v) => Some(*v),
+        Value::U64(v) => i64::try_from(*v).ok(),
+        _ => None,
+    }
+}
+
+/// A double-quoted string, escaping only `\\`, `\"`, `\n`, `\r` and `\t`.
+pub(crate) fn string_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+///
+/// The field identifier of each of `owner`'s members, in order. A member without one takes its
+/// position, counting from 1, when `positional` is set; every identifier must be unique and
+/// accepted by `is_valid`.
+///
+pub(crate) fn field_ids<'a>(
+    owner: &Identifier,
+    members: impl Iterator<Item = (&'a Identifier, &'a Option<u32>)>,
+    positional: bool,
+    is_valid: impl Fn(u32) -> bool,
+) -> Result<Vec<u32>> {
+    let mut seen: HashSet<u32> = Default::default();
+    let mut field_ids: Vec<u32> = Default::default();
+    for (i, (name, field_id)) in members.enumerate() {
+        let field_id = match field_id {
+            Some(field_id) => *field_id,
+            None if positional => (i + 1) as u32,
+            None => {
+                return Err(ErrorKind::MissingFieldId(owner.to_string(), name.to_string()).into())
+            }
+        };
+        if !is_valid(field_id) {
+            return Err(ErrorKind::InvalidFieldId(owner.to_string(), field_id).into());
+        }
+        if !seen.insert(field_id) {
+            return Err(ErrorKind::DuplicateFieldId(owner.to_string(), field_id).into());
+        }
+        field_ids.push(field_id);
+    }
+    Ok(field_ids)
+}
@@ -0,0 +1,598 @@
+/*!
+Writes modules as JSON Schema (draft 2020-12) documents.
+
+A JSON document cannot be written one element at a time, so the whole schema for a module is
+written by `write_module`, with each structured type, enumeration and type alias as an entry in
+`$defs`; the remaining `ModuleWriter` methods write nothing. Sub-modules are separate documents,
+and references to types imported from them use the sub-module's document as the base of `$ref`.
+Only the shape of data is described, so methods on structured types are ignored.
+
+# Example
+
+```rust
+use code_writer::language::json_schema::JsonSchemaWriter;
+use code_writer::model::{Builder, Field, Identifier, Import, IsOptional, Module, StructuredType, ValueType};
+use code_writer::writer::module_to_string;
+
+let module = Module::new(Identifier::new("address"))
+    .add_structure(
+        StructuredType::structure(Identifier::new("Country"))
+            .add_field(Field::new(Identifier::new("code"), ValueType::string()).build())
+            .add_field(Field::new(Identifier::new("name"), ValueType::string()).optional().build())
+            .build(),
+    )
+    .build();
+
+assert_eq!(
+    module_to_string(&JsonSchemaWriter::default(), &module).unwrap(),
+    r#"{
+    "$schema": "https://json-schema.org/draft/2020-12/schema",
+    "$id": "address.schema.json",
+    "title": "address",
+    "$defs": {
+        "Country": {
+            "type": "object",
+            "properties": {
+                "code": {
+                    "type": "string"
+                },
+                "name": {
+                    "type": "string"
+                }
+            },
+            "required": ["code"]
+        }
+    }
+}
+"#
+);
+```
+*/
+
+use crate::error::{ErrorKind, Result};
+use crate::language::helpers::not_generic;
+use crate::model::identity::Namespace;
+use crate::model::{
+    Comment, Enumeration, Field, HasDocumentation, HasName, HasOptionalValue, HasType, Identifier,
+    Import, IsOptional, KnownType, Module, ModuleContent, StructuredType, StructuredTypeKind,
+    TypeAlias, Value, ValueType,
+};
+use crate::project::ProjectLayout;
+use crate::writer::{CodeWriter, ModuleWriter};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Default)]
+pub struct JsonSchemaWriter {}
+
+/// Every module is written to `name.schema.json` in the project root, matching its `$id`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct JsonSchemaLayout {}
+
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Boolean(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// Maps the names of imported items to the `$ref` of their definition in another document.
+struct References {
+    imported: HashMap<String, String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<W> ModuleWriter<W> for JsonSchemaWriter
+where
+    W: Write,
+{
+    fn write_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        let references = References::new(module);
+        let mut definitions: Vec<(&Identifier, Json)> = Default::default();
+        for content in module.content() {
+            match content {
+                ModuleContent::Import(_) | ModuleContent::Comment(_) | ModuleContent::Module(_) => {
+                }
+                ModuleContent::Structure(record) => {
+                    definitions.push((record.name(), references.structured_type(record)?))
+                }
+                ModuleContent::Enumeration(enumeration) => {
+                    definitions.push((enumeration.name(), references.enumeration(enumeration)?))
+                }
+                ModuleContent::Alias(type_alias) => {
+                    definitions.push((type_alias.name(), references.type_alias(type_alias)?))
+                }
+                ModuleContent::Constant(_) => return Err(unsupported("constant")),
+                ModuleContent::Variable(_) => return Err(unsupported("variable")),
+                ModuleContent::Function(_) => return Err(unsupported("function_decl")),
+                ModuleContent::Implementation(_) => return Err(unsupported("implementation")),
+                ModuleContent::Region(_) => return Err(unsupported("region")),
+            }
+        }
+
+        let mut header = vec![
+            ("$schema".to_string(), Json::string(JSON_SCHEMA_DIALECT)),
+            (
+                "$id".to_string(),
+                Json::string(&format!("{}.schema.json", module.name())),
+            ),
+            ("title".to_string(), Json::string(module.name().as_ref())),
+        ];
+        if let Some(documentation) = module.documentation() {
+            header.push(("description".to_string(), Json::string(documentation)));
+        }
+
+        writer.write_str("{")?;
+        writer.new_line()?;
+        writer.indent();
+        let header_len = header.len();
+        for (i, (key, value)) in header.iter().enumerate() {
+            write_entry(
+                writer,
+                key,
+                value,
+                i + 1 < header_len || !definitions.is_empty(),
+            )?;
+        }
+        if !definitions.is_empty() {
+            writer.write_str("\"$defs\": {")?;
+            writer.new_line()?;
+            writer.indent();
+            let definitions_len = definitions.len();
+            for (i, (name, schema)) in definitions.iter().enumerate() {
+                writer.begin_element(name);
+                write_entry(writer, name.as_ref(), schema, i + 1 < definitions_len)?;
+                writer.end_element();
+            }
+            writer.outdent();
+            writer.write_str("}")?;
+            writer.new_line()?;
+        }
+        writer.outdent();
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn write_sub_module(&self, _: &mut CodeWriter<W>, _: &Module) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_import(&self, _: &mut CodeWriter<W>, _: &Import) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_comment(&self, _: &mut CodeWriter<W>, _: &Comment) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_structured_type(&self, _: &mut CodeWriter<W>, _: &StructuredType) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_enumeration(&self, _: &mut CodeWriter<W>, _: &Enumeration) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_type_alias(&self, _: &mut CodeWriter<W>, _: &TypeAlias) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ProjectLayout for JsonSchemaLayout {
+    fn module_file_path(&self, namespace: &Namespace) -> PathBuf {
+        let name = namespace
+            .path()
+            .last()
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        PathBuf::from(format!("{}.schema.json", name))
+    }
+
+    fn fingerprint_comment(&self, _: &str) -> Option<String> {
+        None
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Json {
+    fn string(s: &str) -> Self {
+        Json::String(s.to_string())
+    }
+
+    fn object(entries: Vec<(&str, Json)>) -> Self {
+        Json::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    fn type_of(name: &str) -> Self {
+        Json::object(vec![("type", Json::string(name))])
+    }
+
+    fn push(&mut self, key: &str, value: Json) {
+        if let Json::Object(entries) = self {
+            entries.push((key.to_string(), value));
+        }
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, Json::Array(_) | Json::Object(_))
+    }
+
+    fn to_scalar_string(&self) -> String {
+        match self {
+            Json::Boolean(v) => v.to_string(),
+            Json::Number(v) => v.clone(),
+            Json::String(v) => quoted(v),
+            Json::Array(_) | Json::Object(_) => unreachable!(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl References {
+    fn new(module: &Module) -> Self {
+        let mut imported: HashMap<String, String> = Default::default();
+        for content in module.content() {
+            if let ModuleContent::Import(import) = content {
+                if let Some(document) = import.namespace().path().last() {
+                    for item in import.items() {
+                        let local_name = item.alias().as_ref().unwrap_or_else(|| item.name());
+                        let _ = imported.insert(
+                            local_name.to_string(),
+                            format!("{}.schema.json#/$defs/{}", document, item.name()),
+                        );
+                    }
+                }
+            }
+        }
+        Self { imported }
+    }
+
+    fn reference(&self, name: &Identifier) -> Json {
+        let reference = match self.imported.get(name.as_ref()) {
+            Some(reference) => reference.clone(),
+            None => format!("#/$defs/{}", name),
+        };
+        Json::object(vec![("$ref", Json::String(reference))])
+    }
+
+    fn structured_type(&self, record: &StructuredType) -> Result<Json> {
+        not_generic(record)?;
+        let mut schema = described(record);
+        match record.kind() {
+            StructuredTypeKind::Structure
+            | StructuredTypeKind::Class
+            | StructuredTypeKind::Exception => {
+                if !record.extends().is_empty() {
+                    let extends = record
+                        .extends()
+                        .iter()
+                        .map(|vt| self.value_type(vt))
+                        .collect::<Result<Vec<Json>>>()?;
+                    schema.push("allOf", Json::Array(extends));
+                }
+                schema.push("type", Json::string("object"));
+                schema.push("properties", self.properties(record.fields())?);
+                let required: Vec<Json> = record
+                    .fields()
+                    .iter()
+                    .filter(|field| !is_optional_field(field))
+                    .map(|field| Json::string(field.name().as_ref()))
+                    .collect();
+                if !required.is_empty() {
+                    schema.push("required", Json::Array(required));
+                }
+            }
+            StructuredTypeKind::Union => {
+                let alternatives = record
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        Ok(Json::object(vec![
+                            ("type", Json::string("object")),
+                            ("properties", self.properties(std::slice::from_ref(field))?),
+                            (
+                                "required",
+                                Json::Array(vec![Json::string(field.name().as_ref())]),
+                            ),
+                        ]))
+                    })
+                    .collect::<Result<Vec<Json>>>()?;
+                schema.push("oneOf", Json::Array(alternatives));
+            }
+            StructuredTypeKind::Interface => return Err(unsupported("interface")),
+            StructuredTypeKind::Service => return Err(unsupported("service")),
+        }
+        Ok(schema)
+    }
+
+    fn properties(&self, fields: &[Field]) -> Result<Json> {
+        let mut properties: Vec<(String, Json)> = Default::default();
+        for field in fields {
+            let value_type = match field.value_type() {
+                ValueType::Optional(vt) => vt.as_ref(),
+                vt => vt,
+            };
+            let mut schema = described(field);
+            if let Json::Object(entries) = self.value_type(value_type)? {
+                for (key, value) in entries {
+                    schema.push(&key, value);
+                }
+            }
+            if let Some(value) = field.value() {
+                schema.push("default", json_value(value)?);
+            }
+            properties.push((field.name().to_string(), schema));
+        }
+        Ok(Json::Object(properties))
+    }
+
+    fn enumeration(&self, enumeration: &Enumeration) -> Result<Json> {
+        not_generic(enumeration)?;
+        let mut values: Vec<Json> = Default::default();
+        for variant in enumeration.variants() {
+            if variant.has_payload() {
+                return Err(unsupported("enumeration_variant_payload"));
+            }
+            values.push(match variant.value() {
+                None => Json::string(variant.name().as_ref()),
+                Some(value) => json_value(value)?,
+            });
+        }
+        let mut schema = described(enumeration);
+        // Only a list of constants can carry the documentation of each variant.
+        if enumeration
+            .variants()
+            .iter()
+            .any(|variant| variant.documentation().is_some())
+        {
+            let constants = enumeration
+                .variants()
+                .iter()
+                .zip(values)
+                .map(|(variant, value)| {
+                    let mut constant = Json::object(vec![("const", value)]);
+                    if let Some(documentation) = variant.documentation() {
+                        constant.push("description", Json::string(documentation));
+                    }
+                    constant
+                })
+                .collect();
+            schema.push("oneOf", Json::Array(constants));
+        } else {
+            schema.push("enum", Json::Array(values));
+        }
+        Ok(schema)
+    }
+
+    fn type_alias(&self, type_alias: &TypeAlias) -> Result<Json> {
+        not_generic(type_alias)?;
+        self.value_type(type_alias.value_type())
+    }
+
+    fn value_type(&self, vt: &ValueType) -> Result<Json> {
+        Ok(match vt {
+            ValueType::Known(kt) => match kt {
+                KnownType::I8 | KnownType::I16 | KnownType::I32 | KnownType::I64 => {
+                    Json::type_of("integer")
+                }
+                KnownType::U8 | KnownType::U16 | KnownType::U32 | KnownType::U64 => {
+                    Json::object(vec![
+                        ("type", Json::string("integer")),
+                        ("minimum", Json::Number("0".to_string())),
+                    ])
+                }
+                KnownType::F32 | KnownType::F64 => Json::type_of("number"),
+                KnownType::Boolean => Json::type_of("boolean"),
+                KnownType::Char => Json::object(vec![
+                    ("type", Json::string("string")),
+                    ("minLength", Json::Number("1".to_string())),
+                    ("maxLength", Json::Number("1".to_string())),
+                ]),
+                KnownType::String | KnownType::StringSlice => Json::type_of("string"),
+            },
+            ValueType::Reference(name) => self.reference(name),
+            ValueType::Borrowed(_, vt)
+            | ValueType::BorrowedMut(_, vt)
+            | ValueType::Pointer(vt)
+            | ValueType::PointerMut(vt)
+            | ValueType::Boxed(vt) => self.value_type(vt)?,
+            ValueType::Unit => Json::type_of("null"),
+            ValueType::Tuple(vts) => Json::object(vec![
+                ("type", Json::string("array")),
+                (
+                    "prefixItems",
+                    Json::Array(
+                        vts.iter()
+                            .map(|vt| self.value_type(vt))
+                            .collect::<Result<Vec<Json>>>()?,
+                    ),
+                ),
+                ("items", Json::Boolean(false)),
+            ]),
+            ValueType::Array(vt) | ValueType::Slice(vt) => Json::object(vec![
+                ("type", Json::string("array")),
+                ("items", self.value_type(vt)?),
+            ]),
+            ValueType::Set(vt) => Json::object(vec![
+                ("type", Json::string("array")),
+                ("items", self.value_type(vt)?),
+                ("uniqueItems", Json::Boolean(true)),
+            ]),
+            ValueType::FixedArray(vt, size) => Json::object(vec![
+                ("type", Json::string("array")),
+                ("items", self.value_type(vt)?),
+                ("minItems", Json::Number(size.to_string())),
+                ("maxItems", Json::Number(size.to_string())),
+            ]),
+            ValueType::Optional(vt) => Json::object(vec![(
+                "anyOf",
+                Json::Array(vec![self.value_type(vt)?, Json::type_of("null")]),
+            )]),
+            ValueType::Map(kt, vt) => {
+                let mut schema = Json::type_of("object");
+                if let ValueType::Reference(name) = kt.as_ref() {
+                    schema.push("propertyNames", self.reference(name));
+                }
+                schema.push("additionalProperties", self.value_type(vt)?);
+                schema
+            }
+            ValueType::Lifetime(_) => return Err(unsupported("lifetime")),
+            ValueType::Result(_, _) => return Err(unsupported("result")),
+            ValueType::Generic(_, _) => return Err(unsupported("generic_type")),
+            ValueType::Function(_, _) => return Err(unsupported("function_type")),
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn unsupported(kind: &str) -> crate::error::Error {
+    ErrorKind::UnsupportedElementKind(kind.to_string()).into()
+}
+
+fn is_optional_field(field: &Field) -> bool {
+    field.is_optional() || field.value_type().is_optional()
+}
+
+fn described(item: &dyn HasDocumentation) -> Json {
+    match item.documentation() {
+        None => Json::Object(Default::default()),
+        Some(documentation) => Json::object(vec![("description", Json::string(documentation))]),
+    }
+}
+
+fn json_value(value: &Value) -> Result<Json> {
+    Ok(match value {
+        Value::I8(v) => Json::Number(v.to_string()),
+        Value::U8(v) => Json::Number(v.to_string()),
+        Value::I16(v) => Json::Number(v.to_string()),
+        Value::U16(v) => Json::Number(v.to_string()),
+        Value::I32(v) => Json::Number(v.to_string()),
+        Value::U32(v) => Json::Number(v.to_string()),
+        Value::I64(v) => Json::Number(v.to_string()),
+        Value::U64(v) => Json::Number(v.to_string()),
+        Value::F32(v) if v.is_finite() => Json::Number(v.to_string()),
+        Value::F64(v) if v.is_finite() => Json::Number(v.to_string()),
+        Value::F32(_) | Value::F64(_) => return Err(unsupported("non_finite_number")),
+        Value::Boolean(v) => Json::Boolean(*v),
+        Value::Char(v) => Json::String(v.to_string()),
+        Value::String(v) => Json::String(v.clone()),
+        Value::Identifier(v) => Json::String(v.to_string()),
+        Value::Values(vs) => Json::Array(vs.iter().map(json_value).collect::<Result<_>>()?),
+        Value::NamedValues(vs) => {
+            let mut entries = vs
+                .iter()
+                .map(|(k, v)| {
+                    let key = match json_value(k)? {
+                        Json::String(key) => key,
+                        key if key.is_scalar() => key.to_scalar_string(),
+                        _ => return Err(unsupported("object_key")),
+                    };
+                    Ok((key, json_value(v)?))
+                })
+                .collect::<Result<Vec<(String, Json)>>>()?;
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Json::Object(entries)
+        }
+    })
+}
+
+fn write_entry<W: Write>(
+    writer: &mut CodeWriter<W>,
+    key: &str,
+    value: &Json,
+    comma: bool,
+) -> Result<()> {
+    writer.write_str(&format!("{}: ", quoted(key)))?;
+    write_json(writer, value)?;
+    if comma {
+        writer.write_str(",")?;
+    }
+    writer.new_line()
+}
+
+fn write_json<W: Write>(writer: &mut CodeWriter<W>, value: &Json) -> Result<()> {
+    match value {
+        Json::Array(values) if values.iter().all(Json::is_scalar) => writer.write_str(&format!(
+            "[{}]",
+            values
+                .iter()
+                .map(Json::to_scalar_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        )),
+        Json::Array(values) => {
+            writer.write_str("[")?;
+            writer.new_line()?;
+            writer.indent();
+            for (i, value) in values.iter().enumerate() {
+                write_json(writer, value)?;
+                if i + 1 < values.len() {
+                    writer.write_str(",")?;
+                }
+                writer.new_line()?;
+            }
+            writer.outdent();
+            writer.write_str("]")
+        }
+        Json::Object(entries) if entries.is_empty() => writer.write_str("{}"),
+        Json::Object(entries) => {
+            writer.write_str("{")?;
+            writer.new_line()?;
+            writer.indent();
+            for (i, (key, value)) in entries.iter().enumerate() {
+                write_entry(writer, key, value, i + 1 < entries.len())?;
+            }
+            writer.outdent();
+            writer.write_str("}")
+        }
+        scalar => writer.write_str(&scalar.to_scalar_string()),
+    }
+}
+
+fn quoted(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
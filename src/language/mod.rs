@@ -1,3 +1,15 @@
+pub mod go;
+
+mod helpers;
+
+pub mod json_schema;
+
+pub mod protobuf;
+
+pub mod python;
+
 pub mod rust;
 
 pub mod thrift;
+
+pub mod typescript;
@@ -0,0 +1,501 @@
+/*!
+Writes modules as Protocol Buffers (proto3) files.
+
+Structures, classes and exceptions are written as messages, unions as a message holding a single
+`oneof`, and services as `service` with one `rpc` per method; each rpc takes a single message and
+returns a message. Every module is written to its own file with its full namespace, optionally
+prefixed, as its package.
+
+# Example
+
+```rust
+use code_writer::language::protobuf::ProtobufWriter;
+use code_writer::model::{Builder, Field, HasFieldId, Identifier, Module, StructuredType, ValueType};
+use code_writer::writer::module_to_string;
+
+let module = Module::new(Identifier::new("address"))
+    .add_structure(
+        StructuredType::structure(Identifier::new("Country"))
+            .add_field(
+                Field::new(Identifier::new("code"), ValueType::string())
+                    .set_field_id(1)
+                    .build(),
+            )
+            .build(),
+    )
+    .build();
+
+assert_eq!(
+    module_to_string(&ProtobufWriter::default(), &module).unwrap(),
+    r#"syntax = "proto3";
+
+package address;
+
+message Country {
+    string code = 1;
+}
+"#
+);
+```
+*/
+
+use crate::error::{ErrorKind, Result};
+use crate::language::helpers::{field_ids, integer_value, not_generic};
+use crate::model::identity::Namespace;
+use crate::model::{
+    Comment, Enumeration, Field, FunctionDecl, HasDocumentation, HasFieldId, HasName,
+    HasOptionalType, HasOptionalValue, HasType, Identifier, Import, IsOptional, KnownType, Module,
+    ProtectedRegion, StructuredType, StructuredTypeKind, Value, ValueType,
+};
+use crate::project::ProjectLayout;
+use crate::writer::{CodeWriter, DocCommentStyle, ModuleWriter};
+use std::io::Write;
+use std::path::PathBuf;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Default)]
+pub struct ProtobufWriter {
+    package_prefix: Option<Namespace>,
+    positional_field_ids: bool,
+}
+
+/// Every module is written to `a/b/name.proto` for its namespace `a.b.name`; imports use this path
+/// from the project root, which is expected to be on the import path.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ProtobufLayout {}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const MAX_FIELD_NUMBER: u32 = 536_870_911;
+
+const RESERVED_FIELD_NUMBERS: std::ops::RangeInclusive<u32> = 19_000..=19_999;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<W> ModuleWriter<W> for ProtobufWriter
+where
+    W: Write,
+{
+    fn write_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        self.write_documentation(writer, module)?;
+        writer.write_str("syntax = \"proto3\";")?;
+        writer.new_line()?;
+        writer.blank_line()?;
+        let namespace = match writer.namespace() {
+            None => module.name().to_string(),
+            Some(namespace) => namespace.join("."),
+        };
+        let package = match &self.package_prefix {
+            None => namespace,
+            Some(prefix) => format!("{}.{}", prefix.join("."), namespace),
+        };
+        writer.write_str(&format!("package {};", package))?;
+        writer.new_line()
+    }
+
+    fn write_sub_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        let namespace = match writer.namespace() {
+            None => Namespace::new(vec![module.name().clone()]),
+            Some(namespace) => namespace.with(module.name().clone()),
+        };
+        self.write_proto_import(writer, &namespace)
+    }
+
+    fn write_import(&self, writer: &mut CodeWriter<W>, import: &Import) -> Result<()> {
+        if import.namespace().path().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("empty_import".to_string()).into());
+        }
+        self.write_proto_import(writer, import.namespace())
+    }
+
+    fn write_comment(&self, writer: &mut CodeWriter<W>, comment: &Comment) -> Result<()> {
+        if comment.is_line() {
+            for line in comment.text().split('\n') {
+                writer.write_str(&format!("// {}", line))?;
+                writer.new_line()?;
+            }
+            Ok(())
+        } else {
+            writer.write_str("/*")?;
+            writer.new_line()?;
+            writer.write_str(comment.text())?;
+            writer.new_line()?;
+            writer.write_str("*/")?;
+            writer.new_line()
+        }
+    }
+
+    fn write_structured_type(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        match record.kind() {
+            StructuredTypeKind::Structure
+            | StructuredTypeKind::Class
+            | StructuredTypeKind::Exception => self.write_message(writer, record),
+            StructuredTypeKind::Union => self.write_message(writer, record),
+            StructuredTypeKind::Interface => {
+                Err(ErrorKind::UnsupportedElementKind("interface".to_string()).into())
+            }
+            StructuredTypeKind::Service => self.write_service(writer, record),
+        }
+    }
+
+    fn write_enumeration(
+        &self,
+        writer: &mut CodeWriter<W>,
+        enumeration: &Enumeration,
+    ) -> Result<()> {
+        not_generic(enumeration)?;
+        let mut values: Vec<i64> = Default::default();
+        let mut next = 0;
+        for variant in enumeration.variants() {
+            if variant.has_payload() {
+                return Err(ErrorKind::UnsupportedElementKind(
+                    "enumeration_variant_payload".to_string(),
+                )
+                .into());
+            }
+            let value = match variant.value() {
+                None => next,
+                Some(value) => enumeration_value(value)?,
+            };
+            values.push(value);
+            next = value + 1;
+        }
+        // proto3 requires the first value to be zero; use the variant with value zero if there is
+        // one, otherwise add an unspecified value.
+        let zero = values.iter().position(|value| *value == 0);
+
+        self.write_documentation(writer, enumeration)?;
+        writer.write_str(&format!("enum {} {{", enumeration.name()))?;
+        writer.new_line()?;
+        writer.indent();
+        if zero.is_none() {
+            writer.write_str(&format!(
+                "{}_UNSPECIFIED = 0;",
+                screaming_snake_case(enumeration.name().as_ref())
+            ))?;
+            writer.new_line()?;
+        }
+        let order = zero
+            .into_iter()
+            .chain((0..values.len()).filter(|i| Some(*i) != zero));
+        for i in order {
+            let variant = &enumeration.variants()[i];
+            writer.begin_element(variant.name());
+            self.write_documentation(writer, variant)?;
+            writer.write_str(&format!("{} = {};", variant.name(), values[i]))?;
+            writer.new_line()?;
+            writer.end_element();
+        }
+        writer.outdent();
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn write_region(&self, writer: &mut CodeWriter<W>, region: &ProtectedRegion) -> Result<()> {
+        writer.write_region_markers("// ", region.name().as_ref())
+    }
+}
+
+impl ProjectLayout for ProtobufLayout {
+    fn module_file_path(&self, namespace: &Namespace) -> PathBuf {
+        let mut path: PathBuf = namespace.path().iter().map(|id| id.as_ref()).collect();
+        let _ = path.set_extension("proto");
+        path
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ProtobufWriter {
+    pub fn with_positional_field_ids() -> Self {
+        Self {
+            positional_field_ids: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn package_prefix(&self) -> &Option<Namespace> {
+        &self.package_prefix
+    }
+
+    /// Prefix the package of every module, for example `com.example`.
+    pub fn set_package_prefix(&mut self, package_prefix: Namespace) -> &mut Self {
+        self.package_prefix = Some(package_prefix);
+        self
+    }
+
+    pub fn positional_field_ids(&self) -> bool {
+        self.positional_field_ids
+    }
+
+    fn write_proto_import<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        namespace: &Namespace,
+    ) -> Result<()> {
+        let path: Vec<String> = ProtobufLayout::default()
+            .module_file_path(namespace)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        writer.write_str(&format!("import \"{}\";", path.join("/")))?;
+        writer.new_line()
+    }
+
+    fn write_message<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        not_generic(record)?;
+        if !record.methods().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("message_method".to_string()).into());
+        }
+        let is_union = *record.kind() == StructuredTypeKind::Union;
+        self.write_documentation(writer, record)?;
+        writer.write_str(&format!("message {} {{", record.name()))?;
+        if !record.fields().is_empty() {
+            writer.new_line()?;
+            writer.indent();
+            if is_union {
+                writer.write_str("oneof value {")?;
+                writer.new_line()?;
+                writer.indent();
+            }
+            let field_ids = self.field_ids(record.name(), record.fields())?;
+            for (member, field_id) in record.fields().iter().zip(field_ids) {
+                writer.begin_element(member.name());
+                self.write_documentation(writer, member)?;
+                writer.write_str(&format!(
+                    "{} {} = {};",
+                    self.field_type(member, is_union)?,
+                    member.name(),
+                    field_id
+                ))?;
+                writer.new_line()?;
+                writer.end_element();
+            }
+            if is_union {
+                writer.outdent();
+                writer.write_str("}")?;
+                writer.new_line()?;
+            }
+            writer.outdent();
+        }
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn field_type(&self, member: &Field, in_oneof: bool) -> Result<String> {
+        if member.has_value() {
+            return Err(ErrorKind::UnsupportedElementKind("field_default".to_string()).into());
+        }
+        let (optional, value_type) = match member.value_type() {
+            ValueType::Optional(vt) => (true, vt.as_ref()),
+            vt => (member.is_optional(), vt),
+        };
+        let (label, type_string) = match value_type {
+            ValueType::Array(vt) | ValueType::Slice(vt) | ValueType::Set(vt)
+                if !matches!(vt.as_ref(), ValueType::Known(KnownType::U8)) =>
+            {
+                ("repeated ", scalar_type_string(vt)?)
+            }
+            ValueType::FixedArray(vt, _) => ("repeated ", scalar_type_string(vt)?),
+            ValueType::Map(kt, vt) => ("", map_type_string(kt, vt)?),
+            vt if optional => ("optional ", scalar_type_string(vt)?),
+            vt => ("", scalar_type_string(vt)?),
+        };
+        if in_oneof && !label.is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("oneof_label".to_string()).into());
+        }
+        if in_oneof && type_string.starts_with("map<") {
+            return Err(ErrorKind::UnsupportedElementKind("oneof_map".to_string()).into());
+        }
+        Ok(format!("{}{}", label, type_string))
+    }
+
+    fn field_ids(&self, owner: &Identifier, fields: &[Field]) -> Result<Vec<u32>> {
+        field_ids(
+            owner,
+            fields.iter().map(|f| (f.name(), f.field_id())),
+            self.positional_field_ids,
+            |field_id| {
+                field_id != 0
+                    && field_id <= MAX_FIELD_NUMBER
+                    && !RESERVED_FIELD_NUMBERS.contains(&field_id)
+            },
+        )
+    }
+
+    fn write_service<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        not_generic(record)?;
+        if !record.fields().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("service_field".to_string()).into());
+        }
+        if !record.extends().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("service_extends".to_string()).into());
+        }
+        self.write_documentation(writer, record)?;
+        writer.write_str(&format!("service {} {{", record.name()))?;
+        if !record.methods().is_empty() {
+            writer.new_line()?;
+            writer.indent();
+            for method in record.methods() {
+                writer.begin_element(method.name());
+                self.write_documentation(writer, method)?;
+                writer.write_str(&self.rpc_signature(method)?)?;
+                writer.new_line()?;
+                writer.end_element();
+            }
+            writer.outdent();
+        }
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn rpc_signature(&self, method: &FunctionDecl) -> Result<String> {
+        not_generic(method)?;
+        if method.has_body() {
+            return Err(ErrorKind::UnsupportedElementKind("function_body".to_string()).into());
+        }
+        if method.is_oneway() || !method.throws().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("rpc_exceptions".to_string()).into());
+        }
+        match (method.parameters().as_slice(), method.value_type()) {
+            ([parameter], Some(ValueType::Reference(response))) => match parameter.value_type() {
+                ValueType::Reference(request) => Ok(format!(
+                    "rpc {}({}) returns ({});",
+                    method.name(),
+                    request,
+                    response
+                )),
+                _ => Err(ErrorKind::UnsupportedElementKind("rpc_signature".to_string()).into()),
+            },
+            _ => Err(ErrorKind::UnsupportedElementKind("rpc_signature".to_string()).into()),
+        }
+    }
+
+    fn write_documentation<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasDocumentation,
+    ) -> Result<()> {
+        if let Some(documentation) = item.documentation() {
+            match writer.policy().doc_comment_style() {
+                DocCommentStyle::Block => {
+                    writer.write_str("/**")?;
+                    writer.new_line()?;
+                    writer.write_doc_text("", documentation)?;
+                    writer.write_str("*/")?;
+                    writer.new_line()?;
+                }
+                DocCommentStyle::Line => writer.write_doc_text("// ", documentation)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn scalar_type_string(vt: &ValueType) -> Result<String> {
+    Ok(match vt {
+        ValueType::Known(kt) => match kt {
+            KnownType::I8 | KnownType::I16 | KnownType::I32 => "int32",
+            KnownType::U8 | KnownType::U16 | KnownType::U32 => "uint32",
+            KnownType::I64 => "int64",
+            KnownType::U64 => "uint64",
+            KnownType::F32 => "float",
+            KnownType::F64 => "double",
+            KnownType::Boolean => "bool",
+            KnownType::Char | KnownType::String | KnownType::StringSlice => "string",
+        }
+        .to_string(),
+        ValueType::Reference(t) => t.to_string(),
+        ValueType::Borrowed(_, t)
+        | ValueType::BorrowedMut(_, t)
+        | ValueType::Pointer(t)
+        | ValueType::PointerMut(t)
+        | ValueType::Boxed(t) => scalar_type_string(t)?,
+        ValueType::Array(t) | ValueType::Slice(t)
+            if matches!(t.as_ref(), ValueType::Known(KnownType::U8)) =>
+        {
+            "bytes".to_string()
+        }
+        ValueType::Array(_)
+        | ValueType::Slice(_)
+        | ValueType::Set(_)
+        | ValueType::FixedArray(_, _)
+        | ValueType::Map(_, _)
+        | ValueType::Optional(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("nested_collection".to_string()).into())
+        }
+        ValueType::Lifetime(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("lifetime".to_string()).into())
+        }
+        ValueType::Unit => return Err(ErrorKind::UnsupportedElementKind("unit".to_string()).into()),
+        ValueType::Tuple(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("tuple".to_string()).into())
+        }
+        ValueType::Result(_, _) => {
+            return Err(ErrorKind::UnsupportedElementKind("result".to_string()).into())
+        }
+        ValueType::Generic(_, _) => {
+            return Err(ErrorKind::UnsupportedElementKind("generic_type".to_string()).into())
+        }
+        ValueType::Function(_, _) => {
+            return Err(ErrorKind::UnsupportedElementKind("function_type".to_string()).into())
+        }
+    })
+}
+
+fn map_type_string(kt: &ValueType, vt: &ValueType) -> Result<String> {
+    let key = scalar_type_string(kt)?;
+    match key.as_str() {
+        "int32" | "uint32" | "int64" | "uint64" | "bool" | "string" => {
+            Ok(format!("map<{}, {}>", key, scalar_type_string(vt)?))
+        }
+        _ => Err(ErrorKind::UnsupportedElementKind("map_key".to_string()).into()),
+    }
+}
+
+fn enumeration_value(value: &Value) -> Result<i64> {
+    match integer_value(value) {
+        Some(value) if value >= i32::MIN as i64 && value <= i32::MAX as i64 => Ok(value),
+        _ => Err(ErrorKind::UnsupportedElementKind("enumeration_value".to_string()).into()),
+    }
+}
+
+fn screaming_snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && previous_lower {
+            result.push('_');
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        result.extend(c.to_uppercase());
+    }
+    result
+}
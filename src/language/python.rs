@@ -0,0 +1,854 @@
+/*!
+Writes modules as Python source, using dataclasses and type hints.
+
+Structures, classes and exceptions are written as `@dataclass` classes, and unions as a dataclass in
+which every field is optional. The interface and service kinds are written as `typing.Protocol`
+classes and enumerations as `enum.Enum` subclasses. Functions and methods are written as stubs
+whose body is `...`. Imports from `dataclasses`, `enum` and `typing` are added to the module as
+required.
+
+# Example
+
+```rust
+use code_writer::language::python::PythonWriter;
+use code_writer::model::{Builder, Field, Identifier, IsOptional, Module, StructuredType, ValueType};
+use code_writer::writer::module_to_string;
+
+let module = Module::new(Identifier::new("address"))
+    .add_structure(
+        StructuredType::structure(Identifier::new("Country"))
+            .add_field(Field::new(Identifier::new("code"), ValueType::string()).build())
+            .add_field(Field::new(Identifier::new("name"), ValueType::string()).optional().build())
+            .build(),
+    )
+    .build();
+
+assert_eq!(
+    module_to_string(&PythonWriter::default(), &module).unwrap(),
+    r#"from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import Optional
+
+@dataclass
+class Country:
+    code: str
+    name: Optional[str] = None
+"#
+);
+```
+*/
+
+use crate::error::{ErrorKind, Result};
+use crate::language::helpers::{not_generic, string_literal};
+use crate::model::identity::Namespace;
+use crate::model::visitor::{walk_module, Visitor, Walk};
+use crate::model::{
+    Comment, Enumeration, EnumerationVariant, Field, FunctionDecl, HasDocumentation,
+    HasGenericParameters, HasName, HasOptionalType, HasOptionalValue, HasType, HasValue, Import,
+    IsOptional, KnownType, Module, NamedValue, Parameter, ProtectedRegion, StructuredType,
+    StructuredTypeKind, TypeAlias, Value, ValueType,
+};
+use crate::project::{ProjectLayout, FINGERPRINT_MARKER};
+use crate::writer::{Block, CodeWriter, List, ListWrapping, ModuleWriter};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::PathBuf;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Default)]
+pub struct PythonWriter {}
+
+/// Every module is written as a package, `__init__.py` in a directory for its namespace.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct PythonLayout {}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+/// Collects the names a module must import from the standard library, by module.
+#[derive(Default)]
+struct StandardImports {
+    imports: BTreeMap<&'static str, BTreeSet<&'static str>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<W> ModuleWriter<W> for PythonWriter
+where
+    W: Write,
+{
+    fn write_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        if let Some(documentation) = module.documentation() {
+            self.write_docstring(writer, documentation)?;
+            writer.blank_line()?;
+        }
+        writer.write_str("from __future__ import annotations")?;
+        writer.new_line()?;
+
+        let mut standard_imports = StandardImports::default();
+        walk_module(&mut standard_imports, module)?;
+        if !standard_imports.imports.is_empty() {
+            writer.blank_line()?;
+            for (module_name, names) in standard_imports.imports {
+                writer.write_str(&format!(
+                    "from {} import {}",
+                    module_name,
+                    names.into_iter().collect::<Vec<&str>>().join(", ")
+                ))?;
+                writer.new_line()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_sub_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        writer.write_str(&format!("from . import {}", module.name()))?;
+        writer.new_line()
+    }
+
+    fn write_import(&self, writer: &mut CodeWriter<W>, import: &Import) -> Result<()> {
+        let module_name = import.namespace().join(".");
+        if import.items().is_empty() {
+            writer.write_str(&format!("import {}", module_name))?;
+        } else {
+            let items: Vec<String> = import
+                .items()
+                .iter()
+                .map(|item| match item.alias() {
+                    None => item.name().to_string(),
+                    Some(alias) => format!("{} as {}", item.name(), alias),
+                })
+                .collect();
+            let single_line = format!("from {} import {}", module_name, items.join(", "));
+            if writer.fits(&single_line) {
+                writer.write_str(&single_line)?;
+            } else {
+                writer.write_str(&format!("from {} import ", module_name))?;
+                writer.write_list(&List::new("(", ")", ListWrapping::OnePerLine), &items)?;
+            }
+        }
+        writer.new_line()
+    }
+
+    fn write_comment(&self, writer: &mut CodeWriter<W>, comment: &Comment) -> Result<()> {
+        writer.write_doc_text("# ", comment.text())
+    }
+
+    fn write_structured_type(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        if !record.associated_types().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("associated_type".to_string()).into());
+        }
+        if !record.associated_constants().is_empty() {
+            return Err(
+                ErrorKind::UnsupportedElementKind("associated_constant".to_string()).into(),
+            );
+        }
+        let type_variables = self.write_type_variables(writer, record)?;
+        let mut bases = value_types(record.extends())?;
+        let is_protocol = match record.kind() {
+            StructuredTypeKind::Interface | StructuredTypeKind::Service => {
+                if type_variables.is_empty() {
+                    bases.push("Protocol".to_string());
+                } else {
+                    bases.push(format!("Protocol[{}]", type_variables.join(", ")));
+                }
+                true
+            }
+            kind => {
+                if *kind == StructuredTypeKind::Exception && bases.is_empty() {
+                    bases.push("Exception".to_string());
+                }
+                if !type_variables.is_empty() {
+                    bases.push(format!("Generic[{}]", type_variables.join(", ")));
+                }
+                false
+            }
+        };
+        let is_union = *record.kind() == StructuredTypeKind::Union;
+        let fields = record
+            .fields()
+            .iter()
+            .map(|field| {
+                if is_protocol {
+                    Ok((annotation(field.value_type(), field.is_optional())?, None))
+                } else {
+                    field_declaration(field, is_union)
+                }
+            })
+            .collect::<Result<Vec<(String, Option<String>)>>>()?;
+
+        if !is_protocol {
+            // A field without a default may only follow one with a default if fields are
+            // keyword-only.
+            let first_default = fields.iter().position(|(_, default)| default.is_some());
+            let keyword_only = match first_default {
+                None => false,
+                Some(i) => fields[i..].iter().any(|(_, default)| default.is_none()),
+            };
+            writer.write_str(if keyword_only {
+                "@dataclass(kw_only=True)"
+            } else {
+                "@dataclass"
+            })?;
+            writer.new_line()?;
+        }
+        writer.write_str(&format!("class {}", record.name()))?;
+        if !bases.is_empty() {
+            writer.write_str(&format!("({})", bases.join(", ")))?;
+        }
+        let block = Block::indentation(":");
+        writer.open_block(&block)?;
+        let mut is_empty = true;
+        if let Some(documentation) = record.documentation() {
+            self.write_docstring(writer, documentation)?;
+            if !record.fields().is_empty() {
+                writer.blank_line()?;
+            }
+            is_empty = false;
+        }
+        for (field, (annotation, default)) in record.fields().iter().zip(fields) {
+            is_empty = false;
+            writer.begin_element(field.name());
+            self.write_attribute_doc(writer, field)?;
+            match default {
+                None => writer.write_str(&format!("{}: {}", field.name(), annotation))?,
+                Some(default) => {
+                    writer.write_str(&format!("{}: {} = {}", field.name(), annotation, default))?
+                }
+            }
+            writer.new_line()?;
+            writer.end_element();
+        }
+        for method in record.methods() {
+            if !is_empty {
+                writer.blank_line()?;
+            }
+            is_empty = false;
+            writer.begin_element(method.name());
+            self.write_function(writer, method, true)?;
+            writer.end_element();
+        }
+        if is_empty {
+            writer.write_str("pass")?;
+        }
+        writer.close_block(&block)
+    }
+
+    fn write_enumeration(
+        &self,
+        writer: &mut CodeWriter<W>,
+        enumeration: &Enumeration,
+    ) -> Result<()> {
+        not_generic(enumeration)?;
+        writer.write_str(&format!("class {}(Enum)", enumeration.name()))?;
+        let block = Block::indentation(":");
+        writer.open_block(&block)?;
+        if let Some(documentation) = enumeration.documentation() {
+            self.write_docstring(writer, documentation)?;
+            if !enumeration.variants().is_empty() {
+                writer.blank_line()?;
+            }
+        }
+        for variant in enumeration.variants() {
+            if variant.has_payload() {
+                return Err(ErrorKind::UnsupportedElementKind(
+                    "enumeration_variant_payload".to_string(),
+                )
+                .into());
+            }
+            writer.begin_element(variant.name());
+            self.write_attribute_doc(writer, variant)?;
+            writer.write_str(&format!(
+                "{} = {}",
+                variant.name(),
+                match variant.value() {
+                    None => "auto()".to_string(),
+                    Some(value) => literal(value),
+                }
+            ))?;
+            writer.new_line()?;
+            writer.end_element();
+        }
+        if enumeration.variants().is_empty() && !enumeration.has_documentation() {
+            writer.write_str("pass")?;
+        }
+        writer.close_block(&block)
+    }
+
+    fn write_constant(&self, writer: &mut CodeWriter<W>, constant: &NamedValue) -> Result<()> {
+        self.write_attribute_doc(writer, constant)?;
+        writer.write_str(&format!(
+            "{}: Final[{}] = {}",
+            constant.name(),
+            value_type(constant.value_type())?,
+            literal(constant.value())
+        ))?;
+        writer.new_line()
+    }
+
+    fn write_variable(&self, writer: &mut CodeWriter<W>, variable: &NamedValue) -> Result<()> {
+        self.write_attribute_doc(writer, variable)?;
+        writer.write_str(&format!(
+            "{}: {} = {}",
+            variable.name(),
+            value_type(variable.value_type())?,
+            literal(variable.value())
+        ))?;
+        writer.new_line()
+    }
+
+    fn write_function_decl(
+        &self,
+        writer: &mut CodeWriter<W>,
+        function_decl: &FunctionDecl,
+    ) -> Result<()> {
+        let _ = self.write_type_variables(writer, function_decl)?;
+        self.write_function(writer, function_decl, false)
+    }
+
+    fn write_type_alias(&self, writer: &mut CodeWriter<W>, type_alias: &TypeAlias) -> Result<()> {
+        not_generic(type_alias)?;
+        writer.write_str(&format!(
+            "{}: TypeAlias = {}",
+            type_alias.name(),
+            value_type(type_alias.value_type())?
+        ))?;
+        writer.new_line()
+    }
+
+    fn write_region(&self, writer: &mut CodeWriter<W>, region: &ProtectedRegion) -> Result<()> {
+        writer.write_region_markers("# ", region.name().as_ref())
+    }
+}
+
+impl ProjectLayout for PythonLayout {
+    fn module_file_path(&self, namespace: &Namespace) -> PathBuf {
+        let mut path: PathBuf = namespace.path().iter().map(|id| id.as_ref()).collect();
+        path.push("__init__.py");
+        path
+    }
+
+    fn fingerprint_comment(&self, fingerprint: &str) -> Option<String> {
+        Some(format!("# {} {}", FINGERPRINT_MARKER, fingerprint))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl PythonWriter {
+    fn write_function<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        function: &FunctionDecl,
+        in_class: bool,
+    ) -> Result<()> {
+        if function.has_body() {
+            return Err(ErrorKind::UnsupportedElementKind("function_body".to_string()).into());
+        }
+        let mut parameters: Vec<String> = Default::default();
+        if in_class {
+            if function.is_method() {
+                parameters.push("self".to_string());
+            } else {
+                writer.write_str("@staticmethod")?;
+                writer.new_line()?;
+            }
+        }
+        let declarations = function
+            .parameters()
+            .iter()
+            .map(parameter_declaration)
+            .collect::<Result<Vec<(String, bool)>>>()?;
+        // A parameter without a default may only follow one with a default if both are
+        // keyword-only.
+        let first_default = declarations.iter().position(|(_, default)| *default);
+        for (i, (declaration, _)) in declarations.iter().enumerate() {
+            if Some(i) == first_default && declarations[i..].iter().any(|(_, default)| !default) {
+                parameters.push("*".to_string());
+            }
+            parameters.push(declaration.clone());
+        }
+        let return_type = match function.value_type() {
+            None => "None".to_string(),
+            Some(vt) => value_type(vt)?,
+        };
+
+        writer.write_str(&format!("def {}", function.name()))?;
+        writer.write_list_then(
+            &List::new("(", ")", ListWrapping::OnePerLine),
+            &parameters,
+            &format!(" -> {}", return_type),
+        )?;
+        let block = Block::indentation(":");
+        writer.open_block(&block)?;
+        self.write_function_docstring(writer, function)?;
+        writer.write_str("...")?;
+        writer.close_block(&block)
+    }
+
+    fn write_type_variables<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasGenericParameters,
+    ) -> Result<Vec<String>> {
+        if item.has_where_predicates() {
+            return Err(ErrorKind::UnsupportedElementKind("where_clause".to_string()).into());
+        }
+        let mut names: Vec<String> = Default::default();
+        for parameter in item
+            .generic_parameters()
+            .iter()
+            .filter(|parameter| !parameter.is_lifetime())
+        {
+            if parameter.has_default() {
+                return Err(ErrorKind::UnsupportedElementKind(
+                    "generic_parameter_default".to_string(),
+                )
+                .into());
+            }
+            let bounds: Vec<&ValueType> = parameter
+                .bounds()
+                .iter()
+                .filter(|bound| !matches!(bound, ValueType::Lifetime(_)))
+                .collect();
+            match bounds.as_slice() {
+                [] => writer.write_str(&format!(
+                    "{} = TypeVar(\"{}\")",
+                    parameter.name(),
+                    parameter.name()
+                ))?,
+                [bound] => writer.write_str(&format!(
+                    "{} = TypeVar(\"{}\", bound={})",
+                    parameter.name(),
+                    parameter.name(),
+                    value_type(bound)?
+                ))?,
+                _ => {
+                    return Err(
+                        ErrorKind::UnsupportedElementKind("multiple_bounds".to_string()).into(),
+                    )
+                }
+            }
+            writer.new_line()?;
+            names.push(parameter.name().to_string());
+        }
+        if !names.is_empty() {
+            writer.blank_line()?;
+        }
+        Ok(names)
+    }
+
+    fn write_function_docstring<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        function: &FunctionDecl,
+    ) -> Result<()> {
+        let mut sections: Vec<String> = Default::default();
+        let arguments: Vec<String> = function
+            .parameters()
+            .iter()
+            .filter_map(|parameter| {
+                parameter
+                    .documentation()
+                    .as_ref()
+                    .map(|documentation| format!("    {}: {}", parameter.name(), documentation))
+            })
+            .collect();
+        if !arguments.is_empty() {
+            sections.push(format!("Args:\n{}", arguments.join("\n")));
+        }
+        let raises = function
+            .throws()
+            .iter()
+            .map(|throws| {
+                let exception = value_type(throws.value_type())?;
+                Ok(match throws.documentation() {
+                    None => format!("    {}", exception),
+                    Some(documentation) => format!("    {}: {}", exception, documentation),
+                })
+            })
+            .collect::<Result<Vec<String>>>()?;
+        if !raises.is_empty() {
+            sections.push(format!("Raises:\n{}", raises.join("\n")));
+        }
+        match function.documentation() {
+            None if sections.is_empty() => Ok(()),
+            None => self.write_docstring(writer, &sections.join("\n\n")),
+            Some(documentation) => {
+                sections.insert(0, documentation.trim().to_string());
+                self.write_docstring(writer, &sections.join("\n\n"))
+            }
+        }
+    }
+
+    fn write_docstring<W: Write>(&self, writer: &mut CodeWriter<W>, text: &str) -> Result<()> {
+        let text = docstring_text(text);
+        let single_line = format!("\"\"\"{}\"\"\"", text);
+        if !text.contains('\n') && writer.fits(&single_line) {
+            writer.write_str(&single_line)?;
+            return writer.new_line();
+        }
+        writer.write_str("\"\"\"")?;
+        for line in text.split('\n') {
+            writer.write_str(line)?;
+            writer.new_line()?;
+        }
+        writer.write_str("\"\"\"")?;
+        writer.new_line()
+    }
+
+    fn write_attribute_doc<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasDocumentation,
+    ) -> Result<()> {
+        if let Some(documentation) = item.documentation() {
+            writer.write_doc_text("#: ", documentation)?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Visitor for StandardImports {
+    fn enter_module(&mut self, namespace: &Namespace, _: &Module) -> Result<Walk> {
+        Ok(if namespace.path().len() == 1 {
+            Walk::Continue
+        } else {
+            Walk::SkipChildren
+        })
+    }
+
+    fn visit_constant(&mut self, constant: &NamedValue) -> Result<()> {
+        self.add("typing", "Final");
+        self.add_value_type(constant.value_type());
+        Ok(())
+    }
+
+    fn visit_variable(&mut self, variable: &NamedValue) -> Result<()> {
+        self.add_value_type(variable.value_type());
+        Ok(())
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) -> Result<()> {
+        self.add("typing", "TypeAlias");
+        self.add_value_type(type_alias.value_type());
+        Ok(())
+    }
+
+    fn enter_structured_type(&mut self, structured_type: &StructuredType) -> Result<Walk> {
+        let is_generic = structured_type
+            .generic_parameters()
+            .iter()
+            .any(|parameter| !parameter.is_lifetime());
+        match structured_type.kind() {
+            StructuredTypeKind::Interface | StructuredTypeKind::Service => {
+                self.add("typing", "Protocol")
+            }
+            kind => {
+                self.add("dataclasses", "dataclass");
+                if *kind == StructuredTypeKind::Union {
+                    self.add("typing", "Optional");
+                }
+                if is_generic {
+                    self.add("typing", "Generic");
+                }
+            }
+        }
+        if is_generic {
+            self.add("typing", "TypeVar");
+        }
+        for extends in structured_type.extends() {
+            self.add_value_type(extends);
+        }
+        Ok(Walk::Continue)
+    }
+
+    fn enter_enumeration(&mut self, _: &Enumeration) -> Result<Walk> {
+        self.add("enum", "Enum");
+        Ok(Walk::Continue)
+    }
+
+    fn enter_variant(&mut self, variant: &EnumerationVariant) -> Result<Walk> {
+        if !variant.has_value() {
+            self.add("enum", "auto");
+        }
+        Ok(Walk::Continue)
+    }
+
+    fn enter_field(&mut self, field: &Field) -> Result<()> {
+        if field.is_optional() {
+            self.add("typing", "Optional");
+        }
+        if let Some(Value::Values(_)) | Some(Value::NamedValues(_)) = field.value() {
+            self.add("dataclasses", "field");
+        }
+        self.add_value_type(field.value_type());
+        Ok(())
+    }
+
+    fn enter_function(&mut self, function: &FunctionDecl) -> Result<Walk> {
+        if function
+            .generic_parameters()
+            .iter()
+            .any(|parameter| !parameter.is_lifetime())
+        {
+            self.add("typing", "TypeVar");
+        }
+        if let Some(vt) = function.value_type() {
+            self.add_value_type(vt);
+        }
+        Ok(Walk::Continue)
+    }
+
+    fn enter_parameter(&mut self, parameter: &Parameter) -> Result<()> {
+        if parameter.is_optional() {
+            self.add("typing", "Optional");
+        }
+        self.add_value_type(parameter.value_type());
+        Ok(())
+    }
+}
+
+impl StandardImports {
+    fn add(&mut self, module_name: &'static str, name: &'static str) {
+        let _ = self.imports.entry(module_name).or_default().insert(name);
+    }
+
+    fn add_value_type(&mut self, vt: &ValueType) {
+        match vt {
+            ValueType::Optional(vt) => {
+                self.add("typing", "Optional");
+                self.add_value_type(vt);
+            }
+            ValueType::Function(pts, rt) => {
+                self.add("typing", "Callable");
+                for pt in pts {
+                    self.add_value_type(pt);
+                }
+                if let Some(rt) = rt {
+                    self.add_value_type(rt);
+                }
+            }
+            ValueType::Borrowed(_, vt)
+            | ValueType::BorrowedMut(_, vt)
+            | ValueType::Pointer(vt)
+            | ValueType::PointerMut(vt)
+            | ValueType::Boxed(vt)
+            | ValueType::Slice(vt)
+            | ValueType::FixedArray(vt, _)
+            | ValueType::Array(vt)
+            | ValueType::Set(vt) => self.add_value_type(vt),
+            ValueType::Map(kt, vt) | ValueType::Result(kt, vt) => {
+                self.add_value_type(kt);
+                self.add_value_type(vt);
+            }
+            ValueType::Tuple(vts) | ValueType::Generic(_, vts) => {
+                for vt in vts {
+                    self.add_value_type(vt);
+                }
+            }
+            ValueType::Known(_)
+            | ValueType::Reference(_)
+            | ValueType::Lifetime(_)
+            | ValueType::Unit => {}
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The trimmed text with backslashes escaped, as are quotes that could close the docstring early:
+/// those in a run of three or more, and those that would run into the closing delimiter.
+///
+fn docstring_text(text: &str) -> String {
+    let mut rest = text.trim();
+    let mut escaped = String::with_capacity(rest.len());
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '"' => {
+                let run = rest.len() - rest.trim_start_matches('"').len();
+                if run >= 3 || run == rest.len() {
+                    escaped.push_str(&"\\\"".repeat(run));
+                } else {
+                    escaped.push_str(&rest[..run]);
+                }
+                run
+            }
+            '\\' => {
+                escaped.push_str("\\\\");
+                1
+            }
+            _ => {
+                escaped.push(c);
+                c.len_utf8()
+            }
+        };
+        rest = &rest[len..];
+    }
+    escaped
+}
+
+/// The annotation and default, if any, for a dataclass field.
+fn field_declaration(field: &Field, is_union: bool) -> Result<(String, Option<String>)> {
+    let optional = is_union || field.is_optional();
+    let annotation = annotation(field.value_type(), optional)?;
+    let default = match field.value() {
+        Some(Value::Values(vs)) if vs.is_empty() => Some("field(default_factory=list)".to_string()),
+        Some(Value::NamedValues(vs)) if vs.is_empty() => {
+            Some("field(default_factory=dict)".to_string())
+        }
+        Some(value @ Value::Values(_)) | Some(value @ Value::NamedValues(_)) => {
+            Some(format!("field(default_factory=lambda: {})", literal(value)))
+        }
+        Some(value) => Some(literal(value)),
+        None if optional || field.value_type().is_optional() => Some("None".to_string()),
+        None => None,
+    };
+    Ok((annotation, default))
+}
+
+/// The declaration of a parameter, and whether it has a default.
+fn parameter_declaration(parameter: &Parameter) -> Result<(String, bool)> {
+    let annotation = annotation(parameter.value_type(), parameter.is_optional())?;
+    Ok(match parameter.value() {
+        Some(value) => (
+            format!("{}: {} = {}", parameter.name(), annotation, literal(value)),
+            true,
+        ),
+        None if parameter.is_optional() || parameter.value_type().is_optional() => {
+            (format!("{}: {} = None", parameter.name(), annotation), true)
+        }
+        None => (format!("{}: {}", parameter.name(), annotation), false),
+    })
+}
+
+fn annotation(vt: &ValueType, optional: bool) -> Result<String> {
+    if optional && !vt.is_optional() {
+        Ok(format!("Optional[{}]", value_type(vt)?))
+    } else {
+        value_type(vt)
+    }
+}
+
+fn value_types(vts: &[ValueType]) -> Result<Vec<String>> {
+    vts.iter().map(value_type).collect()
+}
+
+fn value_type(vt: &ValueType) -> Result<String> {
+    Ok(match vt {
+        ValueType::Known(kt) => match kt {
+            KnownType::I8
+            | KnownType::U8
+            | KnownType::I16
+            | KnownType::U16
+            | KnownType::I32
+            | KnownType::U32
+            | KnownType::I64
+            | KnownType::U64 => "int",
+            KnownType::F32 | KnownType::F64 => "float",
+            KnownType::Boolean => "bool",
+            KnownType::Char | KnownType::String | KnownType::StringSlice => "str",
+        }
+        .to_string(),
+        ValueType::Reference(t) => t.to_string(),
+        ValueType::Borrowed(_, t)
+        | ValueType::BorrowedMut(_, t)
+        | ValueType::Pointer(t)
+        | ValueType::PointerMut(t)
+        | ValueType::Boxed(t) => value_type(t)?,
+        ValueType::Slice(t) | ValueType::Array(t) | ValueType::FixedArray(t, _)
+            if matches!(t.as_ref(), ValueType::Known(KnownType::U8)) =>
+        {
+            "bytes".to_string()
+        }
+        ValueType::Slice(t) | ValueType::Array(t) | ValueType::FixedArray(t, _) => {
+            format!("list[{}]", value_type(t)?)
+        }
+        ValueType::Set(t) => format!("set[{}]", value_type(t)?),
+        ValueType::Map(kt, vt) => format!("dict[{}, {}]", value_type(kt)?, value_type(vt)?),
+        ValueType::Unit => "None".to_string(),
+        ValueType::Tuple(ts) => format!("tuple[{}]", value_types(ts)?.join(", ")),
+        ValueType::Optional(t) => format!("Optional[{}]", value_type(t)?),
+        ValueType::Generic(t, ts) => format!("{}[{}]", t, value_types(ts)?.join(", ")),
+        ValueType::Function(pts, rt) => format!(
+            "Callable[[{}], {}]",
+            value_types(pts)?.join(", "),
+            match rt {
+                None => "None".to_string(),
+                Some(rt) => value_type(rt)?,
+            }
+        ),
+        ValueType::Lifetime(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("lifetime".to_string()).into())
+        }
+        ValueType::Result(_, _) => {
+            return Err(ErrorKind::UnsupportedElementKind("result".to_string()).into())
+        }
+    })
+}
+
+fn literal(value: &Value) -> String {
+    match value {
+        Value::I8(v) => v.to_string(),
+        Value::U8(v) => v.to_string(),
+        Value::I16(v) => v.to_string(),
+        Value::U16(v) => v.to_string(),
+        Value::I32(v) => v.to_string(),
+        Value::U32(v) => v.to_string(),
+        Value::I64(v) => v.to_string(),
+        Value::U64(v) => v.to_string(),
+        Value::F32(v) => float_literal(*v as f64),
+        Value::F64(v) => float_literal(*v),
+        Value::Boolean(true) => "True".to_string(),
+        Value::Boolean(false) => "False".to_string(),
+        Value::Char(v) => string_literal(&v.to_string()),
+        Value::String(v) => string_literal(v),
+        Value::Identifier(v) => v.to_string(),
+        Value::Values(vs) => format!(
+            "[{}]",
+            vs.iter().map(literal).collect::<Vec<String>>().join(", ")
+        ),
+        Value::NamedValues(vs) => {
+            let mut entries: Vec<String> = vs
+                .iter()
+                .map(|(k, v)| format!("{}: {}", literal(k), literal(v)))
+                .collect();
+            entries.sort();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+fn float_literal(v: f64) -> String {
+    if v.is_nan() {
+        "float(\"nan\")".to_string()
+    } else if v.is_infinite() {
+        if v > 0.0 {
+            "float(\"inf\")".to_string()
+        } else {
+            "float(\"-inf\")".to_string()
+        }
+    } else {
+        format!("{:?}", v)
+    }
+}
@@ -7,16 +7,19 @@ More detailed description, with
 
 */
 
-use crate::error::Result;
+use crate::error::{ErrorKind, Result};
+use crate::language::helpers::is_integer;
 use crate::model::identity::Namespace;
 use crate::model::{
-    Comment, Enumeration, FunctionDecl, HasDocumentation, HasName, HasOptionalType,
-    HasOptionalValue, HasProperties, HasType, HasValue, HasVisibility, Import, ImportItem,
-    IsOptional, KnownType, Module, NamedValue, StructuredType, StructuredTypeKind, TypeAlias,
-    Value, ValueType, Visibility,
+    AssociatedConstant, AssociatedType, BinaryOperator, Comment, Enumeration, Expression, Field,
+    FunctionDecl, HasDocumentation, HasGenericParameters, HasName, HasOptionalType,
+    HasOptionalValue, HasProperties, HasType, HasValue, HasVisibility, Identifier, Implementation,
+    Import, ImportItem, IsOptional, KnownType, Module, NamedValue, ProtectedRegion, Receiver,
+    Statement, StatementBlock, StructuredType, StructuredTypeKind, TypeAlias, UnaryOperator, Value,
+    ValueType, VariantPayload, Visibility,
 };
-use crate::writer::{CodeWriter, ModuleWriter};
-use std::fs::File;
+use crate::project::ProjectLayout;
+use crate::writer::{CodeWriter, DocCommentStyle, List, ListWrapping, ModuleWriter};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
@@ -24,23 +27,40 @@ use std::path::{Path, PathBuf};
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+#[derive(Default)]
 pub struct RustWriter {}
 
+/// Either `name.rs` alongside a `name/` directory for sub-modules, or `name/mod.rs`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum RustLayout {
+    #[default]
+    NamedFiles,
+    ModFiles,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Types
 // ------------------------------------------------------------------------------------------------
 
+/// How defaulted generic parameters are written, as Rust only allows them on type declarations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GenericDefaults {
+    Write,
+    Omit,
+    Reject,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
 
 /// `Fn(&Namespace, &Path) -> PathBuf`
 pub fn namespace_to_file_path(namespace: &Namespace, current_path: &Path) -> PathBuf {
-    PathBuf::from(current_path)
+    current_path.join(RustLayout::default().module_file_path(namespace))
 }
 
-/// `Fn() -> Box<dyn ModuleWriter<File>>`
-pub fn rust_module_writer() -> Box<dyn ModuleWriter<File>> {
+/// `Fn() -> Box<dyn ModuleWriter<W>>`
+pub fn rust_module_writer<W: Write>() -> Box<dyn ModuleWriter<W>> {
     Box::new(RustWriter::default())
 }
 
@@ -48,19 +68,13 @@ pub fn rust_module_writer() -> Box<dyn ModuleWriter<File>> {
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
-impl Default for RustWriter {
-    fn default() -> Self {
-        Self {}
-    }
-}
-
 impl<W> ModuleWriter<W> for RustWriter
 where
     W: Write,
 {
     fn write_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
         if let Some(documentation) = module.documentation() {
-            self.write_block_comment(writer, "/*!", "*/", documentation)?;
+            self.write_doc_comment(writer, "/*!", "//! ", documentation)?;
         }
         Ok(())
     }
@@ -72,24 +86,29 @@ where
     }
 
     fn write_import(&self, writer: &mut CodeWriter<W>, import: &Import) -> Result<()> {
-        assert!(!import.items().is_empty());
+        if import.namespace().path().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("empty_import".to_string()).into());
+        }
         self.write_visibility(writer, import)?;
-        writer.write_str(&format!("use {}::", import.namespace().join("::")))?;
-        if import.items().len() == 1 {
+        writer.write_str(&format!("use {}", import.namespace().join("::")))?;
+        if import.items().is_empty() {
+            writer.write_str(";")?;
+        } else if import.items().len() == 1 {
             writer.write_str(&format!(
-                "{};",
+                "::{};",
                 import_item(import.items().iter().next().unwrap())
             ))?;
         } else {
-            writer.write_str(&format!(
-                "{{{}}};",
-                import
+            writer.write_str("::")?;
+            writer.write_list_then(
+                &List::new("{", "}", ListWrapping::Fill),
+                &import
                     .items()
                     .iter()
                     .map(import_item)
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            ))?;
+                    .collect::<Vec<String>>(),
+                ";",
+            )?;
         }
         writer.new_line()
     }
@@ -98,7 +117,7 @@ where
         if comment.is_line() {
             self.write_line_comment(writer, "//", comment.text())
         } else {
-            self.write_block_comment(writer, "/*", "*/", comment.text())
+            self.write_block_comment(writer, "/*", "*/", comment.text(), false)
         }
     }
 
@@ -111,9 +130,9 @@ where
             StructuredTypeKind::Structure => self.write_structure(writer, record, "struct"),
             StructuredTypeKind::Union => self.write_structure(writer, record, "union"),
             StructuredTypeKind::Exception => self.write_exception(writer, record),
-            StructuredTypeKind::Class => self.write_structure(writer, record, "class"),
-            StructuredTypeKind::Interface => self.write_structure(writer, record, "interface"),
-            StructuredTypeKind::Service => self.write_structure(writer, record, "service"),
+            StructuredTypeKind::Class => self.write_structure(writer, record, "struct"),
+            StructuredTypeKind::Interface => self.write_trait(writer, record),
+            StructuredTypeKind::Service => self.write_trait(writer, record),
         }
     }
 
@@ -122,25 +141,67 @@ where
         writer: &mut CodeWriter<W>,
         enumeration: &Enumeration,
     ) -> Result<()> {
+        let variants = enumeration.variants();
+        if variants
+            .iter()
+            .filter_map(|variant| variant.value().as_ref())
+            .any(|value| !is_integer(value))
+        {
+            return Err(ErrorKind::UnsupportedElementKind("enumeration_value".to_string()).into());
+        }
+        // Discriminants on an enumeration with payloads need an explicit primitive representation.
+        let needs_repr = variants.iter().any(|variant| variant.has_value())
+            && variants.iter().any(|variant| variant.has_payload())
+            && !enumeration
+                .properties()
+                .iter()
+                .any(|property| property.name().as_ref() == "repr");
+
         self.write_documentation(writer, enumeration)?;
         self.write_attributes(writer, enumeration, false)?;
+        if needs_repr {
+            writer.write_str("#[repr(isize)]")?;
+            writer.new_line()?;
+        }
         self.write_visibility(writer, enumeration)?;
-        writer.write_str(&format!("enum {} {{", enumeration.name()))?;
+        writer.write_str(&format!("enum {}", enumeration.name()))?;
+        self.write_generic_parameters(writer, enumeration, GenericDefaults::Write)?;
+        self.write_where_clause_then(writer, enumeration, "{")?;
         if !enumeration.variants().is_empty() {
             writer.new_line()?;
             writer.indent();
             for member in enumeration.variants() {
+                writer.begin_element(member.name());
                 self.write_documentation(writer, member)?;
                 self.write_attributes(writer, member, false)?;
-                writer.write_str(&format!(
-                    "{}{},",
-                    member.name(),
-                    match &member.value_type() {
-                        None => String::new(),
-                        Some(v) => format!("({})", self.value_type(v, false)),
+                writer.write_str(member.name().as_ref())?;
+                match member.payload() {
+                    None => {}
+                    Some(VariantPayload::Tuple(value_types)) => {
+                        writer.write_str(&format!(
+                            "({})",
+                            value_types
+                                .iter()
+                                .map(|vt| self.value_type(vt, false))
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ))?;
                     }
-                ))?;
+                    Some(VariantPayload::Struct(fields)) => {
+                        writer.write_str(" {")?;
+                        writer.new_line()?;
+                        writer.indent();
+                        self.write_fields(writer, fields, false)?;
+                        writer.outdent();
+                        writer.write_str("}")?;
+                    }
+                }
+                if let Some(value) = member.value() {
+                    writer.write_str(&format!(" = {}", self.value(value, false)))?;
+                }
+                writer.write_str(",")?;
                 writer.new_line()?;
+                writer.end_element();
             }
             writer.outdent();
         }
@@ -156,8 +217,8 @@ where
         writer.write_str(&format!(
             "const {}: {} = {};",
             constant.name(),
-            self.value_type(&constant.value_type(), false),
-            self.value(&constant.value(), false),
+            self.value_type(constant.value_type(), false),
+            self.literal(constant.value()),
         ))?;
         writer.new_line()
     }
@@ -169,7 +230,7 @@ where
             "let {}: {} = {};",
             variable.name(),
             self.value_type(variable.value_type(), false),
-            self.value(&variable.value(), false),
+            self.literal(variable.value()),
         ))?;
         writer.new_line()
     }
@@ -179,42 +240,84 @@ where
         writer: &mut CodeWriter<W>,
         function_decl: &FunctionDecl,
     ) -> Result<()> {
-        self.write_documentation(writer, function_decl)?;
-        self.write_attributes(writer, function_decl, false)?;
-        self.write_visibility(writer, function_decl)?;
+        self.write_function(writer, function_decl, true)
+    }
+
+    fn write_implementation(
+        &self,
+        writer: &mut CodeWriter<W>,
+        implementation: &Implementation,
+    ) -> Result<()> {
+        self.write_documentation(writer, implementation)?;
+        self.write_attributes(writer, implementation, false)?;
+        writer.write_str("impl")?;
+        self.write_generic_parameters(writer, implementation, GenericDefaults::Omit)?;
         writer.write_str(&format!(
-            "fn {}({}){}",
-            function_decl.name(),
-            &function_decl
-                .parameters()
-                .iter()
-                .map(|p| format!(
-                    "{}: {}",
-                    p.name(),
-                    self.value_type(&p.value_type(), p.is_optional())
-                ))
-                .collect::<Vec<String>>()
-                .join(", "),
-            match &function_decl.value_type() {
+            " {}{}",
+            match implementation.trait_type() {
                 None => String::new(),
-                Some(vt) => format!(": {}", self.value_type(vt, false)),
+                Some(trait_type) => format!("{} for ", self.value_type(trait_type, false)),
             },
+            self.value_type(implementation.target_type(), false),
         ))?;
-        writer.write_str(";")?;
+        self.write_where_clause_then(writer, implementation, "{")?;
+        self.write_members(
+            writer,
+            implementation.associated_types(),
+            implementation.associated_constants(),
+            implementation.methods(),
+            false,
+            !implementation.is_trait_implementation(),
+        )?;
+        if !implementation.regions().is_empty() {
+            if implementation.associated_types().is_empty()
+                && implementation.associated_constants().is_empty()
+                && implementation.methods().is_empty()
+            {
+                writer.new_line()?;
+            } else {
+                writer.blank_line()?;
+            }
+            writer.indent();
+            for region in implementation.regions() {
+                self.write_region(writer, region)?;
+            }
+            writer.outdent();
+        }
+        writer.write_str("}")?;
         writer.new_line()
     }
 
     fn write_type_alias(&self, writer: &mut CodeWriter<W>, type_alias: &TypeAlias) -> Result<()> {
         self.write_visibility(writer, type_alias)?;
-        writer.write_str(&format!(
-            "type {} = {};",
-            type_alias.name(),
-            self.value_type(&type_alias.value_type(), false)
-        ))?;
+        writer.write_str(&format!("type {}", type_alias.name()))?;
+        self.write_generic_parameters(writer, type_alias, GenericDefaults::Write)?;
+        writer.write_str(" = ")?;
+        self.write_value_type_then(writer, type_alias.value_type(), false, "")?;
+        self.write_where_clause_then(writer, type_alias, ";")?;
         writer.new_line()
     }
+
+    fn write_region(&self, writer: &mut CodeWriter<W>, region: &ProtectedRegion) -> Result<()> {
+        writer.write_region_markers("// ", region.name().as_ref())
+    }
 }
 
+impl ProjectLayout for RustLayout {
+    fn module_file_path(&self, namespace: &Namespace) -> PathBuf {
+        let mut path: PathBuf = namespace.path().iter().map(|id| id.as_ref()).collect();
+        match self {
+            RustLayout::NamedFiles => {
+                let _ = path.set_extension("rs");
+            }
+            RustLayout::ModFiles => path.push("mod.rs"),
+        }
+        path
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
 impl RustWriter {
     fn write_structure<W: Write>(
         &self,
@@ -222,31 +325,339 @@ impl RustWriter {
         record: &StructuredType,
         kind: &'static str,
     ) -> Result<()> {
+        if !record.associated_types().is_empty() {
+            return Err(
+                ErrorKind::UnsupportedElementKind("inherent_associated_type".to_string()).into(),
+            );
+        }
+        let default_fields = if kind == "struct" && record.fields().iter().any(|f| f.has_value()) {
+            Some(self.default_fields(record)?)
+        } else {
+            None
+        };
         self.write_documentation(writer, record)?;
         self.write_attributes(writer, record, false)?;
         self.write_visibility(writer, record)?;
-        writer.write_str(&format!("{} {} {{", kind, record.name()))?;
+        writer.write_str(&format!("{} {}", kind, record.name()))?;
+        self.write_generic_parameters(writer, record, GenericDefaults::Write)?;
+        self.write_where_clause_then(writer, record, "{")?;
         if !record.fields().is_empty() {
             writer.new_line()?;
             writer.indent();
-            for member in record.fields() {
-                self.write_documentation(writer, member)?;
-                self.write_attributes(writer, member, false)?;
-                self.write_visibility(writer, record)?;
-                writer.write_str(&format!(
-                    "{}: {},",
-                    member.name(),
-                    self.value_type(&member.value_type(), member.is_optional()),
-                ))?;
-                writer.new_line()?;
-            }
+            self.write_fields(writer, record.fields(), true)?;
             writer.outdent();
         }
 
+        writer.write_str("}")?;
+        writer.new_line()?;
+
+        if !record.methods().is_empty() || !record.associated_constants().is_empty() {
+            writer.blank_line()?;
+            writer.write_str("impl")?;
+            self.write_generic_parameters(writer, record, GenericDefaults::Omit)?;
+            writer.write_str(&format!(" {}", record.name()))?;
+            self.write_generic_arguments(writer, record)?;
+            self.write_where_clause_then(writer, record, "{")?;
+            self.write_members(
+                writer,
+                &[],
+                record.associated_constants(),
+                record.methods(),
+                false,
+                true,
+            )?;
+            writer.write_str("}")?;
+            writer.new_line()?;
+        }
+
+        if let Some(default_fields) = default_fields {
+            writer.blank_line()?;
+            self.write_default_impl(writer, record, &default_fields)?;
+        }
+        Ok(())
+    }
+
+    fn write_trait<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        if !record.fields().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("interface_field".to_string()).into());
+        }
+        self.write_documentation(writer, record)?;
+        self.write_attributes(writer, record, false)?;
+        self.write_visibility(writer, record)?;
+        writer.write_str(&format!("trait {}", record.name()))?;
+        self.write_generic_parameters(writer, record, GenericDefaults::Write)?;
+        if !record.extends().is_empty() {
+            writer.write_str(&format!(": {}", self.bounds(record.extends())))?;
+        }
+        self.write_where_clause_then(writer, record, "{")?;
+        self.write_members(
+            writer,
+            record.associated_types(),
+            record.associated_constants(),
+            record.methods(),
+            true,
+            false,
+        )?;
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn write_members<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        associated_types: &[AssociatedType],
+        associated_constants: &[AssociatedConstant],
+        methods: &[FunctionDecl],
+        in_trait: bool,
+        with_visibility: bool,
+    ) -> Result<()> {
+        if associated_types.is_empty() && associated_constants.is_empty() && methods.is_empty() {
+            return Ok(());
+        }
+        writer.new_line()?;
+        writer.indent();
+        for associated_type in associated_types {
+            writer.begin_element(associated_type.name());
+            self.write_documentation(writer, associated_type)?;
+            self.write_attributes(writer, associated_type, false)?;
+            writer.write_str(&format!("type {}", associated_type.name()))?;
+            if in_trait {
+                if associated_type.has_value_type() {
+                    return Err(ErrorKind::UnsupportedElementKind(
+                        "associated_type_default".to_string(),
+                    )
+                    .into());
+                }
+                if !associated_type.bounds().is_empty() {
+                    writer.write_str(&format!(": {}", self.bounds(associated_type.bounds())))?;
+                }
+            } else {
+                match associated_type.value_type() {
+                    None => {
+                        return Err(ErrorKind::UnsupportedElementKind(
+                            "associated_type_without_type".to_string(),
+                        )
+                        .into())
+                    }
+                    Some(vt) => writer.write_str(&format!(" = {}", self.value_type(vt, false)))?,
+                }
+            }
+            writer.write_str(";")?;
+            writer.new_line()?;
+            writer.end_element();
+        }
+        if !associated_types.is_empty() && !associated_constants.is_empty() {
+            writer.blank_line()?;
+        }
+        for associated_constant in associated_constants {
+            writer.begin_element(associated_constant.name());
+            self.write_documentation(writer, associated_constant)?;
+            self.write_attributes(writer, associated_constant, false)?;
+            writer.write_str(&format!(
+                "const {}: {}",
+                associated_constant.name(),
+                self.value_type(associated_constant.value_type(), false)
+            ))?;
+            match associated_constant.value() {
+                None if !in_trait => {
+                    return Err(ErrorKind::UnsupportedElementKind(
+                        "associated_constant_without_value".to_string(),
+                    )
+                    .into())
+                }
+                None => {}
+                Some(value) => writer.write_str(&format!(" = {}", self.literal(value)))?,
+            }
+            writer.write_str(";")?;
+            writer.new_line()?;
+            writer.end_element();
+        }
+        if (!associated_types.is_empty() || !associated_constants.is_empty()) && !methods.is_empty()
+        {
+            writer.blank_line()?;
+        }
+        for (i, method) in methods.iter().enumerate() {
+            if i > 0 {
+                writer.blank_line()?;
+            }
+            writer.begin_element(method.name());
+            self.write_function(writer, method, with_visibility)?;
+            writer.end_element();
+        }
+        writer.outdent();
+        Ok(())
+    }
+
+    fn write_function<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        function_decl: &FunctionDecl,
+        with_visibility: bool,
+    ) -> Result<()> {
+        self.write_documentation(writer, function_decl)?;
+        self.write_attributes(writer, function_decl, false)?;
+        if with_visibility {
+            self.write_visibility(writer, function_decl)?;
+        }
+        let mut parameters: Vec<(Option<&Identifier>, String)> = Default::default();
+        if let Some(receiver) = function_decl.receiver() {
+            parameters.push((
+                None,
+                match receiver {
+                    Receiver::Value => "self",
+                    Receiver::Reference => "&self",
+                    Receiver::MutableReference => "&mut self",
+                }
+                .to_string(),
+            ));
+        }
+        parameters.extend(function_decl.parameters().iter().map(|p| {
+            (
+                Some(p.name()),
+                format!(
+                    "{}: {}",
+                    p.name(),
+                    self.value_type(p.value_type(), p.is_optional())
+                ),
+            )
+        }));
+        writer.write_str(&format!("fn {}", function_decl.name()))?;
+        self.write_generic_parameters(writer, function_decl, GenericDefaults::Reject)?;
+        writer.write_element_list_then(
+            &List::new("(", ")", ListWrapping::OnePerLine),
+            &parameters,
+            &match &function_decl.value_type() {
+                None => String::new(),
+                Some(vt) => format!(" -> {}", self.value_type(vt, false)),
+            },
+        )?;
+        match function_decl.body() {
+            None => self.write_where_clause_then(writer, function_decl, ";")?,
+            Some(body) => {
+                if !self.write_where_clause(writer, function_decl)? {
+                    writer.write_str(" ")?;
+                }
+                self.write_statement_block(writer, body)?;
+            }
+        }
+        writer.new_line()
+    }
+
+    fn write_default_impl<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+        default_fields: &[String],
+    ) -> Result<()> {
+        writer.write_str("impl")?;
+        self.write_generic_parameters(writer, record, GenericDefaults::Omit)?;
+        writer.write_str(&format!(" Default for {}", record.name()))?;
+        self.write_generic_arguments(writer, record)?;
+        self.write_where_clause_then(writer, record, "{")?;
+        writer.new_line()?;
+        writer.indent();
+        writer.write_str("fn default() -> Self {")?;
+        writer.new_line()?;
+        writer.indent();
+        writer.write_str("Self {")?;
+        writer.new_line()?;
+        writer.indent();
+        for default_field in default_fields {
+            writer.write_str(default_field)?;
+            writer.new_line()?;
+        }
+        writer.outdent();
+        writer.write_str("}")?;
+        writer.new_line()?;
+        writer.outdent();
+        writer.write_str("}")?;
+        writer.new_line()?;
+        writer.outdent();
         writer.write_str("}")?;
         writer.new_line()
     }
 
+    fn default_fields(&self, record: &StructuredType) -> Result<Vec<String>> {
+        record
+            .fields()
+            .iter()
+            .map(|field| {
+                Ok(format!(
+                    "{}: {},",
+                    field.name(),
+                    match field.value() {
+                        None => "Default::default()".to_string(),
+                        Some(value) => {
+                            let value = self.typed_literal(field.value_type(), value)?;
+                            if field.is_optional() {
+                                format!("Some({})", value)
+                            } else {
+                                value
+                            }
+                        }
+                    }
+                ))
+            })
+            .collect()
+    }
+
+    /// Writes a type, wrapping the arguments of a generic type if it would exceed `max_width`.
+    fn write_value_type_then<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        value_type: &ValueType,
+        optional: bool,
+        then: &str,
+    ) -> Result<()> {
+        let single_line = self.value_type(value_type, optional);
+        if !optional && !writer.fits(&format!("{}{}", single_line, then)) {
+            let type_arguments = match value_type {
+                ValueType::Generic(t, gt) => Some((t.to_string(), gt.iter().collect())),
+                ValueType::Result(ok, err) => Some(("Result".to_string(), vec![&**ok, &**err])),
+                ValueType::Map(kt, vt) => Some(("HashMap".to_string(), vec![&**kt, &**vt])),
+                _ => None,
+            };
+            if let Some((name, arguments)) = type_arguments {
+                writer.write_str(&name)?;
+                return writer.write_list_then(
+                    &List::new("<", ">", ListWrapping::OnePerLine),
+                    &arguments
+                        .into_iter()
+                        .map(|t: &ValueType| self.value_type(t, false))
+                        .collect::<Vec<String>>(),
+                    then,
+                );
+            }
+        }
+        writer.write_str(&single_line)?;
+        writer.write_str(then)
+    }
+
+    fn write_fields<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        fields: &[Field],
+        with_visibility: bool,
+    ) -> Result<()> {
+        for member in fields {
+            writer.begin_element(member.name());
+            self.write_documentation(writer, member)?;
+            self.write_attributes(writer, member, false)?;
+            if with_visibility {
+                self.write_visibility(writer, member)?;
+            }
+            writer.write_str(&format!("{}: ", member.name()))?;
+            self.write_value_type_then(writer, member.value_type(), member.is_optional(), ",")?;
+            writer.new_line()?;
+            writer.end_element();
+        }
+        Ok(())
+    }
+
     fn write_exception<W: Write>(
         &self,
         writer: &mut CodeWriter<W>,
@@ -255,6 +666,131 @@ impl RustWriter {
         self.write_structure(writer, record, "struct")
     }
 
+    fn write_statement_block<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        block: &StatementBlock,
+    ) -> Result<()> {
+        if block.is_empty() {
+            return writer.write_str("{}");
+        }
+        writer.write_str("{")?;
+        writer.new_line()?;
+        writer.indent();
+        for statement in block.statements() {
+            self.write_statement(writer, statement)?;
+        }
+        writer.outdent();
+        writer.write_str("}")
+    }
+
+    fn write_statement<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        statement: &Statement,
+    ) -> Result<()> {
+        match statement {
+            Statement::Comment(comment) => return self.write_comment(writer, comment),
+            Statement::Let(name, value_type, value) => {
+                self.write_let(writer, "let", name, value_type, value)?
+            }
+            Statement::LetMut(name, value_type, value) => {
+                self.write_let(writer, "let mut", name, value_type, value)?
+            }
+            Statement::Assign(lhs, rhs) => writer.write_str(&format!(
+                "{} = {};",
+                self.expression(lhs),
+                self.expression(rhs)
+            ))?,
+            Statement::Expression(expression) => {
+                writer.write_str(&format!("{};", self.expression(expression)))?
+            }
+            Statement::Return(None) => writer.write_str("return;")?,
+            Statement::Return(Some(expression)) => {
+                writer.write_str(&format!("return {};", self.expression(expression)))?
+            }
+            Statement::If(condition, then_block, else_block) => {
+                self.write_if(writer, condition, then_block, else_block)?
+            }
+            Statement::Match(expression, arms) => {
+                writer.write_str(&format!("match {} {{", self.expression(expression)))?;
+                writer.new_line()?;
+                writer.indent();
+                for arm in arms {
+                    writer.write_str(&self.expression(arm.pattern()))?;
+                    if let Some(guard) = arm.guard() {
+                        writer.write_str(&format!(" if {}", self.expression(guard)))?;
+                    }
+                    writer.write_str(" => ")?;
+                    self.write_statement_block(writer, arm.body())?;
+                    writer.new_line()?;
+                }
+                writer.outdent();
+                writer.write_str("}")?;
+            }
+            Statement::Loop(body) => {
+                writer.write_str("loop ")?;
+                self.write_statement_block(writer, body)?;
+            }
+            Statement::While(condition, body) => {
+                writer.write_str(&format!("while {} ", self.expression(condition)))?;
+                self.write_statement_block(writer, body)?;
+            }
+            Statement::For(name, iterable, body) => {
+                writer.write_str(&format!("for {} in {} ", name, self.expression(iterable)))?;
+                self.write_statement_block(writer, body)?;
+            }
+            Statement::Break => writer.write_str("break;")?,
+            Statement::Continue => writer.write_str("continue;")?,
+            Statement::Block(body) => self.write_statement_block(writer, body)?,
+        }
+        writer.new_line()
+    }
+
+    fn write_let<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        keyword: &str,
+        name: &Identifier,
+        value_type: &Option<ValueType>,
+        value: &Option<Expression>,
+    ) -> Result<()> {
+        writer.write_str(&format!(
+            "{} {}{}{};",
+            keyword,
+            name,
+            match value_type {
+                None => String::new(),
+                Some(vt) => format!(": {}", self.value_type(vt, false)),
+            },
+            match value {
+                None => String::new(),
+                Some(v) => format!(" = {}", self.expression(v)),
+            }
+        ))
+    }
+
+    fn write_if<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        condition: &Expression,
+        then_block: &StatementBlock,
+        else_block: &Option<StatementBlock>,
+    ) -> Result<()> {
+        writer.write_str(&format!("if {} ", self.expression(condition)))?;
+        self.write_statement_block(writer, then_block)?;
+        if let Some(else_block) = else_block {
+            writer.write_str(" else ")?;
+            match else_block.statements().as_slice() {
+                [Statement::If(condition, then_block, else_block)] => {
+                    self.write_if(writer, condition, then_block, else_block)?
+                }
+                _ => self.write_statement_block(writer, else_block)?,
+            }
+        }
+        Ok(())
+    }
+
     fn write_line_comment<W: Write>(
         &self,
         writer: &mut CodeWriter<W>,
@@ -280,12 +816,17 @@ impl RustWriter {
         start: &str,
         end: &str,
         text: &str,
+        reflow: bool,
     ) -> Result<()> {
         writer.write_str(start)?;
         writer.new_line()?;
-        for line in text.split("\n") {
-            writer.write_str(line)?;
-            writer.new_line()?;
+        if reflow {
+            writer.write_doc_text("", text)?;
+        } else {
+            for line in text.split("\n") {
+                writer.write_str(line)?;
+                writer.new_line()?;
+            }
         }
         writer.write_str(end)?;
         writer.new_line()
@@ -305,13 +846,28 @@ impl RustWriter {
         })
     }
 
+    fn write_doc_comment<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        block_start: &str,
+        line_prefix: &str,
+        text: &str,
+    ) -> Result<()> {
+        match writer.policy().doc_comment_style() {
+            DocCommentStyle::Block => {
+                self.write_block_comment(writer, block_start, "*/", text, true)
+            }
+            DocCommentStyle::Line => writer.write_doc_text(line_prefix, text),
+        }
+    }
+
     fn write_documentation<W: Write>(
         &self,
         writer: &mut CodeWriter<W>,
         item: &dyn HasDocumentation,
     ) -> Result<()> {
         if let Some(documentation) = item.documentation() {
-            self.write_block_comment(writer, "/**", "*/", documentation)?;
+            self.write_doc_comment(writer, "/**", "/// ", documentation)?;
         }
         Ok(())
     }
@@ -329,10 +885,20 @@ impl RustWriter {
                 writer.write_str("#[")?;
             }
             writer.write_str(property.name().as_ref())?;
-            if let Some(value) = property.value() {
-                writer.write_str(&self.value(value, true))?;
+            match property.value() {
+                Some(Value::Values(vs)) => writer.write_list_then(
+                    &List::new("(", ")", ListWrapping::Fill),
+                    &vs.iter()
+                        .map(|v| self.value(v, true))
+                        .collect::<Vec<String>>(),
+                    "]",
+                )?,
+                Some(value) => {
+                    writer.write_str(&self.value(value, true))?;
+                    writer.write_str("]")?;
+                }
+                None => writer.write_str("]")?,
             }
-            writer.write_str("]")?;
             writer.new_line()?;
         }
         Ok(())
@@ -403,6 +969,276 @@ impl RustWriter {
         }
     }
 
+    fn write_where_clause<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasGenericParameters,
+    ) -> Result<bool> {
+        if item.has_where_predicates() {
+            writer.new_line()?;
+            writer.write_str("where")?;
+            writer.new_line()?;
+            writer.indent();
+            for predicate in item.where_predicates() {
+                writer.write_str(&format!(
+                    "{}: {},",
+                    self.value_type(predicate.subject(), false),
+                    self.bounds(predicate.bounds())
+                ))?;
+                writer.new_line()?;
+            }
+            writer.outdent();
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn write_where_clause_then<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasGenericParameters,
+        then: &str,
+    ) -> Result<()> {
+        if !self.write_where_clause(writer, item)? && then != ";" {
+            writer.write_str(" ")?;
+        }
+        writer.write_str(then)
+    }
+
+    fn write_generic_parameters<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasGenericParameters,
+        defaults: GenericDefaults,
+    ) -> Result<()> {
+        if !item.has_generic_parameters() {
+            return Ok(());
+        }
+        let mut parameters: Vec<String> = Default::default();
+        for parameter in item.generic_parameters() {
+            let mut parameter_str = if parameter.is_lifetime() {
+                format!("'{}", parameter.name())
+            } else {
+                parameter.name().to_string()
+            };
+            if !parameter.bounds().is_empty() {
+                parameter_str.push_str(&format!(": {}", self.bounds(parameter.bounds())));
+            }
+            if let Some(default) = parameter.default() {
+                match defaults {
+                    GenericDefaults::Write => {
+                        parameter_str.push_str(&format!(" = {}", self.value_type(default, false)))
+                    }
+                    GenericDefaults::Omit => {}
+                    GenericDefaults::Reject => {
+                        return Err(ErrorKind::UnsupportedElementKind(
+                            "generic_parameter_default".to_string(),
+                        )
+                        .into())
+                    }
+                }
+            }
+            parameters.push(parameter_str);
+        }
+        writer.write_list(&List::new("<", ">", ListWrapping::OnePerLine), &parameters)
+    }
+
+    fn write_generic_arguments<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasGenericParameters,
+    ) -> Result<()> {
+        if !item.has_generic_parameters() {
+            return Ok(());
+        }
+        writer.write_list(
+            &List::new("<", ">", ListWrapping::Fill),
+            &item
+                .generic_parameters()
+                .iter()
+                .map(|p| {
+                    if p.is_lifetime() {
+                        format!("'{}", p.name())
+                    } else {
+                        p.name().to_string()
+                    }
+                })
+                .collect::<Vec<String>>(),
+        )
+    }
+
+    fn bounds(&self, bounds: &[ValueType]) -> String {
+        bounds
+            .iter()
+            .map(|t| self.value_type(t, false))
+            .collect::<Vec<String>>()
+            .join(" + ")
+    }
+
+    fn expression(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Literal(v) => self.literal(v),
+            Expression::Variable(v) => v.to_string(),
+            Expression::Path(v) => v.join("::"),
+            Expression::Field(receiver, name) => {
+                format!("{}.{}", self.expression(receiver), name)
+            }
+            Expression::Call(function, arguments) => format!(
+                "{}({})",
+                self.expression(function),
+                self.expressions(arguments)
+            ),
+            Expression::MethodCall(receiver, name, arguments) => format!(
+                "{}.{}({})",
+                self.expression(receiver),
+                name,
+                self.expressions(arguments)
+            ),
+            Expression::StructLiteral(name, fields) => {
+                if fields.is_empty() {
+                    format!("{} {{}}", name)
+                } else {
+                    format!(
+                        "{} {{ {} }}",
+                        name,
+                        fields
+                            .iter()
+                            .map(|(name, value)| format!("{}: {}", name, self.expression(value)))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    )
+                }
+            }
+            Expression::Unary(operator, expression) => format!(
+                "{}{}",
+                match operator {
+                    UnaryOperator::Not => "!",
+                    UnaryOperator::Negate => "-",
+                },
+                self.operand(expression)
+            ),
+            Expression::Binary(lhs, operator, rhs) => format!(
+                "{} {} {}",
+                self.operand(lhs),
+                match operator {
+                    BinaryOperator::Add => "+",
+                    BinaryOperator::Subtract => "-",
+                    BinaryOperator::Multiply => "*",
+                    BinaryOperator::Divide => "/",
+                    BinaryOperator::Remainder => "%",
+                    BinaryOperator::Equal => "==",
+                    BinaryOperator::NotEqual => "!=",
+                    BinaryOperator::Less => "<",
+                    BinaryOperator::LessOrEqual => "<=",
+                    BinaryOperator::Greater => ">",
+                    BinaryOperator::GreaterOrEqual => ">=",
+                    BinaryOperator::And => "&&",
+                    BinaryOperator::Or => "||",
+                },
+                self.operand(rhs)
+            ),
+        }
+    }
+
+    fn literal(&self, value: &Value) -> String {
+        match value {
+            Value::String(v) => format!("{:?}", v),
+            Value::Char(v) => format!("{:?}", v),
+            Value::F32(v) => format!("{:?}", v),
+            Value::F64(v) => format!("{:?}", v),
+            _ => self.value(value, false),
+        }
+    }
+
+    fn typed_literal(&self, value_type: &ValueType, value: &Value) -> Result<String> {
+        Ok(match (value_type, value) {
+            (_, Value::Identifier(_)) => self.literal(value),
+            (ValueType::Known(KnownType::String), Value::String(_)) => {
+                format!("{}.to_string()", self.literal(value))
+            }
+            (ValueType::Known(known_type), _) if is_literal_of(known_type, value) => {
+                self.literal(value)
+            }
+            (ValueType::Borrowed(_, t), Value::String(_))
+                if matches!(**t, ValueType::Known(KnownType::StringSlice)) =>
+            {
+                self.literal(value)
+            }
+            (ValueType::Optional(value_type), _) => {
+                format!("Some({})", self.typed_literal(value_type, value)?)
+            }
+            (ValueType::Boxed(value_type), _) => {
+                format!("Box::new({})", self.typed_literal(value_type, value)?)
+            }
+            (ValueType::Array(value_type), Value::Values(vs)) => {
+                format!("vec![{}]", self.typed_literals(value_type, vs)?.join(", "))
+            }
+            (ValueType::FixedArray(value_type, size), Value::Values(vs)) if vs.len() == *size => {
+                format!("[{}]", self.typed_literals(value_type, vs)?.join(", "))
+            }
+            (ValueType::Set(_), Value::Values(vs)) if vs.is_empty() => "HashSet::new()".to_string(),
+            (ValueType::Set(value_type), Value::Values(vs)) => format!(
+                "HashSet::from([{}])",
+                self.typed_literals(value_type, vs)?.join(", ")
+            ),
+            (ValueType::Tuple(value_types), Value::Values(vs)) if value_types.len() == vs.len() => {
+                let values = value_types
+                    .iter()
+                    .zip(vs)
+                    .map(|(vt, v)| self.typed_literal(vt, v))
+                    .collect::<Result<Vec<String>>>()?;
+                if values.len() == 1 {
+                    format!("({},)", values[0])
+                } else {
+                    format!("({})", values.join(", "))
+                }
+            }
+            (ValueType::Map(_, _), Value::NamedValues(vs)) if vs.is_empty() => {
+                "HashMap::new()".to_string()
+            }
+            (ValueType::Map(key_type, value_type), Value::NamedValues(vs)) => {
+                let mut entries = vs
+                    .iter()
+                    .map(|(k, v)| {
+                        Ok(format!(
+                            "({}, {})",
+                            self.typed_literal(key_type, k)?,
+                            self.typed_literal(value_type, v)?
+                        ))
+                    })
+                    .collect::<Result<Vec<String>>>()?;
+                entries.sort();
+                format!("HashMap::from([{}])", entries.join(", "))
+            }
+            _ => return Err(ErrorKind::UnsupportedElementKind("field_value".to_string()).into()),
+        })
+    }
+
+    fn typed_literals(&self, value_type: &ValueType, values: &[Value]) -> Result<Vec<String>> {
+        values
+            .iter()
+            .map(|v| self.typed_literal(value_type, v))
+            .collect()
+    }
+
+    fn operand(&self, expression: &Expression) -> String {
+        match expression {
+            Expression::Unary(_, _) | Expression::Binary(_, _, _) => {
+                format!("({})", self.expression(expression))
+            }
+            _ => self.expression(expression),
+        }
+    }
+
+    fn expressions(&self, expressions: &[Expression]) -> String {
+        expressions
+            .iter()
+            .map(|e| self.expression(e))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
     fn value_type(&self, value_type: &ValueType, optional: bool) -> String {
         let initial = match value_type {
             ValueType::Known(kt) => match kt {
@@ -419,29 +1255,51 @@ impl RustWriter {
                 KnownType::Boolean => "bool",
                 KnownType::Char => "char",
                 KnownType::String => "String",
+                KnownType::StringSlice => "str",
             }
             .to_string(),
             ValueType::Reference(t) => t.to_string(),
+            ValueType::Lifetime(lt) => format!("'{}", lt),
+            ValueType::Borrowed(lt, t) => {
+                format!("&{}{}", lifetime_prefix(lt), self.value_type(t, false))
+            }
+            ValueType::BorrowedMut(lt, t) => {
+                format!("&{}mut {}", lifetime_prefix(lt), self.value_type(t, false))
+            }
+            ValueType::Pointer(t) => format!("*const {}", self.value_type(t, false)),
+            ValueType::PointerMut(t) => format!("*mut {}", self.value_type(t, false)),
+            ValueType::Boxed(t) => format!("Box<{}>", self.value_type(t, false)),
+            ValueType::Slice(t) => format!("[{}]", self.value_type(t, false)),
+            ValueType::Unit => "()".to_string(),
+            ValueType::Tuple(ts) => {
+                if ts.len() == 1 {
+                    format!("({},)", self.value_type(&ts[0], false))
+                } else {
+                    format!(
+                        "({})",
+                        ts.iter()
+                            .map(|t| self.value_type(t, false))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    )
+                }
+            }
+            ValueType::FixedArray(t, len) => format!("[{}; {}]", self.value_type(t, false), len),
+            ValueType::Optional(t) => format!("Option<{}>", self.value_type(t, false)),
+            ValueType::Result(ok, err) => format!(
+                "Result<{}, {}>",
+                self.value_type(ok, false),
+                self.value_type(err, false)
+            ),
             ValueType::Array(t) => {
-                format!("Vec<{}>", self.value_type(&t, false))
+                format!("Vec<{}>", self.value_type(t, false))
             }
-            ValueType::Set(t) => format!("HashSet<{}>", self.value_type(&t, false)),
+            ValueType::Set(t) => format!("HashSet<{}>", self.value_type(t, false)),
             ValueType::Map(kt, vt) => format!(
                 "HashMap<{}, {}>",
-                self.value_type(&kt, false),
-                self.value_type(&vt, false)
+                self.value_type(kt, false),
+                self.value_type(vt, false)
             ),
-            ValueType::Constrained(t, tc) => {
-                assert!(!tc.is_empty());
-                format!(
-                    "{}: {}",
-                    t,
-                    tc.iter()
-                        .map(|t| self.value_type(t, false))
-                        .collect::<Vec<String>>()
-                        .join(" + ")
-                )
-            }
             ValueType::Generic(t, gt) => {
                 assert!(!gt.is_empty());
                 format!(
@@ -462,7 +1320,7 @@ impl RustWriter {
                         .join(", "),
                     match rt {
                         None => String::new(),
-                        Some(rt) => format!(" -> {}", self.value_type(&rt, false)),
+                        Some(rt) => format!(" -> {}", self.value_type(rt, false)),
                     }
                 )
             }
@@ -479,6 +1337,50 @@ impl RustWriter {
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+///
+/// Whether `value` can be written as a literal of `known_type`: integers must be in range for an
+/// integer type and floats finite for a float type, as neither converts to the other.
+///
+fn is_literal_of(known_type: &KnownType, value: &Value) -> bool {
+    let integer = match value {
+        Value::I8(v) => Some(*v as i128),
+        Value::U8(v) => Some(*v as i128),
+        Value::I16(v) => Some(*v as i128),
+        Value::U16(v) => Some(*v as i128),
+        Value::I32(v) => Some(*v as i128),
+        Value::U32(v) => Some(*v as i128),
+        Value::I64(v) => Some(*v as i128),
+        Value::U64(v) => Some(*v as i128),
+        _ => None,
+    };
+    let in_range = |min: i128, max: i128| integer.is_some_and(|v| v >= min && v <= max);
+    match known_type {
+        KnownType::I8 => in_range(i8::MIN.into(), i8::MAX.into()),
+        KnownType::U8 => in_range(u8::MIN.into(), u8::MAX.into()),
+        KnownType::I16 => in_range(i16::MIN.into(), i16::MAX.into()),
+        KnownType::U16 => in_range(u16::MIN.into(), u16::MAX.into()),
+        KnownType::I32 => in_range(i32::MIN.into(), i32::MAX.into()),
+        KnownType::U32 => in_range(u32::MIN.into(), u32::MAX.into()),
+        KnownType::I64 => in_range(i64::MIN.into(), i64::MAX.into()),
+        KnownType::U64 => in_range(u64::MIN.into(), u64::MAX.into()),
+        KnownType::F32 | KnownType::F64 => match value {
+            Value::F32(v) => v.is_finite(),
+            Value::F64(v) => v.is_finite(),
+            _ => false,
+        },
+        KnownType::Boolean => matches!(value, Value::Boolean(_)),
+        KnownType::Char => matches!(value, Value::Char(_)),
+        KnownType::String | KnownType::StringSlice => matches!(value, Value::String(_)),
+    }
+}
+
+fn lifetime_prefix(lifetime: &Option<Identifier>) -> String {
+    match lifetime {
+        None => String::new(),
+        Some(lt) => format!("'{} ", lt),
+    }
+}
+
 fn import_item(ii: &ImportItem) -> String {
     format!(
         "{}{}",
@@ -7,20 +7,33 @@ More detailed description, with
 
 */
 
-use crate::error::Result;
+use crate::error::{ErrorKind, Result};
+use crate::language::helpers::{field_ids, not_generic, string_literal};
+use crate::model::identity::Namespace;
 use crate::model::{
-    Comment, Enumeration, FunctionDecl, HasDocumentation, HasName, HasOptionalType, HasType,
-    HasValue, Import, IsOptional, KnownType, Module, NamedValue, StructuredType,
-    StructuredTypeKind, TypeAlias, Value, ValueType,
+    Comment, Enumeration, FunctionDecl, HasDocumentation, HasFieldId, HasName, HasOptionalType,
+    HasOptionalValue, HasType, HasValue, Identifier, Import, IsOptional, KnownType, Module,
+    NamedValue, Parameter, ProtectedRegion, StructuredType, StructuredTypeKind, TypeAlias, Value,
+    ValueType,
 };
-use crate::writer::{CodeWriter, ModuleWriter};
+use crate::project::{relative_path, ProjectLayout};
+use crate::writer::{CodeWriter, DocCommentStyle, List, ListWrapping, ModuleWriter};
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
-pub struct ThriftWriter {}
+#[derive(Default)]
+pub struct ThriftWriter {
+    positional_field_ids: bool,
+}
+
+/// Each module is written to `a/b/name.thrift` following its namespace; includes are written
+/// relative to the including file, which is where the Thrift compiler looks first.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ThriftLayout {}
 
 // ------------------------------------------------------------------------------------------------
 // Private Types
@@ -34,34 +47,37 @@ pub struct ThriftWriter {}
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
-impl Default for ThriftWriter {
-    fn default() -> Self {
-        Self {}
-    }
-}
-
 impl<W> ModuleWriter<W> for ThriftWriter
 where
     W: Write,
 {
     fn write_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
         if let Some(documentation) = module.documentation() {
-            self.write_block_comment(writer, "/*", "*/", documentation)?;
+            self.write_doc_comment(writer, "/*", "// ", documentation)?;
         }
         writer.blank_line()
     }
 
+    fn write_sub_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        let namespace = match writer.namespace() {
+            None => Namespace::new(vec![module.name().clone()]),
+            Some(namespace) => namespace.with(module.name().clone()),
+        };
+        self.write_include(writer, &namespace)
+    }
+
     fn write_import(&self, writer: &mut CodeWriter<W>, import: &Import) -> Result<()> {
-        assert!(!import.items().is_empty());
-        writer.write_str(&format!("include \"{}\"", import.namespace().join("/")))?;
-        writer.new_line()
+        if import.namespace().path().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("empty_import".to_string()).into());
+        }
+        self.write_include(writer, import.namespace())
     }
 
     fn write_comment(&self, writer: &mut CodeWriter<W>, comment: &Comment) -> Result<()> {
         if comment.is_line() {
             self.write_line_comment(writer, "#", comment.text())
         } else {
-            self.write_block_comment(writer, "/*", "*/", comment.text())
+            self.write_block_comment(writer, "/*", "*/", comment.text(), false)
         }
     }
 
@@ -76,7 +92,7 @@ where
             StructuredTypeKind::Exception => self.write_structure(writer, record, "exception"),
             StructuredTypeKind::Class => self.write_structure(writer, record, "struct"),
             StructuredTypeKind::Interface => self.write_structure(writer, record, "struct"),
-            StructuredTypeKind::Service => self.write_structure(writer, record, "service"),
+            StructuredTypeKind::Service => self.write_service(writer, record),
         }
     }
 
@@ -85,13 +101,31 @@ where
         writer: &mut CodeWriter<W>,
         enumeration: &Enumeration,
     ) -> Result<()> {
+        not_generic(enumeration)?;
+        self.write_documentation(writer, enumeration)?;
         writer.write_str(&format!("enum {} {{", enumeration.name()))?;
         if !enumeration.variants().is_empty() {
             writer.new_line()?;
             writer.indent();
             for variant in enumeration.variants() {
-                writer.write_str(&format!("{},", variant.name()))?;
+                writer.begin_element(variant.name());
+                if variant.has_payload() {
+                    return Err(ErrorKind::UnsupportedElementKind(
+                        "enumeration_variant_payload".to_string(),
+                    )
+                    .into());
+                }
+                self.write_documentation(writer, variant)?;
+                writer.write_str(&format!(
+                    "{}{},",
+                    variant.name(),
+                    match variant.value() {
+                        None => String::new(),
+                        Some(value) => format!(" = {}", value_str(value)),
+                    }
+                ))?;
                 writer.new_line()?;
+                writer.end_element();
             }
             writer.outdent();
         }
@@ -102,7 +136,7 @@ where
     fn write_constant(&self, writer: &mut CodeWriter<W>, named_value: &NamedValue) -> Result<()> {
         writer.write_str(&format!(
             "const {} {} = {}",
-            value_type_string(named_value.value_type()),
+            value_type_string(named_value.value_type())?,
             named_value.name(),
             value_str(named_value.value()),
         ))?;
@@ -114,45 +148,107 @@ where
         writer: &mut CodeWriter<W>,
         function_decl: &FunctionDecl,
     ) -> Result<()> {
+        if function_decl.has_body() {
+            return Err(ErrorKind::UnsupportedElementKind("function_body".to_string()).into());
+        }
         self.write_function_head(writer, function_decl)?;
         writer.write_str(";")?;
         writer.new_line()
     }
 
     fn write_type_alias(&self, writer: &mut CodeWriter<W>, type_alias: &TypeAlias) -> Result<()> {
+        not_generic(type_alias)?;
         writer.write_str(&format!(
             "typedef {} {}",
-            value_type_string(&type_alias.value_type()),
+            value_type_string(type_alias.value_type())?,
             type_alias.name(),
         ))?;
         writer.new_line()
     }
+
+    fn write_region(&self, writer: &mut CodeWriter<W>, region: &ProtectedRegion) -> Result<()> {
+        writer.write_region_markers("// ", region.name().as_ref())
+    }
 }
 
+impl ProjectLayout for ThriftLayout {
+    fn module_file_path(&self, namespace: &Namespace) -> PathBuf {
+        let mut path: PathBuf = namespace.path().iter().map(|id| id.as_ref()).collect();
+        let _ = path.set_extension("thrift");
+        path
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
 impl ThriftWriter {
+    pub fn with_positional_field_ids() -> Self {
+        Self {
+            positional_field_ids: true,
+        }
+    }
+
+    pub fn positional_field_ids(&self) -> bool {
+        self.positional_field_ids
+    }
+
+    /// Includes the file for `namespace`, relative to the module being written if it is known.
+    fn write_include<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        namespace: &Namespace,
+    ) -> Result<()> {
+        let layout = ThriftLayout::default();
+        let path = layout.module_file_path(namespace);
+        let path = match writer.namespace() {
+            None => path,
+            Some(current) => relative_path(
+                layout
+                    .module_file_path(current)
+                    .parent()
+                    .unwrap_or_else(|| Path::new("")),
+                &path,
+            ),
+        };
+        let path: Vec<String> = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        writer.write_str(&format!("include \"{}\"", path.join("/")))?;
+        writer.new_line()
+    }
+
     fn write_structure<W: Write>(
         &self,
         writer: &mut CodeWriter<W>,
         record: &StructuredType,
         kind: &'static str,
     ) -> Result<()> {
+        not_generic(record)?;
         writer.write_str(&format!("{} {} {{", kind, record.name()))?;
         if !record.fields().is_empty() {
             writer.new_line()?;
             writer.indent();
-            for (i, member) in record.fields().iter().enumerate() {
+            let field_ids = self.field_ids(
+                record.name(),
+                record.fields().iter().map(|f| (f.name(), f.field_id())),
+            )?;
+            for (member, field_id) in record.fields().iter().zip(field_ids) {
+                writer.begin_element(member.name());
                 writer.write_str(&format!(
-                    "{}: {} {} {},",
-                    i + 1,
-                    if member.is_optional() {
+                    "{}: {} {} {}{},",
+                    field_id,
+                    if member.is_optional() || member.value_type().is_optional() {
                         "optional"
                     } else {
                         "required"
                     },
-                    value_type_string(member.value_type()),
+                    value_type_string(member.value_type())?,
                     member.name(),
+                    default_value_str(member.value()),
                 ))?;
                 writer.new_line()?;
+                writer.end_element();
             }
             writer.outdent();
         }
@@ -161,6 +257,16 @@ impl ThriftWriter {
         writer.new_line()
     }
 
+    fn field_ids<'a>(
+        &self,
+        owner: &Identifier,
+        members: impl Iterator<Item = (&'a Identifier, &'a Option<u32>)>,
+    ) -> Result<Vec<u32>> {
+        field_ids(owner, members, self.positional_field_ids, |field_id| {
+            field_id != 0 && field_id <= i16::MAX as u32
+        })
+    }
+
     fn write_line_comment<W: Write>(
         &self,
         writer: &mut CodeWriter<W>,
@@ -186,36 +292,152 @@ impl ThriftWriter {
         start: &str,
         end: &str,
         text: &str,
+        reflow: bool,
     ) -> Result<()> {
         writer.write_str(start)?;
         writer.new_line()?;
-        for line in text.split("\n") {
-            writer.write_str(line)?;
-            writer.new_line()?;
+        if reflow {
+            writer.write_doc_text("", text)?;
+        } else {
+            for line in text.split("\n") {
+                writer.write_str(line)?;
+                writer.new_line()?;
+            }
         }
         writer.write_str(end)?;
         writer.new_line()
     }
 
+    fn write_doc_comment<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        block_start: &str,
+        line_prefix: &str,
+        text: &str,
+    ) -> Result<()> {
+        match writer.policy().doc_comment_style() {
+            DocCommentStyle::Block => {
+                self.write_block_comment(writer, block_start, "*/", text, true)
+            }
+            DocCommentStyle::Line => writer.write_doc_text(line_prefix, text),
+        }
+    }
+
+    fn write_documentation<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasDocumentation,
+    ) -> Result<()> {
+        if let Some(documentation) = item.documentation() {
+            self.write_doc_comment(writer, "/**", "/// ", documentation)?;
+        }
+        Ok(())
+    }
+
+    fn write_service<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        not_generic(record)?;
+        if !record.fields().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("service_field".to_string()).into());
+        }
+        self.write_documentation(writer, record)?;
+        writer.write_str(&format!("service {}", record.name()))?;
+        match record.extends().as_slice() {
+            [] => {}
+            [extends] => writer.write_str(&format!(" extends {}", value_type_string(extends)?))?,
+            _ => {
+                return Err(
+                    ErrorKind::UnsupportedElementKind("multiple_inheritance".to_string()).into(),
+                )
+            }
+        }
+        writer.write_str(" {")?;
+        if !record.methods().is_empty() {
+            writer.new_line()?;
+            writer.indent();
+            for method in record.methods() {
+                writer.begin_element(method.name());
+                if method.has_body() {
+                    return Err(
+                        ErrorKind::UnsupportedElementKind("function_body".to_string()).into(),
+                    );
+                }
+                self.write_documentation(writer, method)?;
+                self.write_function_head(writer, method)?;
+                writer.write_str(",")?;
+                writer.new_line()?;
+                writer.end_element();
+            }
+            writer.outdent();
+        }
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
     fn write_function_head<W: Write>(
         &self,
         writer: &mut CodeWriter<W>,
         function_decl: &FunctionDecl,
     ) -> Result<()> {
+        not_generic(function_decl)?;
+        if function_decl.is_oneway() {
+            let returns_void = match function_decl.value_type() {
+                None | Some(ValueType::Unit) => true,
+                Some(_) => false,
+            };
+            if !returns_void || !function_decl.throws().is_empty() {
+                return Err(ErrorKind::UnsupportedElementKind("oneway_result".to_string()).into());
+            }
+            writer.write_str("oneway ")?;
+        }
         writer.write_str(&format!(
-            "{} {}({})",
+            "{} {}",
             match &function_decl.value_type() {
                 None => String::from("void"),
-                Some(vt) => value_type_string(vt),
+                Some(vt) => return_type_string(vt)?,
             },
             function_decl.name(),
+        ))?;
+        let parameter_list = List::new("(", ")", ListWrapping::OnePerLine);
+        writer.write_element_list_then(
+            &parameter_list,
             &function_decl
                 .parameters()
                 .iter()
-                .map(|p| format!("{} {}", value_type_string(p.value_type()), p.name(),))
-                .collect::<Vec<String>>()
-                .join(", "),
-        ))
+                .map(|p| Some(p.name()))
+                .zip(self.parameter_list(function_decl.name(), function_decl.parameters())?)
+                .collect::<Vec<(Option<&Identifier>, String)>>(),
+            "",
+        )?;
+        if !function_decl.throws().is_empty() {
+            writer.write_str(" throws ")?;
+            writer.write_list(
+                &parameter_list,
+                &self.parameter_list(function_decl.name(), function_decl.throws())?,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn parameter_list(&self, owner: &Identifier, parameters: &[Parameter]) -> Result<Vec<String>> {
+        let field_ids =
+            self.field_ids(owner, parameters.iter().map(|p| (p.name(), p.field_id())))?;
+        parameters
+            .iter()
+            .zip(field_ids)
+            .map(|(p, field_id)| {
+                Ok(format!(
+                    "{}: {} {}{}",
+                    field_id,
+                    value_type_string(p.value_type())?,
+                    p.name(),
+                    default_value_str(p.value()),
+                ))
+            })
+            .collect::<Result<Vec<String>>>()
     }
 }
 
@@ -223,8 +445,24 @@ impl ThriftWriter {
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
-fn value_type_string(vt: &ValueType) -> String {
+/// The type of a field, parameter or value; an outer `Optional` is expressed by requiredness.
+fn value_type_string(vt: &ValueType) -> Result<String> {
+    match vt {
+        ValueType::Optional(t) => element_type_string(t),
+        _ => element_type_string(vt),
+    }
+}
+
+/// `void` is only valid as the result of a function.
+fn return_type_string(vt: &ValueType) -> Result<String> {
     match vt {
+        ValueType::Unit => Ok("void".to_string()),
+        _ => value_type_string(vt),
+    }
+}
+
+fn element_type_string(vt: &ValueType) -> Result<String> {
+    Ok(match vt {
         ValueType::Known(kt) => match kt {
             KnownType::I8 => "i8",
             KnownType::U8 => "byte",
@@ -232,30 +470,39 @@ fn value_type_string(vt: &ValueType) -> String {
             KnownType::I32 | KnownType::U32 => "i32",
             KnownType::I64 | KnownType::U64 => "i64",
             KnownType::F32 | KnownType::F64 => "double",
-            KnownType::Boolean => "boolean",
+            KnownType::Boolean => "bool",
             KnownType::Char => "i8",
-            KnownType::String => "string",
+            KnownType::String | KnownType::StringSlice => "string",
         }
         .to_string(),
         ValueType::Reference(t) => t.to_string(),
-        ValueType::Array(t) => {
-            format!("list<{}>", value_type_string(&t))
+        ValueType::Lifetime(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("lifetime".to_string()).into())
         }
-        ValueType::Set(t) => format!("set<{}>", value_type_string(&t)),
-        ValueType::Map(kt, vt) => format!(
-            "map<{}, {}>",
-            value_type_string(&kt),
-            value_type_string(&vt)
-        ),
-        ValueType::Constrained(t, tc) => {
-            assert!(!tc.is_empty());
+        ValueType::Borrowed(_, t)
+        | ValueType::BorrowedMut(_, t)
+        | ValueType::Pointer(t)
+        | ValueType::PointerMut(t)
+        | ValueType::Boxed(t) => element_type_string(t)?,
+        ValueType::Optional(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("optional".to_string()).into())
+        }
+        ValueType::Unit => return Err(ErrorKind::UnsupportedElementKind("unit".to_string()).into()),
+        ValueType::Tuple(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("tuple".to_string()).into())
+        }
+        ValueType::Result(_, _) => {
+            return Err(ErrorKind::UnsupportedElementKind("result".to_string()).into())
+        }
+        ValueType::Slice(t) | ValueType::Array(t) | ValueType::FixedArray(t, _) => {
+            format!("list<{}>", element_type_string(t)?)
+        }
+        ValueType::Set(t) => format!("set<{}>", element_type_string(t)?),
+        ValueType::Map(kt, vt) => {
             format!(
-                "{}: {}",
-                t,
-                tc.iter()
-                    .map(value_type_string)
-                    .collect::<Vec<String>>()
-                    .join(" + ")
+                "map<{}, {}>",
+                element_type_string(kt)?,
+                element_type_string(vt)?
             )
         }
         ValueType::Generic(t, gt) => {
@@ -263,25 +510,39 @@ fn value_type_string(vt: &ValueType) -> String {
             format!(
                 "{}<{}>",
                 t,
-                gt.iter()
-                    .map(value_type_string)
-                    .collect::<Vec<String>>()
-                    .join(", ")
+                element_type_strings(
+                    &gt.iter()
+                        .filter(|t| !matches!(t, ValueType::Lifetime(_)))
+                        .cloned()
+                        .collect::<Vec<ValueType>>()
+                )?
             )
         }
         ValueType::Function(pt, rt) => {
             format!(
                 "fn({}){}",
-                pt.iter()
-                    .map(value_type_string)
-                    .collect::<Vec<String>>()
-                    .join(", "),
+                element_type_strings(pt)?,
                 match rt {
                     None => String::new(),
-                    Some(rt) => format!(" -> {}", value_type_string(rt)),
+                    Some(rt) => format!(" -> {}", return_type_string(rt)?),
                 }
             )
         }
+    })
+}
+
+fn element_type_strings(vts: &[ValueType]) -> Result<String> {
+    Ok(vts
+        .iter()
+        .map(element_type_string)
+        .collect::<Result<Vec<String>>>()?
+        .join(", "))
+}
+
+fn default_value_str(value: &Option<Value>) -> String {
+    match value {
+        None => String::new(),
+        Some(value) => format!(" = {}", value_str(value)),
     }
 }
 
@@ -299,13 +560,10 @@ fn value_str(value: &Value) -> String {
         Value::F64(v) => v.to_string(),
         Value::Boolean(v) => v.to_string(),
         Value::Char(v) => v.to_string(),
-        Value::String(v) => v.to_string(),
+        Value::String(v) => string_literal(v),
         Value::Values(vs) => format!(
             "[{}]",
-            vs.iter()
-                .map(|v| value_str(v))
-                .collect::<Vec<String>>()
-                .join(", ")
+            vs.iter().map(value_str).collect::<Vec<String>>().join(", ")
         ),
         Value::NamedValues(vs) => {
             format!(
@@ -0,0 +1,737 @@
+/*!
+Writes modules as TypeScript source (`.ts`) or declaration (`.d.ts`) files.
+
+Structured types are written as interfaces, except unions which are written as a type that is a
+union of single-property object types. Enumerations are written as a numeric or string `enum`, or as
+a union of literal types, depending on the writer's `EnumerationStyle`. Functions are written as
+`declare function` signatures; only public items are exported.
+
+# Example
+
+```rust
+use code_writer::language::typescript::TypeScriptWriter;
+use code_writer::model::{
+    Builder, Field, HasVisibility, Identifier, IsOptional, Module, StructuredType, ValueType,
+    Visibility,
+};
+use code_writer::writer::module_to_string;
+
+let module = Module::new(Identifier::new("address"))
+    .add_structure(
+        StructuredType::structure(Identifier::new("Country"))
+            .set_visibility(Visibility::Public)
+            .add_field(Field::new(Identifier::new("code"), ValueType::string()).build())
+            .add_field(Field::new(Identifier::new("name"), ValueType::string()).optional().build())
+            .build(),
+    )
+    .build();
+
+assert_eq!(
+    module_to_string(&TypeScriptWriter::default(), &module).unwrap(),
+    r#"export interface Country {
+    code: string;
+    name?: string;
+}
+"#
+);
+```
+*/
+
+use crate::error::{ErrorKind, Result};
+use crate::language::helpers::{is_integer, not_generic, string_literal};
+use crate::model::identity::Namespace;
+use crate::model::{
+    Comment, Enumeration, Field, FunctionDecl, HasDocumentation, HasGenericParameters, HasName,
+    HasOptionalType, HasOptionalValue, HasType, HasValue, HasVisibility, Import, IsOptional,
+    KnownType, Module, NamedValue, Parameter, ProtectedRegion, StructuredType, StructuredTypeKind,
+    TypeAlias, Value, ValueType, Visibility,
+};
+use crate::project::{relative_path, ProjectLayout};
+use crate::writer::{CodeWriter, List, ListWrapping, ModuleWriter};
+use std::io::Write;
+use std::path::PathBuf;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// How an `Enumeration` is written; variants without a value are numbered, or named, implicitly.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum EnumerationStyle {
+    /// `enum X { A, B = 2 }`, with string or numeric variant values.
+    #[default]
+    Enum,
+    /// `enum X { A = "A", B = "b" }`, using the variant name where there is no value.
+    StringEnum,
+    /// `type X = "A" | "B";`, using the variant name where there is no value.
+    Union,
+}
+
+#[derive(Default)]
+pub struct TypeScriptWriter {
+    enumeration_style: EnumerationStyle,
+    declaration_file: bool,
+}
+
+/// Every module is written to `index.ts`, or `index.d.ts`, in a directory for its namespace, so
+/// that a sub-module is imported from `./name`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum TypeScriptLayout {
+    #[default]
+    Sources,
+    Declarations,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<W> ModuleWriter<W> for TypeScriptWriter
+where
+    W: Write,
+{
+    fn write_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        if let Some(documentation) = module.documentation() {
+            self.write_tsdoc(
+                writer,
+                documentation,
+                &["@packageDocumentation".to_string()],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_sub_module(&self, writer: &mut CodeWriter<W>, module: &Module) -> Result<()> {
+        writer.write_str(&format!(
+            "export * as {} from './{}';",
+            module.name(),
+            module.name()
+        ))?;
+        writer.new_line()
+    }
+
+    fn write_import(&self, writer: &mut CodeWriter<W>, import: &Import) -> Result<()> {
+        let name = match import.namespace().path().last() {
+            None => {
+                return Err(ErrorKind::UnsupportedElementKind("empty_import".to_string()).into())
+            }
+            Some(name) => name,
+        };
+        let path = module_specifier(writer, import.namespace());
+        if import.items().is_empty() {
+            writer.write_str(&format!("import * as {} from '{}';", name, path))?;
+        } else {
+            let items: Vec<String> = import
+                .items()
+                .iter()
+                .map(|item| match item.alias() {
+                    None => item.name().to_string(),
+                    Some(alias) => format!("{} as {}", item.name(), alias),
+                })
+                .collect();
+            let single_line = format!("import {{ {} }} from '{}';", items.join(", "), path);
+            if writer.fits(&single_line) {
+                writer.write_str(&single_line)?;
+            } else {
+                writer.write_str("import {")?;
+                writer.new_line()?;
+                writer.indent();
+                for item in items {
+                    writer.write_str(&format!("{},", item))?;
+                    writer.new_line()?;
+                }
+                writer.outdent();
+                writer.write_str(&format!("}} from '{}';", path))?;
+            }
+        }
+        writer.new_line()
+    }
+
+    fn write_comment(&self, writer: &mut CodeWriter<W>, comment: &Comment) -> Result<()> {
+        if comment.is_line() {
+            writer.write_doc_text("// ", comment.text())
+        } else {
+            writer.write_str("/*")?;
+            writer.new_line()?;
+            writer.write_doc_text(" * ", comment.text())?;
+            writer.write_str(" */")?;
+            writer.new_line()
+        }
+    }
+
+    fn write_structured_type(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        if !record.associated_types().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("associated_type".to_string()).into());
+        }
+        if !record.associated_constants().is_empty() {
+            return Err(
+                ErrorKind::UnsupportedElementKind("associated_constant".to_string()).into(),
+            );
+        }
+        match record.kind() {
+            StructuredTypeKind::Union => self.write_union(writer, record),
+            StructuredTypeKind::Service if !record.fields().is_empty() => {
+                Err(ErrorKind::UnsupportedElementKind("service_field".to_string()).into())
+            }
+            _ => self.write_interface(writer, record),
+        }
+    }
+
+    fn write_enumeration(
+        &self,
+        writer: &mut CodeWriter<W>,
+        enumeration: &Enumeration,
+    ) -> Result<()> {
+        not_generic(enumeration)?;
+        let mut values: Vec<Option<String>> = Default::default();
+        for variant in enumeration.variants() {
+            if variant.has_payload() {
+                return Err(ErrorKind::UnsupportedElementKind(
+                    "enumeration_variant_payload".to_string(),
+                )
+                .into());
+            }
+            values.push(match (variant.value(), &self.enumeration_style) {
+                (Some(value @ Value::String(_)), _) => Some(literal(value)?),
+                (Some(value), _) if is_integer(value) => Some(literal(value)?),
+                (Some(_), _) => {
+                    return Err(
+                        ErrorKind::UnsupportedElementKind("enumeration_value".to_string()).into(),
+                    )
+                }
+                (None, EnumerationStyle::Enum) => None,
+                (None, _) => Some(string_literal(variant.name().as_ref())),
+            });
+        }
+
+        self.write_documentation(writer, enumeration)?;
+        self.write_export(writer, enumeration)?;
+        if self.enumeration_style == EnumerationStyle::Union {
+            let literals: Vec<String> = values.into_iter().flatten().collect();
+            return self.write_union_type(writer, enumeration.name().as_ref(), &literals);
+        }
+        if self.declaration_file {
+            writer.write_str("declare ")?;
+        }
+        writer.write_str(&format!("enum {} {{", enumeration.name()))?;
+        if !enumeration.variants().is_empty() {
+            writer.new_line()?;
+            writer.indent();
+            for (variant, value) in enumeration.variants().iter().zip(values) {
+                writer.begin_element(variant.name());
+                self.write_documentation(writer, variant)?;
+                match value {
+                    None => writer.write_str(&format!("{},", variant.name()))?,
+                    Some(value) => writer.write_str(&format!("{} = {},", variant.name(), value))?,
+                }
+                writer.new_line()?;
+                writer.end_element();
+            }
+            writer.outdent();
+        }
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn write_constant(&self, writer: &mut CodeWriter<W>, constant: &NamedValue) -> Result<()> {
+        self.write_named_value(writer, "const", constant)
+    }
+
+    fn write_variable(&self, writer: &mut CodeWriter<W>, variable: &NamedValue) -> Result<()> {
+        self.write_named_value(writer, "let", variable)
+    }
+
+    fn write_function_decl(
+        &self,
+        writer: &mut CodeWriter<W>,
+        function_decl: &FunctionDecl,
+    ) -> Result<()> {
+        self.write_function_documentation(writer, function_decl)?;
+        self.write_export(writer, function_decl)?;
+        writer.write_str("declare function ")?;
+        self.write_signature(writer, function_decl)
+    }
+
+    fn write_type_alias(&self, writer: &mut CodeWriter<W>, type_alias: &TypeAlias) -> Result<()> {
+        let line = format!(
+            "type {}{} = {};",
+            type_alias.name(),
+            self.generic_parameters(type_alias)?,
+            value_type(type_alias.value_type())?
+        );
+        self.write_documentation(writer, type_alias)?;
+        self.write_export(writer, type_alias)?;
+        writer.write_str(&line)?;
+        writer.new_line()
+    }
+
+    fn write_region(&self, writer: &mut CodeWriter<W>, region: &ProtectedRegion) -> Result<()> {
+        writer.write_region_markers("// ", region.name().as_ref())
+    }
+}
+
+impl ProjectLayout for TypeScriptLayout {
+    fn module_file_path(&self, namespace: &Namespace) -> PathBuf {
+        let mut path: PathBuf = namespace.path().iter().map(|id| id.as_ref()).collect();
+        match self {
+            TypeScriptLayout::Sources => path.push("index.ts"),
+            TypeScriptLayout::Declarations => path.push("index.d.ts"),
+        }
+        path
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TypeScriptWriter {
+    /// A writer for `.d.ts` files, where enumerations, constants and variables are ambient.
+    pub fn declaration_file() -> Self {
+        Self {
+            declaration_file: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_declaration_file(&self) -> bool {
+        self.declaration_file
+    }
+
+    pub fn enumeration_style(&self) -> &EnumerationStyle {
+        &self.enumeration_style
+    }
+
+    pub fn set_enumeration_style(&mut self, enumeration_style: EnumerationStyle) -> &mut Self {
+        self.enumeration_style = enumeration_style;
+        self
+    }
+
+    fn write_interface<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        self.write_documentation(writer, record)?;
+        self.write_export(writer, record)?;
+        writer.write_str(&format!(
+            "interface {}{}",
+            record.name(),
+            self.generic_parameters(record)?
+        ))?;
+        if !record.extends().is_empty() {
+            writer.write_str(&format!(" extends {}", value_types(record.extends())?))?;
+        }
+        writer.write_str(" {")?;
+        if !record.fields().is_empty() || !record.methods().is_empty() {
+            writer.new_line()?;
+            writer.indent();
+            for field in record.fields() {
+                writer.begin_element(field.name());
+                self.write_documentation(writer, field)?;
+                writer.write_str(&format!("{};", self.field(field)?))?;
+                writer.new_line()?;
+                writer.end_element();
+            }
+            for method in record.methods() {
+                writer.begin_element(method.name());
+                self.write_function_documentation(writer, method)?;
+                self.write_signature(writer, method)?;
+                writer.end_element();
+            }
+            writer.outdent();
+        }
+        writer.write_str("}")?;
+        writer.new_line()
+    }
+
+    fn write_union<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        record: &StructuredType,
+    ) -> Result<()> {
+        if !record.methods().is_empty() {
+            return Err(ErrorKind::UnsupportedElementKind("union_method".to_string()).into());
+        }
+        let alternatives = record
+            .fields()
+            .iter()
+            .map(|field| Ok(format!("{{ {} }}", self.field(field)?)))
+            .collect::<Result<Vec<String>>>()?;
+        self.write_documentation(writer, record)?;
+        self.write_export(writer, record)?;
+        self.write_union_type(
+            writer,
+            &format!("{}{}", record.name(), self.generic_parameters(record)?),
+            &alternatives,
+        )
+    }
+
+    fn write_union_type<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        name: &str,
+        alternatives: &[String],
+    ) -> Result<()> {
+        let head = format!("type {} =", name);
+        let single_line = if alternatives.is_empty() {
+            format!("{} never;", head)
+        } else {
+            format!("{} {};", head, alternatives.join(" | "))
+        };
+        if alternatives.len() < 2 || writer.fits(&single_line) {
+            writer.write_str(&single_line)?;
+            return writer.new_line();
+        }
+        writer.write_str(&head)?;
+        writer.new_line()?;
+        writer.indent();
+        for (i, alternative) in alternatives.iter().enumerate() {
+            writer.write_str(&format!("| {}", alternative))?;
+            if i + 1 == alternatives.len() {
+                writer.write_str(";")?;
+            }
+            writer.new_line()?;
+        }
+        writer.outdent();
+        Ok(())
+    }
+
+    fn write_named_value<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        keyword: &str,
+        named_value: &NamedValue,
+    ) -> Result<()> {
+        self.write_documentation(writer, named_value)?;
+        self.write_export(writer, named_value)?;
+        if self.declaration_file {
+            writer.write_str(&format!(
+                "declare {} {}: {};",
+                keyword,
+                named_value.name(),
+                value_type(named_value.value_type())?
+            ))?;
+        } else {
+            writer.write_str(&format!(
+                "{} {}: {} = {};",
+                keyword,
+                named_value.name(),
+                value_type(named_value.value_type())?,
+                literal(named_value.value())?
+            ))?;
+        }
+        writer.new_line()
+    }
+
+    fn write_signature<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        function: &FunctionDecl,
+    ) -> Result<()> {
+        if function.has_body() {
+            return Err(ErrorKind::UnsupportedElementKind("function_body".to_string()).into());
+        }
+        writer.write_str(&format!(
+            "{}{}",
+            function.name(),
+            self.generic_parameters(function)?
+        ))?;
+        let parameters = function
+            .parameters()
+            .iter()
+            .map(parameter)
+            .collect::<Result<Vec<String>>>()?;
+        let return_type = match function.value_type() {
+            None => "void".to_string(),
+            Some(vt) => value_type(vt)?,
+        };
+        writer.write_list_then(
+            &List::new("(", ")", ListWrapping::OnePerLine),
+            &parameters,
+            &format!(": {};", return_type),
+        )?;
+        writer.new_line()
+    }
+
+    fn field(&self, field: &Field) -> Result<String> {
+        Ok(match field.value_type() {
+            ValueType::Optional(vt) => format!("{}?: {}", field.name(), value_type(vt)?),
+            vt if field.is_optional() => format!("{}?: {}", field.name(), value_type(vt)?),
+            vt => format!("{}: {}", field.name(), value_type(vt)?),
+        })
+    }
+
+    fn generic_parameters(&self, item: &dyn HasGenericParameters) -> Result<String> {
+        if item.has_where_predicates() {
+            return Err(ErrorKind::UnsupportedElementKind("where_clause".to_string()).into());
+        }
+        let mut parameters: Vec<String> = Default::default();
+        for parameter in item
+            .generic_parameters()
+            .iter()
+            .filter(|parameter| !parameter.is_lifetime())
+        {
+            let mut parameter_str = parameter.name().to_string();
+            let bounds = parameter
+                .bounds()
+                .iter()
+                .filter(|bound| !matches!(bound, ValueType::Lifetime(_)))
+                .map(value_type)
+                .collect::<Result<Vec<String>>>()?;
+            if !bounds.is_empty() {
+                parameter_str.push_str(&format!(" extends {}", bounds.join(" & ")));
+            }
+            if let Some(default) = parameter.default() {
+                parameter_str.push_str(&format!(" = {}", value_type(default)?));
+            }
+            parameters.push(parameter_str);
+        }
+        Ok(if parameters.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", parameters.join(", "))
+        })
+    }
+
+    fn write_export<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasVisibility,
+    ) -> Result<()> {
+        if let Some(Visibility::Public) = item.visibility() {
+            writer.write_str("export ")?;
+        }
+        Ok(())
+    }
+
+    fn write_function_documentation<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        function: &FunctionDecl,
+    ) -> Result<()> {
+        let mut tags: Vec<String> = Default::default();
+        for parameter in function.parameters() {
+            if let Some(documentation) = parameter.documentation() {
+                tags.push(format!("@param {} - {}", parameter.name(), documentation));
+            }
+        }
+        for throws in function.throws() {
+            let mut tag = format!("@throws {{@link {}}}", value_type(throws.value_type())?);
+            if let Some(documentation) = throws.documentation() {
+                tag.push_str(&format!(" {}", documentation));
+            }
+            tags.push(tag);
+        }
+        match function.documentation() {
+            None if tags.is_empty() => Ok(()),
+            None => self.write_tsdoc(writer, "", &tags),
+            Some(documentation) => self.write_tsdoc(writer, documentation, &tags),
+        }
+    }
+
+    fn write_documentation<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        item: &dyn HasDocumentation,
+    ) -> Result<()> {
+        if let Some(documentation) = item.documentation() {
+            self.write_tsdoc(writer, documentation, &[])?;
+        }
+        Ok(())
+    }
+
+    fn write_tsdoc<W: Write>(
+        &self,
+        writer: &mut CodeWriter<W>,
+        text: &str,
+        tags: &[String],
+    ) -> Result<()> {
+        let single_line = format!("/** {} */", text.trim());
+        if tags.is_empty() && !text.trim().contains('\n') && writer.fits(&single_line) {
+            writer.write_str(&single_line)?;
+            return writer.new_line();
+        }
+        writer.write_str("/**")?;
+        writer.new_line()?;
+        if !text.trim().is_empty() {
+            writer.write_doc_text(" * ", text)?;
+            if !tags.is_empty() {
+                writer.write_str(" *")?;
+                writer.new_line()?;
+            }
+        }
+        for tag in tags {
+            writer.write_str(&format!(" * {}", tag))?;
+            writer.new_line()?;
+        }
+        writer.write_str(" */")?;
+        writer.new_line()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The specifier of the module at `namespace`, relative to the directory of the module being
+/// written; as every module is the index of its own directory, directories are all that matter.
+///
+fn module_specifier<W: Write>(writer: &CodeWriter<W>, namespace: &Namespace) -> String {
+    let directory = |namespace: &Namespace| -> PathBuf {
+        namespace.path().iter().map(|id| id.as_ref()).collect()
+    };
+    let path = match writer.namespace() {
+        None => directory(namespace),
+        Some(current) => relative_path(&directory(current), &directory(namespace)),
+    };
+    let path: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    match path.first() {
+        None => ".".to_string(),
+        Some(first) if first == ".." => path.join("/"),
+        Some(_) => format!("./{}", path.join("/")),
+    }
+}
+
+fn parameter(parameter: &Parameter) -> Result<String> {
+    Ok(match parameter.value_type() {
+        ValueType::Optional(vt) => format!("{}?: {}", parameter.name(), value_type(vt)?),
+        vt if parameter.is_optional() || parameter.has_value() => {
+            format!("{}?: {}", parameter.name(), value_type(vt)?)
+        }
+        vt => format!("{}: {}", parameter.name(), value_type(vt)?),
+    })
+}
+
+fn value_types(vts: &[ValueType]) -> Result<String> {
+    Ok(vts
+        .iter()
+        .map(value_type)
+        .collect::<Result<Vec<String>>>()?
+        .join(", "))
+}
+
+fn value_type(vt: &ValueType) -> Result<String> {
+    Ok(match vt {
+        ValueType::Known(kt) => match kt {
+            KnownType::I8
+            | KnownType::U8
+            | KnownType::I16
+            | KnownType::U16
+            | KnownType::I32
+            | KnownType::U32
+            | KnownType::I64
+            | KnownType::U64
+            | KnownType::F32
+            | KnownType::F64 => "number",
+            KnownType::Boolean => "boolean",
+            KnownType::Char | KnownType::String | KnownType::StringSlice => "string",
+        }
+        .to_string(),
+        ValueType::Reference(t) => t.to_string(),
+        ValueType::Borrowed(_, t)
+        | ValueType::BorrowedMut(_, t)
+        | ValueType::Pointer(t)
+        | ValueType::PointerMut(t)
+        | ValueType::Boxed(t) => value_type(t)?,
+        ValueType::Slice(t) | ValueType::Array(t) | ValueType::FixedArray(t, _) => {
+            let member = value_type(t)?;
+            if member.contains(' ') {
+                format!("({})[]", member)
+            } else {
+                format!("{}[]", member)
+            }
+        }
+        ValueType::Unit => "void".to_string(),
+        ValueType::Tuple(ts) => format!("[{}]", value_types(ts)?),
+        ValueType::Optional(t) => format!("{} | undefined", value_type(t)?),
+        ValueType::Set(t) => format!("Set<{}>", value_type(t)?),
+        ValueType::Map(kt, vt) => match kt.as_ref() {
+            ValueType::Known(KnownType::Boolean) | ValueType::Known(KnownType::Char) => {
+                format!("Map<{}, {}>", value_type(kt)?, value_type(vt)?)
+            }
+            ValueType::Known(_) => format!("Record<{}, {}>", value_type(kt)?, value_type(vt)?),
+            _ => format!("Map<{}, {}>", value_type(kt)?, value_type(vt)?),
+        },
+        ValueType::Generic(t, ts) => format!("{}<{}>", t, value_types(ts)?),
+        ValueType::Function(pts, rt) => format!(
+            "({}) => {}",
+            pts.iter()
+                .enumerate()
+                .map(|(i, pt)| Ok(format!("arg{}: {}", i, value_type(pt)?)))
+                .collect::<Result<Vec<String>>>()?
+                .join(", "),
+            match rt {
+                None => "void".to_string(),
+                Some(rt) => value_type(rt)?,
+            }
+        ),
+        ValueType::Lifetime(_) => {
+            return Err(ErrorKind::UnsupportedElementKind("lifetime".to_string()).into())
+        }
+        ValueType::Result(_, _) => {
+            return Err(ErrorKind::UnsupportedElementKind("result".to_string()).into())
+        }
+    })
+}
+
+fn non_finite_literal(v: f64) -> String {
+    if v.is_nan() {
+        "NaN"
+    } else if v > 0.0 {
+        "Infinity"
+    } else {
+        "-Infinity"
+    }
+    .to_string()
+}
+
+fn literal(value: &Value) -> Result<String> {
+    Ok(match value {
+        Value::I8(v) => v.to_string(),
+        Value::U8(v) => v.to_string(),
+        Value::I16(v) => v.to_string(),
+        Value::U16(v) => v.to_string(),
+        Value::I32(v) => v.to_string(),
+        Value::U32(v) => v.to_string(),
+        Value::I64(v) => v.to_string(),
+        Value::U64(v) => v.to_string(),
+        Value::F32(v) if v.is_finite() => v.to_string(),
+        Value::F64(v) if v.is_finite() => v.to_string(),
+        Value::F32(v) => non_finite_literal(*v as f64),
+        Value::F64(v) => non_finite_literal(*v),
+        Value::Boolean(v) => v.to_string(),
+        Value::Char(v) => string_literal(&v.to_string()),
+        Value::String(v) => string_literal(v),
+        Value::Identifier(v) => v.to_string(),
+        Value::Values(vs) => format!(
+            "[{}]",
+            vs.iter()
+                .map(literal)
+                .collect::<Result<Vec<String>>>()?
+                .join(", ")
+        ),
+        Value::NamedValues(vs) => {
+            let mut entries = vs
+                .iter()
+                .map(|(k, v)| Ok(format!("{}: {}", literal(k)?, literal(v)?)))
+                .collect::<Result<Vec<String>>>()?;
+            entries.sort();
+            format!("{{ {} }}", entries.join(", "))
+        }
+    })
+}
@@ -153,4 +153,8 @@ pub mod language;
 
 pub mod model;
 
+pub mod project;
+
+pub mod source_map;
+
 pub mod writer;
@@ -13,6 +13,7 @@ More detailed description, with
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+use crate::model::identity::{HasName, Identifier};
 use crate::model::Builder;
 use std::fmt::Debug;
 
@@ -22,6 +23,15 @@ pub struct Comment {
     is_block: bool,
 }
 
+///
+/// A named region, delimited by marker comments, whose content is written by hand and preserved
+/// when the file containing it is regenerated.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtectedRegion {
+    name: Identifier,
+}
+
 // ------------------------------------------------------------------------------------------------
 
 pub trait HasDocumentation {
@@ -91,6 +101,18 @@ impl Comment {
     }
 }
 
+// ------------------------------------------------------------------------------------------------
+
+impl_has_name!(ProtectedRegion);
+
+impl Builder for ProtectedRegion {}
+
+impl ProtectedRegion {
+    pub fn new(name: Identifier) -> Self {
+        Self { name }
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
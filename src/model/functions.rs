@@ -7,10 +7,11 @@ More detailed description, with
 
 */
 
-use crate::model::comments::HasDocumentation;
-use crate::model::identity::{HasName, Identifier};
+use crate::model::comments::{Comment, HasDocumentation};
+use crate::model::generics::{GenericParameter, HasGenericParameters, WherePredicate};
+use crate::model::identity::{HasName, Identifier, Namespace};
 use crate::model::modules::{HasVisibility, Visibility};
-use crate::model::properties::{HasProperties, IsOptional, Property};
+use crate::model::properties::{HasFieldId, HasProperties, IsOptional, Property};
 use crate::model::values::{HasOptionalType, HasOptionalValue, HasType, Value, ValueType};
 use crate::model::Builder;
 
@@ -22,20 +23,102 @@ use crate::model::Builder;
 pub struct Parameter {
     properties: Vec<Property>,
     optional: bool,
+    field_id: Option<u32>,
     name: Identifier,
     documentation: Option<String>,
     value_type: ValueType,
     value: Option<Value>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum Receiver {
+    Value,
+    Reference,
+    MutableReference,
+}
+
 #[derive(Clone, Debug)]
 pub struct FunctionDecl {
     properties: Vec<Property>,
     visibility: Option<Visibility>,
     name: Identifier,
+    generic_parameters: Vec<GenericParameter>,
+    where_predicates: Vec<WherePredicate>,
     documentation: Option<String>,
+    receiver: Option<Receiver>,
     parameters: Vec<Parameter>,
     value_type: Option<ValueType>,
+    throws: Vec<Parameter>,
+    oneway: bool,
+    body: Option<StatementBlock>,
+}
+
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UnaryOperator {
+    Not,
+    Negate,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    And,
+    Or,
+}
+
+#[derive(Clone, Debug)]
+pub enum Expression {
+    Literal(Value),
+    Variable(Identifier),
+    Path(Namespace),
+    Field(Box<Expression>, Identifier),
+    Call(Box<Expression>, Vec<Expression>),
+    MethodCall(Box<Expression>, Identifier, Vec<Expression>),
+    StructLiteral(Identifier, Vec<(Identifier, Expression)>),
+    Unary(UnaryOperator, Box<Expression>),
+    Binary(Box<Expression>, BinaryOperator, Box<Expression>),
+}
+
+#[derive(Clone, Debug)]
+pub struct MatchArm {
+    pattern: Expression,
+    guard: Option<Expression>,
+    body: StatementBlock,
+}
+
+#[derive(Clone, Debug)]
+pub enum Statement {
+    Comment(Comment),
+    Let(Identifier, Option<ValueType>, Option<Expression>),
+    LetMut(Identifier, Option<ValueType>, Option<Expression>),
+    Assign(Expression, Expression),
+    Expression(Expression),
+    Return(Option<Expression>),
+    If(Expression, StatementBlock, Option<StatementBlock>),
+    Match(Expression, Vec<MatchArm>),
+    Loop(StatementBlock),
+    While(Expression, StatementBlock),
+    For(Identifier, Expression, StatementBlock),
+    Break,
+    Continue,
+    Block(StatementBlock),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct StatementBlock {
+    statements: Vec<Statement>,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -54,6 +137,8 @@ impl_has_properties!(Parameter);
 
 impl_is_optional!(Parameter);
 
+impl_has_field_id!(Parameter);
+
 impl_has_name!(Parameter);
 
 impl_has_documentation!(Parameter);
@@ -69,6 +154,7 @@ impl Parameter {
         Self {
             properties: Default::default(),
             optional: false,
+            field_id: None,
             name,
             documentation: None,
             value_type,
@@ -80,6 +166,7 @@ impl Parameter {
         Self {
             properties: Default::default(),
             optional: false,
+            field_id: None,
             name,
             documentation: None,
             value_type,
@@ -96,6 +183,8 @@ impl_has_visibility!(FunctionDecl);
 
 impl_has_name!(FunctionDecl);
 
+impl_has_generic_parameters!(FunctionDecl);
+
 impl_has_documentation!(FunctionDecl);
 
 impl_has_optional_type!(FunctionDecl);
@@ -108,9 +197,15 @@ impl FunctionDecl {
             properties: Default::default(),
             visibility: None,
             name,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
             documentation: None,
+            receiver: None,
             parameters: Default::default(),
             value_type: None,
+            throws: Default::default(),
+            oneway: false,
+            body: None,
         }
     }
 
@@ -119,16 +214,44 @@ impl FunctionDecl {
             properties: Default::default(),
             visibility: Some(visibility),
             name,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
             documentation: None,
+            receiver: None,
             parameters: Default::default(),
             value_type: None,
+            throws: Default::default(),
+            oneway: false,
+            body: None,
         }
     }
 
+    pub fn receiver(&self) -> &Option<Receiver> {
+        &self.receiver
+    }
+
+    pub fn is_method(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    pub fn set_receiver(&mut self, receiver: Receiver) -> &mut Self {
+        self.receiver = Some(receiver);
+        self
+    }
+
+    pub fn unset_receiver(&mut self) -> &mut Self {
+        self.receiver = None;
+        self
+    }
+
     pub fn parameters(&self) -> &Vec<Parameter> {
         &self.parameters
     }
 
+    pub fn parameters_mut(&mut self) -> &mut Vec<Parameter> {
+        &mut self.parameters
+    }
+
     pub fn set_parameters(&mut self, parameters: Vec<Parameter>) -> &mut Self {
         self.parameters = parameters;
         self
@@ -138,6 +261,223 @@ impl FunctionDecl {
         self.parameters.push(parameter);
         self
     }
+
+    pub fn throws(&self) -> &Vec<Parameter> {
+        &self.throws
+    }
+
+    pub fn throws_mut(&mut self) -> &mut Vec<Parameter> {
+        &mut self.throws
+    }
+
+    pub fn set_throws(&mut self, throws: Vec<Parameter>) -> &mut Self {
+        self.throws = throws;
+        self
+    }
+
+    pub fn add_throws(&mut self, throws: Parameter) -> &mut Self {
+        self.throws.push(throws);
+        self
+    }
+
+    pub fn is_oneway(&self) -> bool {
+        self.oneway
+    }
+
+    pub fn set_oneway(&mut self, oneway: bool) -> &mut Self {
+        self.oneway = oneway;
+        self
+    }
+
+    pub fn body(&self) -> &Option<StatementBlock> {
+        &self.body
+    }
+
+    pub fn has_body(&self) -> bool {
+        self.body.is_some()
+    }
+
+    pub fn set_body(&mut self, body: StatementBlock) -> &mut Self {
+        self.body = Some(body);
+        self
+    }
+
+    pub fn unset_body(&mut self) -> &mut Self {
+        self.body = None;
+        self
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl From<Value> for Expression {
+    fn from(v: Value) -> Self {
+        Self::Literal(v)
+    }
+}
+
+impl From<Identifier> for Expression {
+    fn from(v: Identifier) -> Self {
+        Self::Variable(v)
+    }
+}
+
+impl From<Namespace> for Expression {
+    fn from(v: Namespace) -> Self {
+        Self::Path(v)
+    }
+}
+
+impl Expression {
+    pub fn literal(value: Value) -> Self {
+        Self::Literal(value)
+    }
+
+    pub fn variable(name: Identifier) -> Self {
+        Self::Variable(name)
+    }
+
+    pub fn field(receiver: Expression, name: Identifier) -> Self {
+        Self::Field(Box::new(receiver), name)
+    }
+
+    pub fn call(function: Expression, arguments: Vec<Expression>) -> Self {
+        Self::Call(Box::new(function), arguments)
+    }
+
+    pub fn method_call(receiver: Expression, name: Identifier, arguments: Vec<Expression>) -> Self {
+        Self::MethodCall(Box::new(receiver), name, arguments)
+    }
+
+    pub fn struct_literal(name: Identifier, fields: Vec<(Identifier, Expression)>) -> Self {
+        Self::StructLiteral(name, fields)
+    }
+
+    pub fn logical_not(expression: Expression) -> Self {
+        Self::Unary(UnaryOperator::Not, Box::new(expression))
+    }
+
+    pub fn negate(expression: Expression) -> Self {
+        Self::Unary(UnaryOperator::Negate, Box::new(expression))
+    }
+
+    pub fn binary(lhs: Expression, operator: BinaryOperator, rhs: Expression) -> Self {
+        Self::Binary(Box::new(lhs), operator, Box::new(rhs))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Builder for MatchArm {}
+
+impl MatchArm {
+    pub fn new(pattern: Expression, body: StatementBlock) -> Self {
+        Self {
+            pattern,
+            guard: None,
+            body,
+        }
+    }
+
+    pub fn with_guard(pattern: Expression, guard: Expression, body: StatementBlock) -> Self {
+        Self {
+            pattern,
+            guard: Some(guard),
+            body,
+        }
+    }
+
+    pub fn pattern(&self) -> &Expression {
+        &self.pattern
+    }
+
+    pub fn guard(&self) -> &Option<Expression> {
+        &self.guard
+    }
+
+    pub fn body(&self) -> &StatementBlock {
+        &self.body
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl From<Expression> for Statement {
+    fn from(v: Expression) -> Self {
+        Self::Expression(v)
+    }
+}
+
+impl Statement {
+    pub fn comment(text: &str) -> Self {
+        Self::Comment(Comment::line(text))
+    }
+
+    pub fn let_binding(name: Identifier, value: Expression) -> Self {
+        Self::Let(name, None, Some(value))
+    }
+
+    pub fn let_mut_binding(name: Identifier, value: Expression) -> Self {
+        Self::LetMut(name, None, Some(value))
+    }
+
+    pub fn assign(lhs: Expression, rhs: Expression) -> Self {
+        Self::Assign(lhs, rhs)
+    }
+
+    pub fn return_value(value: Expression) -> Self {
+        Self::Return(Some(value))
+    }
+
+    pub fn if_then(condition: Expression, then_block: StatementBlock) -> Self {
+        Self::If(condition, then_block, None)
+    }
+
+    pub fn if_then_else(
+        condition: Expression,
+        then_block: StatementBlock,
+        else_block: StatementBlock,
+    ) -> Self {
+        Self::If(condition, then_block, Some(else_block))
+    }
+
+    pub fn match_on(expression: Expression, arms: Vec<MatchArm>) -> Self {
+        Self::Match(expression, arms)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Builder for StatementBlock {}
+
+impl From<Vec<Statement>> for StatementBlock {
+    fn from(statements: Vec<Statement>) -> Self {
+        Self { statements }
+    }
+}
+
+impl StatementBlock {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn statements(&self) -> &Vec<Statement> {
+        &self.statements
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    pub fn set_statements(&mut self, statements: Vec<Statement>) -> &mut Self {
+        self.statements = statements;
+        self
+    }
+
+    pub fn add_statement(&mut self, statement: Statement) -> &mut Self {
+        self.statements.push(statement);
+        self
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
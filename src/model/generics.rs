@@ -0,0 +1,203 @@
+/*!
+One-line description.
+
+More detailed description, with
+
+# Example
+
+*/
+
+use crate::model::identity::{HasName, Identifier};
+use crate::model::values::ValueType;
+use crate::model::Builder;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GenericParameterKind {
+    Type,
+    Lifetime,
+}
+
+#[derive(Clone, Debug)]
+pub struct GenericParameter {
+    kind: GenericParameterKind,
+    name: Identifier,
+    bounds: Vec<ValueType>,
+    default: Option<ValueType>,
+}
+
+#[derive(Clone, Debug)]
+pub struct WherePredicate {
+    subject: ValueType,
+    bounds: Vec<ValueType>,
+}
+
+pub trait HasGenericParameters {
+    fn generic_parameters(&self) -> &Vec<GenericParameter>;
+    fn has_generic_parameters(&self) -> bool {
+        !self.generic_parameters().is_empty()
+    }
+    fn set_generic_parameters(&mut self, generic_parameters: Vec<GenericParameter>) -> &mut Self
+    where
+        Self: Sized;
+    fn add_generic_parameter(&mut self, generic_parameter: GenericParameter) -> &mut Self
+    where
+        Self: Sized;
+    fn where_predicates(&self) -> &Vec<WherePredicate>;
+    fn has_where_predicates(&self) -> bool {
+        !self.where_predicates().is_empty()
+    }
+    fn set_where_predicates(&mut self, where_predicates: Vec<WherePredicate>) -> &mut Self
+    where
+        Self: Sized;
+    fn add_where_predicate(&mut self, where_predicate: WherePredicate) -> &mut Self
+    where
+        Self: Sized;
+    fn is_generic(&self) -> bool {
+        self.has_generic_parameters() || self.has_where_predicates()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl_has_name!(GenericParameter);
+
+impl Builder for GenericParameter {}
+
+impl From<Identifier> for GenericParameter {
+    fn from(name: Identifier) -> Self {
+        Self::new(name)
+    }
+}
+
+impl GenericParameter {
+    pub fn new(name: Identifier) -> Self {
+        Self {
+            kind: GenericParameterKind::Type,
+            name,
+            bounds: Default::default(),
+            default: None,
+        }
+    }
+
+    pub fn with_bounds(name: Identifier, bounds: Vec<ValueType>) -> Self {
+        Self {
+            kind: GenericParameterKind::Type,
+            name,
+            bounds,
+            default: None,
+        }
+    }
+
+    pub fn with_default(name: Identifier, default: ValueType) -> Self {
+        Self {
+            kind: GenericParameterKind::Type,
+            name,
+            bounds: Default::default(),
+            default: Some(default),
+        }
+    }
+
+    pub fn lifetime(name: Identifier) -> Self {
+        Self {
+            kind: GenericParameterKind::Lifetime,
+            name,
+            bounds: Default::default(),
+            default: None,
+        }
+    }
+
+    pub fn kind(&self) -> &GenericParameterKind {
+        &self.kind
+    }
+
+    pub fn is_lifetime(&self) -> bool {
+        self.kind == GenericParameterKind::Lifetime
+    }
+
+    pub fn bounds(&self) -> &Vec<ValueType> {
+        &self.bounds
+    }
+
+    pub fn set_bounds(&mut self, bounds: Vec<ValueType>) -> &mut Self {
+        self.bounds = bounds;
+        self
+    }
+
+    pub fn add_bound(&mut self, bound: ValueType) -> &mut Self {
+        self.bounds.push(bound);
+        self
+    }
+
+    pub fn default(&self) -> &Option<ValueType> {
+        &self.default
+    }
+
+    pub fn has_default(&self) -> bool {
+        self.default.is_some()
+    }
+
+    pub fn set_default(&mut self, default: ValueType) -> &mut Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub fn unset_default(&mut self) -> &mut Self {
+        self.default = None;
+        self
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Builder for WherePredicate {}
+
+impl WherePredicate {
+    pub fn new(subject: ValueType, bounds: Vec<ValueType>) -> Self {
+        Self { subject, bounds }
+    }
+
+    pub fn subject(&self) -> &ValueType {
+        &self.subject
+    }
+
+    pub fn set_subject(&mut self, subject: ValueType) -> &mut Self {
+        self.subject = subject;
+        self
+    }
+
+    pub fn bounds(&self) -> &Vec<ValueType> {
+        &self.bounds
+    }
+
+    pub fn set_bounds(&mut self, bounds: Vec<ValueType>) -> &mut Self {
+        self.bounds = bounds;
+        self
+    }
+
+    pub fn add_bound(&mut self, bound: ValueType) -> &mut Self {
+        self.bounds.push(bound);
+        self
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -0,0 +1,187 @@
+/*!
+One-line description.
+
+More detailed description, with
+
+# Example
+
+*/
+
+use crate::model::comments::{HasDocumentation, ProtectedRegion};
+use crate::model::functions::FunctionDecl;
+use crate::model::generics::{GenericParameter, HasGenericParameters, WherePredicate};
+use crate::model::properties::{HasProperties, Property};
+use crate::model::structured_types::{AssociatedConstant, AssociatedType};
+use crate::model::values::ValueType;
+use crate::model::Builder;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+pub struct Implementation {
+    properties: Vec<Property>,
+    documentation: Option<String>,
+    generic_parameters: Vec<GenericParameter>,
+    where_predicates: Vec<WherePredicate>,
+    target_type: ValueType,
+    trait_type: Option<ValueType>,
+    associated_types: Vec<AssociatedType>,
+    associated_constants: Vec<AssociatedConstant>,
+    methods: Vec<FunctionDecl>,
+    regions: Vec<ProtectedRegion>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl_has_properties!(Implementation);
+
+impl_has_documentation!(Implementation);
+
+impl_has_generic_parameters!(Implementation);
+
+impl Builder for Implementation {}
+
+impl Implementation {
+    pub fn new(target_type: ValueType) -> Self {
+        Self {
+            properties: Default::default(),
+            documentation: None,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
+            target_type,
+            trait_type: None,
+            associated_types: Default::default(),
+            associated_constants: Default::default(),
+            methods: Default::default(),
+            regions: Default::default(),
+        }
+    }
+
+    pub fn of_trait(trait_type: ValueType, target_type: ValueType) -> Self {
+        Self {
+            properties: Default::default(),
+            documentation: None,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
+            target_type,
+            trait_type: Some(trait_type),
+            associated_types: Default::default(),
+            associated_constants: Default::default(),
+            methods: Default::default(),
+            regions: Default::default(),
+        }
+    }
+
+    pub fn target_type(&self) -> &ValueType {
+        &self.target_type
+    }
+
+    pub fn set_target_type(&mut self, target_type: ValueType) -> &mut Self {
+        self.target_type = target_type;
+        self
+    }
+
+    pub fn trait_type(&self) -> &Option<ValueType> {
+        &self.trait_type
+    }
+
+    pub fn is_trait_implementation(&self) -> bool {
+        self.trait_type.is_some()
+    }
+
+    pub fn set_trait_type(&mut self, trait_type: ValueType) -> &mut Self {
+        self.trait_type = Some(trait_type);
+        self
+    }
+
+    pub fn unset_trait_type(&mut self) -> &mut Self {
+        self.trait_type = None;
+        self
+    }
+
+    pub fn associated_types(&self) -> &Vec<AssociatedType> {
+        &self.associated_types
+    }
+
+    pub fn set_associated_types(&mut self, associated_types: Vec<AssociatedType>) -> &mut Self {
+        self.associated_types = associated_types;
+        self
+    }
+
+    pub fn add_associated_type(&mut self, associated_type: AssociatedType) -> &mut Self {
+        self.associated_types.push(associated_type);
+        self
+    }
+
+    pub fn associated_constants(&self) -> &Vec<AssociatedConstant> {
+        &self.associated_constants
+    }
+
+    pub fn set_associated_constants(
+        &mut self,
+        associated_constants: Vec<AssociatedConstant>,
+    ) -> &mut Self {
+        self.associated_constants = associated_constants;
+        self
+    }
+
+    pub fn add_associated_constant(
+        &mut self,
+        associated_constant: AssociatedConstant,
+    ) -> &mut Self {
+        self.associated_constants.push(associated_constant);
+        self
+    }
+
+    pub fn methods(&self) -> &Vec<FunctionDecl> {
+        &self.methods
+    }
+
+    pub fn methods_mut(&mut self) -> &mut Vec<FunctionDecl> {
+        &mut self.methods
+    }
+
+    pub fn set_methods(&mut self, methods: Vec<FunctionDecl>) -> &mut Self {
+        self.methods = methods;
+        self
+    }
+
+    pub fn add_method(&mut self, method: FunctionDecl) -> &mut Self {
+        self.methods.push(method);
+        self
+    }
+
+    pub fn regions(&self) -> &Vec<ProtectedRegion> {
+        &self.regions
+    }
+
+    pub fn set_regions(&mut self, regions: Vec<ProtectedRegion>) -> &mut Self {
+        self.regions = regions;
+        self
+    }
+
+    pub fn add_region(&mut self, region: ProtectedRegion) -> &mut Self {
+        self.regions.push(region);
+        self
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -179,3 +179,60 @@ macro_rules! impl_is_optional {
         }
     };
 }
+
+macro_rules! impl_has_field_id {
+    ($name:ty) => {
+        impl HasFieldId for $name {
+            fn field_id(&self) -> &Option<u32> {
+                &self.field_id
+            }
+
+            fn set_field_id(&mut self, field_id: u32) -> &mut Self {
+                self.field_id = Some(field_id);
+                self
+            }
+
+            fn unset_field_id(&mut self) -> &mut Self {
+                self.field_id = None;
+                self
+            }
+        }
+    };
+}
+
+macro_rules! impl_has_generic_parameters {
+    ($name:ty) => {
+        impl HasGenericParameters for $name {
+            fn generic_parameters(&self) -> &Vec<GenericParameter> {
+                &self.generic_parameters
+            }
+
+            fn set_generic_parameters(
+                &mut self,
+                generic_parameters: Vec<GenericParameter>,
+            ) -> &mut Self {
+                self.generic_parameters = generic_parameters;
+                self
+            }
+
+            fn add_generic_parameter(&mut self, generic_parameter: GenericParameter) -> &mut Self {
+                self.generic_parameters.push(generic_parameter);
+                self
+            }
+
+            fn where_predicates(&self) -> &Vec<WherePredicate> {
+                &self.where_predicates
+            }
+
+            fn set_where_predicates(&mut self, where_predicates: Vec<WherePredicate>) -> &mut Self {
+                self.where_predicates = where_predicates;
+                self
+            }
+
+            fn add_where_predicate(&mut self, where_predicate: WherePredicate) -> &mut Self {
+                self.where_predicates.push(where_predicate);
+                self
+            }
+        }
+    };
+}
@@ -43,13 +43,22 @@ pub trait Builder: Clone {
 mod macros;
 
 pub mod comments;
-pub use comments::{Comment, HasDocumentation};
+pub use comments::{Comment, HasDocumentation, ProtectedRegion};
+
+pub mod generics;
+pub use generics::{GenericParameter, GenericParameterKind, HasGenericParameters, WherePredicate};
 
 pub mod identity;
 pub use identity::{HasName, Identifier};
 
 pub mod functions;
-pub use functions::{FunctionDecl, Parameter};
+pub use functions::{
+    BinaryOperator, Expression, FunctionDecl, MatchArm, Parameter, Receiver, Statement,
+    StatementBlock, UnaryOperator,
+};
+
+pub mod implementations;
+pub use implementations::Implementation;
 
 pub mod modules;
 pub use modules::{
@@ -57,14 +66,18 @@ pub use modules::{
 };
 
 pub mod properties;
-pub use properties::{HasProperties, IsOptional, Property};
+pub use properties::{HasFieldId, HasProperties, IsOptional, Property};
 
 pub mod structured_types;
 pub use structured_types::{
-    Enumeration, EnumerationVariant, Field, StructuredType, StructuredTypeKind,
+    AssociatedConstant, AssociatedType, Enumeration, EnumerationVariant, Field, StructuredType,
+    StructuredTypeKind, VariantPayload,
 };
 
 pub mod values;
 pub use values::{
     HasOptionalType, HasOptionalValue, HasType, HasValue, KnownType, NamedValue, Value, ValueType,
 };
+
+pub mod visitor;
+pub use visitor::{Fold, Visitor, VisitorMut, Walk};
@@ -7,11 +7,13 @@ More detailed description, with
 
 */
 
-use crate::model::comments::Comment;
+use crate::model::comments::{Comment, ProtectedRegion};
 use crate::model::functions::FunctionDecl;
+use crate::model::generics::{GenericParameter, HasGenericParameters, WherePredicate};
 use crate::model::identity::{HasName, Identifier, Namespace};
+use crate::model::implementations::Implementation;
 use crate::model::properties::{HasProperties, Property};
-use crate::model::structured_types::StructuredType;
+use crate::model::structured_types::{Enumeration, StructuredType};
 use crate::model::values::{HasType, NamedValue, ValueType};
 use crate::model::{Builder, HasDocumentation};
 use std::fmt::Debug;
@@ -93,8 +95,11 @@ pub trait HasVisibility {
 
 #[derive(Clone, Debug)]
 pub struct TypeAlias {
+    documentation: Option<String>,
     visibility: Option<Visibility>,
     name: Identifier,
+    generic_parameters: Vec<GenericParameter>,
+    where_predicates: Vec<WherePredicate>,
     value_type: ValueType,
 }
 
@@ -118,10 +123,13 @@ pub enum ModuleContent {
     Import(Import),
     Comment(Comment),
     Structure(StructuredType),
+    Enumeration(Enumeration),
     Constant(NamedValue),
     Variable(NamedValue),
     Function(FunctionDecl),
+    Implementation(Implementation),
     Alias(TypeAlias),
+    Region(ProtectedRegion),
     Module(Module),
 }
 
@@ -147,17 +155,26 @@ pub struct Module {
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
+impl_has_documentation!(TypeAlias);
+
 impl_has_visibility!(TypeAlias);
 
 impl_has_name!(TypeAlias);
 
+impl_has_generic_parameters!(TypeAlias);
+
 impl_has_type!(TypeAlias);
 
+impl Builder for TypeAlias {}
+
 impl TypeAlias {
     pub fn new(name: Identifier, target_type: ValueType) -> Self {
         Self {
+            documentation: None,
             visibility: None,
             name,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
             value_type: target_type,
         }
     }
@@ -168,8 +185,11 @@ impl TypeAlias {
         target_type: ValueType,
     ) -> Self {
         Self {
+            documentation: None,
             visibility: Some(visibility),
             name,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
             value_type: target_type,
         }
     }
@@ -331,6 +351,10 @@ impl Module {
         &self.content
     }
 
+    pub fn content_mut(&mut self) -> &mut Vec<ModuleContent> {
+        &mut self.content
+    }
+
     pub fn set_content(&mut self, content: Vec<ModuleContent>) -> &mut Self {
         self.content = content;
         self
@@ -356,6 +380,11 @@ impl Module {
         self
     }
 
+    pub fn add_enumeration(&mut self, content: Enumeration) -> &mut Self {
+        self.add_content_item(ModuleContent::Enumeration(content));
+        self
+    }
+
     pub fn add_constant(&mut self, content: NamedValue) -> &mut Self {
         self.add_content_item(ModuleContent::Constant(content));
         self
@@ -371,11 +400,21 @@ impl Module {
         self
     }
 
+    pub fn add_implementation(&mut self, content: Implementation) -> &mut Self {
+        self.add_content_item(ModuleContent::Implementation(content));
+        self
+    }
+
     pub fn add_alias(&mut self, content: TypeAlias) -> &mut Self {
         self.add_content_item(ModuleContent::Alias(content));
         self
     }
 
+    pub fn add_region(&mut self, content: ProtectedRegion) -> &mut Self {
+        self.add_content_item(ModuleContent::Region(content));
+        self
+    }
+
     pub fn add_sub_module(&mut self, content: Module) -> &mut Self {
         self.add_content_item(ModuleContent::Module(content));
         self
@@ -47,6 +47,21 @@ pub trait IsOptional {
         Self: Sized;
 }
 
+// ------------------------------------------------------------------------------------------------
+
+pub trait HasFieldId {
+    fn field_id(&self) -> &Option<u32>;
+    fn has_field_id(&self) -> bool {
+        self.field_id().is_some()
+    }
+    fn set_field_id(&mut self, field_id: u32) -> &mut Self
+    where
+        Self: Sized;
+    fn unset_field_id(&mut self) -> &mut Self
+    where
+        Self: Sized;
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Types
 // ------------------------------------------------------------------------------------------------
@@ -9,9 +9,10 @@ More detailed description, with
 
 use crate::model::comments::HasDocumentation;
 use crate::model::functions::FunctionDecl;
+use crate::model::generics::{GenericParameter, HasGenericParameters, WherePredicate};
 use crate::model::identity::{HasName, Identifier};
 use crate::model::modules::{HasVisibility, Visibility};
-use crate::model::properties::{HasProperties, IsOptional, Property};
+use crate::model::properties::{HasFieldId, HasProperties, IsOptional, Property};
 use crate::model::values::{HasOptionalType, HasOptionalValue, HasType, Value, ValueType};
 use crate::model::Builder;
 
@@ -24,6 +25,7 @@ pub struct Field {
     properties: Vec<Property>,
     visibility: Option<Visibility>,
     optional: bool,
+    field_id: Option<u32>,
     name: Identifier,
     documentation: Option<String>,
     value_type: ValueType,
@@ -40,25 +42,55 @@ pub enum StructuredTypeKind {
     Service,
 }
 
+#[derive(Clone, Debug)]
+pub struct AssociatedType {
+    properties: Vec<Property>,
+    name: Identifier,
+    documentation: Option<String>,
+    bounds: Vec<ValueType>,
+    value_type: Option<ValueType>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AssociatedConstant {
+    properties: Vec<Property>,
+    name: Identifier,
+    documentation: Option<String>,
+    value_type: ValueType,
+    value: Option<Value>,
+}
+
 #[derive(Clone, Debug)]
 pub struct StructuredType {
     properties: Vec<Property>,
     visibility: Option<Visibility>,
     kind: StructuredTypeKind,
     name: Identifier,
+    generic_parameters: Vec<GenericParameter>,
+    where_predicates: Vec<WherePredicate>,
     documentation: Option<String>,
     extends: Vec<ValueType>,
+    associated_types: Vec<AssociatedType>,
+    associated_constants: Vec<AssociatedConstant>,
     fields: Vec<Field>,
     methods: Vec<FunctionDecl>,
 }
 
 // ------------------------------------------------------------------------------------------------
 
+#[derive(Clone, Debug)]
+pub enum VariantPayload {
+    Tuple(Vec<ValueType>),
+    Struct(Vec<Field>),
+}
+
 #[derive(Clone, Debug)]
 pub struct EnumerationVariant {
     properties: Vec<Property>,
     name: Identifier,
     documentation: Option<String>,
+    payload: Option<VariantPayload>,
+    /// Mirrors a payload that is a tuple of exactly one type, for `HasOptionalType`.
     value_type: Option<ValueType>,
     value: Option<Value>,
 }
@@ -68,6 +100,8 @@ pub struct Enumeration {
     properties: Vec<Property>,
     visibility: Option<Visibility>,
     name: Identifier,
+    generic_parameters: Vec<GenericParameter>,
+    where_predicates: Vec<WherePredicate>,
     documentation: Option<String>,
     variants: Vec<EnumerationVariant>,
 }
@@ -90,6 +124,8 @@ impl_has_visibility!(Field);
 
 impl_is_optional!(Field);
 
+impl_has_field_id!(Field);
+
 impl_has_name!(Field);
 
 impl_has_documentation!(Field);
@@ -106,6 +142,7 @@ impl Field {
             properties: Default::default(),
             visibility: None,
             optional: false,
+            field_id: None,
             name,
             documentation: None,
             value_type,
@@ -118,6 +155,101 @@ impl Field {
             properties: Default::default(),
             visibility: None,
             optional: false,
+            field_id: None,
+            name,
+            documentation: None,
+            value_type,
+            value: Some(value),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl_has_properties!(AssociatedType);
+
+impl_has_name!(AssociatedType);
+
+impl_has_documentation!(AssociatedType);
+
+impl_has_optional_type!(AssociatedType);
+
+impl Builder for AssociatedType {}
+
+impl AssociatedType {
+    pub fn new(name: Identifier) -> Self {
+        Self {
+            properties: Default::default(),
+            name,
+            documentation: None,
+            bounds: Default::default(),
+            value_type: None,
+        }
+    }
+
+    pub fn with_bounds(name: Identifier, bounds: Vec<ValueType>) -> Self {
+        Self {
+            properties: Default::default(),
+            name,
+            documentation: None,
+            bounds,
+            value_type: None,
+        }
+    }
+
+    pub fn with_type(name: Identifier, value_type: ValueType) -> Self {
+        Self {
+            properties: Default::default(),
+            name,
+            documentation: None,
+            bounds: Default::default(),
+            value_type: Some(value_type),
+        }
+    }
+
+    pub fn bounds(&self) -> &Vec<ValueType> {
+        &self.bounds
+    }
+
+    pub fn set_bounds(&mut self, bounds: Vec<ValueType>) -> &mut Self {
+        self.bounds = bounds;
+        self
+    }
+
+    pub fn add_bound(&mut self, bound: ValueType) -> &mut Self {
+        self.bounds.push(bound);
+        self
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl_has_properties!(AssociatedConstant);
+
+impl_has_name!(AssociatedConstant);
+
+impl_has_documentation!(AssociatedConstant);
+
+impl_has_type!(AssociatedConstant);
+
+impl_has_optional_value!(AssociatedConstant);
+
+impl Builder for AssociatedConstant {}
+
+impl AssociatedConstant {
+    pub fn new(name: Identifier, value_type: ValueType) -> Self {
+        Self {
+            properties: Default::default(),
+            name,
+            documentation: None,
+            value_type,
+            value: None,
+        }
+    }
+
+    pub fn with_value(name: Identifier, value_type: ValueType, value: Value) -> Self {
+        Self {
+            properties: Default::default(),
             name,
             documentation: None,
             value_type,
@@ -134,6 +266,8 @@ impl_has_visibility!(StructuredType);
 
 impl_has_name!(StructuredType);
 
+impl_has_generic_parameters!(StructuredType);
+
 impl_has_documentation!(StructuredType);
 
 impl Builder for StructuredType {}
@@ -145,8 +279,12 @@ impl StructuredType {
             visibility: None,
             kind,
             name,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
             documentation: None,
             extends: Default::default(),
+            associated_types: Default::default(),
+            associated_constants: Default::default(),
             fields: Default::default(),
             methods: Default::default(),
         }
@@ -199,10 +337,48 @@ impl StructuredType {
         self
     }
 
+    pub fn associated_types(&self) -> &Vec<AssociatedType> {
+        &self.associated_types
+    }
+
+    pub fn set_associated_types(&mut self, associated_types: Vec<AssociatedType>) -> &mut Self {
+        self.associated_types = associated_types;
+        self
+    }
+
+    pub fn add_associated_type(&mut self, associated_type: AssociatedType) -> &mut Self {
+        self.associated_types.push(associated_type);
+        self
+    }
+
+    pub fn associated_constants(&self) -> &Vec<AssociatedConstant> {
+        &self.associated_constants
+    }
+
+    pub fn set_associated_constants(
+        &mut self,
+        associated_constants: Vec<AssociatedConstant>,
+    ) -> &mut Self {
+        self.associated_constants = associated_constants;
+        self
+    }
+
+    pub fn add_associated_constant(
+        &mut self,
+        associated_constant: AssociatedConstant,
+    ) -> &mut Self {
+        self.associated_constants.push(associated_constant);
+        self
+    }
+
     pub fn fields(&self) -> &Vec<Field> {
         &self.fields
     }
 
+    pub fn fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.fields
+    }
+
     pub fn set_fields(&mut self, fields: Vec<Field>) -> &mut Self {
         self.fields = fields;
         self
@@ -217,6 +393,10 @@ impl StructuredType {
         &self.methods
     }
 
+    pub fn methods_mut(&mut self) -> &mut Vec<FunctionDecl> {
+        &mut self.methods
+    }
+
     pub fn set_methods(&mut self, methods: Vec<FunctionDecl>) -> &mut Self {
         self.methods = methods;
         self
@@ -236,10 +416,27 @@ impl_has_name!(EnumerationVariant);
 
 impl_has_documentation!(EnumerationVariant);
 
-impl_has_optional_type!(EnumerationVariant);
-
 impl_has_optional_value!(EnumerationVariant);
 
+/// Retained from before variants had payloads; the value type of a variant is the payload when
+/// that is a tuple of exactly one type. New code should use `payload` and `set_payload`.
+impl HasOptionalType for EnumerationVariant {
+    fn value_type(&self) -> &Option<ValueType> {
+        &self.value_type
+    }
+
+    fn set_value_type(&mut self, value_type: ValueType) -> &mut Self {
+        self.set_payload(VariantPayload::Tuple(vec![value_type]))
+    }
+
+    fn unset_value_type(&mut self) -> &mut Self {
+        if self.value_type.is_some() {
+            let _ = self.unset_payload();
+        }
+        self
+    }
+}
+
 impl Builder for EnumerationVariant {}
 
 impl EnumerationVariant {
@@ -248,10 +445,68 @@ impl EnumerationVariant {
             properties: Default::default(),
             name,
             documentation: None,
+            payload: None,
+            value_type: None,
+            value: None,
+        }
+    }
+
+    pub fn with_value(name: Identifier, value: Value) -> Self {
+        Self {
+            properties: Default::default(),
+            name,
+            documentation: None,
+            payload: None,
+            value_type: None,
+            value: Some(value),
+        }
+    }
+
+    pub fn tuple(name: Identifier, value_types: Vec<ValueType>) -> Self {
+        let mut variant = Self::new(name);
+        let _ = variant.set_payload(VariantPayload::Tuple(value_types));
+        variant
+    }
+
+    pub fn structure(name: Identifier, fields: Vec<Field>) -> Self {
+        Self {
+            properties: Default::default(),
+            name,
+            documentation: None,
+            payload: Some(VariantPayload::Struct(fields)),
             value_type: None,
             value: None,
         }
     }
+
+    pub fn payload(&self) -> &Option<VariantPayload> {
+        &self.payload
+    }
+
+    pub fn payload_mut(&mut self) -> &mut Option<VariantPayload> {
+        &mut self.payload
+    }
+
+    pub fn has_payload(&self) -> bool {
+        self.payload.is_some()
+    }
+
+    pub fn set_payload(&mut self, payload: VariantPayload) -> &mut Self {
+        self.value_type = match &payload {
+            VariantPayload::Tuple(value_types) if value_types.len() == 1 => {
+                Some(value_types[0].clone())
+            }
+            _ => None,
+        };
+        self.payload = Some(payload);
+        self
+    }
+
+    pub fn unset_payload(&mut self) -> &mut Self {
+        self.value_type = None;
+        self.payload = None;
+        self
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -262,6 +517,8 @@ impl_has_visibility!(Enumeration);
 
 impl_has_name!(Enumeration);
 
+impl_has_generic_parameters!(Enumeration);
+
 impl_has_documentation!(Enumeration);
 
 impl Builder for Enumeration {}
@@ -272,6 +529,8 @@ impl Enumeration {
             properties: Default::default(),
             visibility: None,
             name,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
             documentation: None,
             variants: Default::default(),
         }
@@ -282,6 +541,8 @@ impl Enumeration {
             properties: Default::default(),
             visibility: Some(visibility),
             name,
+            generic_parameters: Default::default(),
+            where_predicates: Default::default(),
             documentation: None,
             variants: Default::default(),
         }
@@ -291,6 +552,10 @@ impl Enumeration {
         &self.variants
     }
 
+    pub fn variants_mut(&mut self) -> &mut Vec<EnumerationVariant> {
+        &mut self.variants
+    }
+
     pub fn set_variants(&mut self, variants: Vec<EnumerationVariant>) -> &mut Self {
         self.variants = variants;
         self
@@ -34,16 +34,28 @@ pub enum KnownType {
     Boolean,
     Char,
     String,
+    StringSlice,
 }
 
 #[derive(Clone, Debug)]
 pub enum ValueType {
     Known(KnownType),
     Reference(Identifier),
+    Lifetime(Identifier),
+    Borrowed(Option<Identifier>, Box<ValueType>),
+    BorrowedMut(Option<Identifier>, Box<ValueType>),
+    Pointer(Box<ValueType>),
+    PointerMut(Box<ValueType>),
+    Boxed(Box<ValueType>),
+    Slice(Box<ValueType>),
+    Unit,
+    Tuple(Vec<ValueType>),
+    FixedArray(Box<ValueType>, usize),
+    Optional(Box<ValueType>),
+    Result(Box<ValueType>, Box<ValueType>),
     Array(Box<ValueType>),
     Set(Box<ValueType>),
     Map(Box<ValueType>, Box<ValueType>),
-    Constrained(Identifier, Vec<ValueType>),
     Generic(Identifier, Vec<ValueType>),
     Function(Vec<ValueType>, Option<Box<ValueType>>),
 }
@@ -181,12 +193,80 @@ impl ValueType {
         Self::Known(KnownType::String)
     }
 
+    pub fn string_slice() -> Self {
+        Self::Known(KnownType::StringSlice)
+    }
+
+    pub fn str_ref() -> Self {
+        Self::borrowed(Self::string_slice())
+    }
+
+    pub fn lifetime(name: Identifier) -> Self {
+        Self::Lifetime(name)
+    }
+
+    pub fn borrowed(target_type: ValueType) -> Self {
+        Self::Borrowed(None, Box::new(target_type))
+    }
+
+    pub fn borrowed_for(lifetime: Identifier, target_type: ValueType) -> Self {
+        Self::Borrowed(Some(lifetime), Box::new(target_type))
+    }
+
+    pub fn borrowed_mut(target_type: ValueType) -> Self {
+        Self::BorrowedMut(None, Box::new(target_type))
+    }
+
+    pub fn borrowed_mut_for(lifetime: Identifier, target_type: ValueType) -> Self {
+        Self::BorrowedMut(Some(lifetime), Box::new(target_type))
+    }
+
+    pub fn pointer_to(target_type: ValueType) -> Self {
+        Self::Pointer(Box::new(target_type))
+    }
+
+    pub fn pointer_mut_to(target_type: ValueType) -> Self {
+        Self::PointerMut(Box::new(target_type))
+    }
+
+    pub fn boxed(target_type: ValueType) -> Self {
+        Self::Boxed(Box::new(target_type))
+    }
+
+    pub fn slice_of(member_type: ValueType) -> Self {
+        Self::Slice(Box::new(member_type))
+    }
+
+    pub fn unit() -> Self {
+        Self::Unit
+    }
+
+    pub fn tuple_of(member_types: Vec<ValueType>) -> Self {
+        Self::Tuple(member_types)
+    }
+
+    pub fn fixed_array_of(member_type: ValueType, length: usize) -> Self {
+        Self::FixedArray(Box::new(member_type), length)
+    }
+
+    pub fn optional(value_type: ValueType) -> Self {
+        Self::Optional(Box::new(value_type))
+    }
+
+    pub fn result_of(ok_type: ValueType, error_type: ValueType) -> Self {
+        Self::Result(Box::new(ok_type), Box::new(error_type))
+    }
+
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Self::Optional(_))
+    }
+
     pub fn array_of(member_type: ValueType) -> Self {
         Self::Array(Box::new(member_type))
     }
 
     pub fn set_of(member_type: ValueType) -> Self {
-        Self::Array(Box::new(member_type))
+        Self::Set(Box::new(member_type))
     }
 
     pub fn map_of(key_type: ValueType, value_type: ValueType) -> Self {
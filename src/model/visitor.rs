@@ -0,0 +1,594 @@
+/*!
+Generic traversal of the model: read-only (`Visitor`) and mutable (`VisitorMut`) visitors with
+enter and exit hooks, and `Fold` which rebuilds, and may rewrite, the tree.
+
+Modules are walked depth first, in content order; sub-modules are walked within their parent. An
+`enter_*` hook for an element that contains others may return `Walk::SkipChildren`, in which case
+the contained elements are not visited but the matching `exit_*` hook is still called.
+
+# Example
+
+```rust
+use code_writer::error::Result;
+use code_writer::model::visitor::{walk_module, Visitor};
+use code_writer::model::{Builder, Field, Identifier, Module, StructuredType, ValueType};
+
+#[derive(Default)]
+struct FieldCounter(usize);
+
+impl Visitor for FieldCounter {
+    fn enter_field(&mut self, _: &Field) -> Result<()> {
+        self.0 += 1;
+        Ok(())
+    }
+}
+
+let module = Module::new(Identifier::new("address"))
+    .add_structure(
+        StructuredType::structure(Identifier::new("Country"))
+            .add_field(Field::new(Identifier::new("code"), ValueType::string()).build())
+            .build(),
+    )
+    .build();
+
+let mut counter = FieldCounter::default();
+walk_module(&mut counter, &module).unwrap();
+assert_eq!(counter.0, 1);
+```
+*/
+
+use crate::error::Result;
+use crate::model::identity::Namespace;
+use crate::model::{
+    Comment, Enumeration, EnumerationVariant, Field, FunctionDecl, HasName, Implementation, Import,
+    Module, ModuleContent, NamedValue, Parameter, ProtectedRegion, StructuredType, TypeAlias,
+    VariantPayload,
+};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Walk {
+    #[default]
+    Continue,
+    SkipChildren,
+}
+
+#[allow(unused_variables)]
+pub trait Visitor {
+    fn enter_module(&mut self, namespace: &Namespace, module: &Module) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_module(&mut self, namespace: &Namespace, module: &Module) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_import(&mut self, import: &Import) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_comment(&mut self, comment: &Comment) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_region(&mut self, region: &ProtectedRegion) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_constant(&mut self, constant: &NamedValue) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_variable(&mut self, variable: &NamedValue) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_structured_type(&mut self, structured_type: &StructuredType) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_structured_type(&mut self, structured_type: &StructuredType) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_enumeration(&mut self, enumeration: &Enumeration) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_enumeration(&mut self, enumeration: &Enumeration) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_variant(&mut self, variant: &EnumerationVariant) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_variant(&mut self, variant: &EnumerationVariant) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_field(&mut self, field: &Field) -> Result<()> {
+        Ok(())
+    }
+
+    fn exit_field(&mut self, field: &Field) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_implementation(&mut self, implementation: &Implementation) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_implementation(&mut self, implementation: &Implementation) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_function(&mut self, function: &FunctionDecl) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_function(&mut self, function: &FunctionDecl) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_parameter(&mut self, parameter: &Parameter) -> Result<()> {
+        Ok(())
+    }
+
+    fn exit_parameter(&mut self, parameter: &Parameter) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    fn enter_module(&mut self, namespace: &Namespace, module: &mut Module) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_module(&mut self, namespace: &Namespace, module: &mut Module) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_import(&mut self, import: &mut Import) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_comment(&mut self, comment: &mut Comment) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_region(&mut self, region: &mut ProtectedRegion) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_constant(&mut self, constant: &mut NamedValue) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_variable(&mut self, variable: &mut NamedValue) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &mut TypeAlias) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_structured_type(&mut self, structured_type: &mut StructuredType) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_structured_type(&mut self, structured_type: &mut StructuredType) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_enumeration(&mut self, enumeration: &mut Enumeration) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_enumeration(&mut self, enumeration: &mut Enumeration) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_variant(&mut self, variant: &mut EnumerationVariant) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_variant(&mut self, variant: &mut EnumerationVariant) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_field(&mut self, field: &mut Field) -> Result<()> {
+        Ok(())
+    }
+
+    fn exit_field(&mut self, field: &mut Field) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_implementation(&mut self, implementation: &mut Implementation) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_implementation(&mut self, implementation: &mut Implementation) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_function(&mut self, function: &mut FunctionDecl) -> Result<Walk> {
+        Ok(Walk::Continue)
+    }
+
+    fn exit_function(&mut self, function: &mut FunctionDecl) -> Result<()> {
+        Ok(())
+    }
+
+    fn enter_parameter(&mut self, parameter: &mut Parameter) -> Result<()> {
+        Ok(())
+    }
+
+    fn exit_parameter(&mut self, parameter: &mut Parameter) -> Result<()> {
+        Ok(())
+    }
+}
+
+///
+/// Rebuilds the tree by value. The default methods fold each contained element in turn; methods
+/// that fold members of a list return `None` to remove the member.
+///
+pub trait Fold {
+    fn fold_module(&mut self, module: Module) -> Result<Module> {
+        fold_module(self, module)
+    }
+
+    fn fold_content(&mut self, content: ModuleContent) -> Result<Option<ModuleContent>> {
+        fold_content(self, content)
+    }
+
+    fn fold_structured_type(&mut self, structured_type: StructuredType) -> Result<StructuredType> {
+        fold_structured_type(self, structured_type)
+    }
+
+    fn fold_enumeration(&mut self, enumeration: Enumeration) -> Result<Enumeration> {
+        fold_enumeration(self, enumeration)
+    }
+
+    fn fold_variant(&mut self, variant: EnumerationVariant) -> Result<Option<EnumerationVariant>> {
+        fold_variant(self, variant).map(Some)
+    }
+
+    fn fold_field(&mut self, field: Field) -> Result<Option<Field>> {
+        Ok(Some(field))
+    }
+
+    fn fold_implementation(&mut self, implementation: Implementation) -> Result<Implementation> {
+        fold_implementation(self, implementation)
+    }
+
+    fn fold_function(&mut self, function: FunctionDecl) -> Result<Option<FunctionDecl>> {
+        fold_function(self, function).map(Some)
+    }
+
+    fn fold_parameter(&mut self, parameter: Parameter) -> Result<Option<Parameter>> {
+        Ok(Some(parameter))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Walk `module`, and all of its sub-modules, with `visitor`.
+pub fn walk_module<V: Visitor + ?Sized>(visitor: &mut V, module: &Module) -> Result<()> {
+    walk_module_in(
+        visitor,
+        &Namespace::new(vec![module.name().clone()]),
+        module,
+    )
+}
+
+/// Walk `module`, whose namespace path is `namespace`, with `visitor`.
+pub fn walk_module_in<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    namespace: &Namespace,
+    module: &Module,
+) -> Result<()> {
+    if visitor.enter_module(namespace, module)? == Walk::Continue {
+        for element in module.content() {
+            match element {
+                ModuleContent::Import(v) => visitor.visit_import(v)?,
+                ModuleContent::Comment(v) => visitor.visit_comment(v)?,
+                ModuleContent::Structure(v) => walk_structured_type(visitor, v)?,
+                ModuleContent::Enumeration(v) => walk_enumeration(visitor, v)?,
+                ModuleContent::Constant(v) => visitor.visit_constant(v)?,
+                ModuleContent::Variable(v) => visitor.visit_variable(v)?,
+                ModuleContent::Function(v) => walk_function(visitor, v)?,
+                ModuleContent::Implementation(v) => walk_implementation(visitor, v)?,
+                ModuleContent::Alias(v) => visitor.visit_type_alias(v)?,
+                ModuleContent::Region(v) => visitor.visit_region(v)?,
+                ModuleContent::Module(v) => {
+                    walk_module_in(visitor, &namespace.with(v.name().clone()), v)?
+                }
+            }
+        }
+    }
+    visitor.exit_module(namespace, module)
+}
+
+pub fn walk_structured_type<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    structured_type: &StructuredType,
+) -> Result<()> {
+    if visitor.enter_structured_type(structured_type)? == Walk::Continue {
+        for field in structured_type.fields() {
+            walk_field(visitor, field)?;
+        }
+        for method in structured_type.methods() {
+            walk_function(visitor, method)?;
+        }
+    }
+    visitor.exit_structured_type(structured_type)
+}
+
+pub fn walk_enumeration<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    enumeration: &Enumeration,
+) -> Result<()> {
+    if visitor.enter_enumeration(enumeration)? == Walk::Continue {
+        for variant in enumeration.variants() {
+            if visitor.enter_variant(variant)? == Walk::Continue {
+                if let Some(VariantPayload::Struct(fields)) = variant.payload() {
+                    for field in fields {
+                        walk_field(visitor, field)?;
+                    }
+                }
+            }
+            visitor.exit_variant(variant)?;
+        }
+    }
+    visitor.exit_enumeration(enumeration)
+}
+
+pub fn walk_implementation<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    implementation: &Implementation,
+) -> Result<()> {
+    if visitor.enter_implementation(implementation)? == Walk::Continue {
+        for method in implementation.methods() {
+            walk_function(visitor, method)?;
+        }
+    }
+    visitor.exit_implementation(implementation)
+}
+
+/// Walk `function`; thrown exceptions are visited as parameters, after the parameters.
+pub fn walk_function<V: Visitor + ?Sized>(visitor: &mut V, function: &FunctionDecl) -> Result<()> {
+    if visitor.enter_function(function)? == Walk::Continue {
+        for parameter in function.parameters().iter().chain(function.throws()) {
+            visitor.enter_parameter(parameter)?;
+            visitor.exit_parameter(parameter)?;
+        }
+    }
+    visitor.exit_function(function)
+}
+
+// ------------------------------------------------------------------------------------------------
+
+/// Walk `module`, and all of its sub-modules, with the mutable `visitor`.
+pub fn walk_module_mut<V: VisitorMut + ?Sized>(visitor: &mut V, module: &mut Module) -> Result<()> {
+    let namespace = Namespace::new(vec![module.name().clone()]);
+    walk_module_in_mut(visitor, &namespace, module)
+}
+
+/// Walk `module`, whose namespace path is `namespace`, with the mutable `visitor`.
+pub fn walk_module_in_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    namespace: &Namespace,
+    module: &mut Module,
+) -> Result<()> {
+    if visitor.enter_module(namespace, module)? == Walk::Continue {
+        for element in module.content_mut() {
+            match element {
+                ModuleContent::Import(v) => visitor.visit_import(v)?,
+                ModuleContent::Comment(v) => visitor.visit_comment(v)?,
+                ModuleContent::Structure(v) => walk_structured_type_mut(visitor, v)?,
+                ModuleContent::Enumeration(v) => walk_enumeration_mut(visitor, v)?,
+                ModuleContent::Constant(v) => visitor.visit_constant(v)?,
+                ModuleContent::Variable(v) => visitor.visit_variable(v)?,
+                ModuleContent::Function(v) => walk_function_mut(visitor, v)?,
+                ModuleContent::Implementation(v) => walk_implementation_mut(visitor, v)?,
+                ModuleContent::Alias(v) => visitor.visit_type_alias(v)?,
+                ModuleContent::Region(v) => visitor.visit_region(v)?,
+                ModuleContent::Module(v) => {
+                    let namespace = namespace.with(v.name().clone());
+                    walk_module_in_mut(visitor, &namespace, v)?
+                }
+            }
+        }
+    }
+    visitor.exit_module(namespace, module)
+}
+
+pub fn walk_structured_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    structured_type: &mut StructuredType,
+) -> Result<()> {
+    if visitor.enter_structured_type(structured_type)? == Walk::Continue {
+        for field in structured_type.fields_mut() {
+            walk_field_mut(visitor, field)?;
+        }
+        for method in structured_type.methods_mut() {
+            walk_function_mut(visitor, method)?;
+        }
+    }
+    visitor.exit_structured_type(structured_type)
+}
+
+pub fn walk_enumeration_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    enumeration: &mut Enumeration,
+) -> Result<()> {
+    if visitor.enter_enumeration(enumeration)? == Walk::Continue {
+        for variant in enumeration.variants_mut() {
+            if visitor.enter_variant(variant)? == Walk::Continue {
+                if let Some(VariantPayload::Struct(fields)) = variant.payload_mut() {
+                    for field in fields {
+                        walk_field_mut(visitor, field)?;
+                    }
+                }
+            }
+            visitor.exit_variant(variant)?;
+        }
+    }
+    visitor.exit_enumeration(enumeration)
+}
+
+pub fn walk_implementation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    implementation: &mut Implementation,
+) -> Result<()> {
+    if visitor.enter_implementation(implementation)? == Walk::Continue {
+        for method in implementation.methods_mut() {
+            walk_function_mut(visitor, method)?;
+        }
+    }
+    visitor.exit_implementation(implementation)
+}
+
+/// Walk `function`; thrown exceptions are visited as parameters, after the parameters.
+pub fn walk_function_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function: &mut FunctionDecl,
+) -> Result<()> {
+    if visitor.enter_function(function)? == Walk::Continue {
+        for parameter in function.parameters_mut() {
+            walk_parameter_mut(visitor, parameter)?;
+        }
+        for parameter in function.throws_mut() {
+            walk_parameter_mut(visitor, parameter)?;
+        }
+    }
+    visitor.exit_function(function)
+}
+
+// ------------------------------------------------------------------------------------------------
+
+/// Fold the content of `module`, including sub-modules, with `folder`.
+pub fn fold_module<F: Fold + ?Sized>(folder: &mut F, mut module: Module) -> Result<Module> {
+    let content = std::mem::take(module.content_mut());
+    let _ = module.set_content(fold_all(content, |v| folder.fold_content(v))?);
+    Ok(module)
+}
+
+/// Fold the element `content`; imports, comments, regions, values and aliases are unchanged.
+pub fn fold_content<F: Fold + ?Sized>(
+    folder: &mut F,
+    content: ModuleContent,
+) -> Result<Option<ModuleContent>> {
+    Ok(match content {
+        ModuleContent::Structure(v) => {
+            Some(ModuleContent::Structure(folder.fold_structured_type(v)?))
+        }
+        ModuleContent::Enumeration(v) => {
+            Some(ModuleContent::Enumeration(folder.fold_enumeration(v)?))
+        }
+        ModuleContent::Function(v) => folder.fold_function(v)?.map(ModuleContent::Function),
+        ModuleContent::Implementation(v) => Some(ModuleContent::Implementation(
+            folder.fold_implementation(v)?,
+        )),
+        ModuleContent::Module(v) => Some(ModuleContent::Module(folder.fold_module(v)?)),
+        _ => Some(content),
+    })
+}
+
+pub fn fold_structured_type<F: Fold + ?Sized>(
+    folder: &mut F,
+    mut structured_type: StructuredType,
+) -> Result<StructuredType> {
+    let fields = std::mem::take(structured_type.fields_mut());
+    let _ = structured_type.set_fields(fold_all(fields, |v| folder.fold_field(v))?);
+    let methods = std::mem::take(structured_type.methods_mut());
+    let _ = structured_type.set_methods(fold_all(methods, |v| folder.fold_function(v))?);
+    Ok(structured_type)
+}
+
+pub fn fold_enumeration<F: Fold + ?Sized>(
+    folder: &mut F,
+    mut enumeration: Enumeration,
+) -> Result<Enumeration> {
+    let variants = std::mem::take(enumeration.variants_mut());
+    let _ = enumeration.set_variants(fold_all(variants, |v| folder.fold_variant(v))?);
+    Ok(enumeration)
+}
+
+pub fn fold_variant<F: Fold + ?Sized>(
+    folder: &mut F,
+    mut variant: EnumerationVariant,
+) -> Result<EnumerationVariant> {
+    if let Some(VariantPayload::Struct(fields)) = variant.payload_mut() {
+        let folded = fold_all(std::mem::take(fields), |v| folder.fold_field(v))?;
+        *fields = folded;
+    }
+    Ok(variant)
+}
+
+pub fn fold_implementation<F: Fold + ?Sized>(
+    folder: &mut F,
+    mut implementation: Implementation,
+) -> Result<Implementation> {
+    let methods = std::mem::take(implementation.methods_mut());
+    let _ = implementation.set_methods(fold_all(methods, |v| folder.fold_function(v))?);
+    Ok(implementation)
+}
+
+pub fn fold_function<F: Fold + ?Sized>(
+    folder: &mut F,
+    mut function: FunctionDecl,
+) -> Result<FunctionDecl> {
+    let parameters = std::mem::take(function.parameters_mut());
+    let _ = function.set_parameters(fold_all(parameters, |v| folder.fold_parameter(v))?);
+    let throws = std::mem::take(function.throws_mut());
+    let _ = function.set_throws(fold_all(throws, |v| folder.fold_parameter(v))?);
+    Ok(function)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn walk_field<V: Visitor + ?Sized>(visitor: &mut V, field: &Field) -> Result<()> {
+    visitor.enter_field(field)?;
+    visitor.exit_field(field)
+}
+
+fn walk_field_mut<V: VisitorMut + ?Sized>(visitor: &mut V, field: &mut Field) -> Result<()> {
+    visitor.enter_field(field)?;
+    visitor.exit_field(field)
+}
+
+fn walk_parameter_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    parameter: &mut Parameter,
+) -> Result<()> {
+    visitor.enter_parameter(parameter)?;
+    visitor.exit_parameter(parameter)
+}
+
+fn fold_all<T, F>(items: Vec<T>, mut f: F) -> Result<Vec<T>>
+where
+    F: FnMut(T) -> Result<Option<T>>,
+{
+    let mut folded = Vec::with_capacity(items.len());
+    for item in items {
+        if let Some(item) = f(item)? {
+            folded.push(item);
+        }
+    }
+    Ok(folded)
+}
@@ -0,0 +1,1001 @@
+/*!
+Writes a tree of modules as a set of files, one per module, laid out according to the conventions
+of the target language.
+
+# Example
+
+```rust
+use code_writer::language::rust::{RustLayout, RustWriter};
+use code_writer::model::modules::Module;
+use code_writer::model::{Builder, Identifier};
+use code_writer::project::{MemoryFileSet, ProjectWriter};
+use std::path::Path;
+
+let module = Module::new(Identifier::new("address"))
+    .add_sub_module(Module::new(Identifier::new("countries")))
+    .build();
+
+let mut files = MemoryFileSet::default();
+ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default())
+    .write(&module, &mut files)
+    .unwrap();
+
+assert!(files.get(Path::new("address.rs")).is_some());
+assert!(files.get(Path::new("address/countries.rs")).is_some());
+```
+*/
+
+use crate::error::{ErrorKind, Result};
+use crate::model::identity::Namespace;
+use crate::model::{HasName, Identifier, Module, ModuleContent};
+use crate::source_map::SourceMap;
+use crate::writer::{
+    write_module_content, CodeWriter, FormattingPolicy, ModuleWriter, REGION_BEGIN_MARKER,
+    REGION_END_MARKER,
+};
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, read_dir, read_to_string, remove_file, write};
+use std::path::{Component, Path, PathBuf};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+pub trait ProjectLayout {
+    /// The path, relative to the project root, of the file for the module at `namespace`.
+    fn module_file_path(&self, namespace: &Namespace) -> PathBuf;
+
+    /// A single-line comment, in the target language, holding `fingerprint`.
+    fn fingerprint_comment(&self, fingerprint: &str) -> Option<String> {
+        Some(format!("// {} {}", FINGERPRINT_MARKER, fingerprint))
+    }
+
+    /// `true` if `path` could be a module file in this layout; by default, if it has the same
+    /// extension, or without one the same file name, as `module_file_path` produces.
+    fn is_module_file_path(&self, path: &Path) -> bool {
+        let example = self.module_file_path(&Namespace::new(vec![Identifier::new("module")]));
+        match example.extension() {
+            Some(extension) => path.extension() == Some(extension),
+            None => path.file_name() == example.file_name(),
+        }
+    }
+}
+
+pub trait FileSet {
+    /// The relative paths of all files in the set; only used to find stale generated files, so
+    /// a set that cannot list its files may return none.
+    fn file_paths(&self) -> Result<Vec<PathBuf>> {
+        Ok(Default::default())
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Option<String>>;
+
+    fn write_file(&mut self, path: &Path, content: &str) -> Result<()>;
+
+    /// Only called for paths returned by `file_paths`.
+    fn remove_file(&mut self, path: &Path) -> Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("cannot remove '{}'", path.display()),
+        )
+        .into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileStatus {
+    Created,
+    Modified,
+    Unchanged,
+    Deleted,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrphanedRegion {
+    path: PathBuf,
+    name: String,
+    content: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WriteReport {
+    files: BTreeMap<PathBuf, FileStatus>,
+    diffs: BTreeMap<PathBuf, String>,
+    source_maps: BTreeMap<PathBuf, SourceMap>,
+    orphaned_regions: Vec<OrphanedRegion>,
+    blocked_files: Vec<PathBuf>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DirectoryFileSet {
+    root: PathBuf,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryFileSet {
+    files: BTreeMap<PathBuf, String>,
+}
+
+pub struct ProjectWriter<L, M>
+where
+    L: ProjectLayout,
+    M: ModuleWriter<Vec<u8>>,
+{
+    layout: L,
+    module_writer: M,
+    policy: FormattingPolicy,
+    write_if_changed: bool,
+    fingerprints: bool,
+    force: bool,
+    remove_stale: bool,
+    source_maps: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Edit {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+pub const FINGERPRINT_MARKER: &str = "@generated fingerprint:";
+
+const DIFF_CONTEXT: usize = 3;
+
+/// How far the difference search goes before writing the rest of a range as a replacement.
+const DIFF_MAX_COST: isize = 4096;
+
+/// A stable (FNV-1a, 64-bit) hash of `content` as 16 hex digits.
+pub fn fingerprint(content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// `true` if the first line of `content` holds a fingerprint that matches the rest of the content,
+/// ignoring the content of any protected regions.
+pub fn has_valid_fingerprint(content: &str) -> bool {
+    match content.split_once('\n') {
+        Some((header, body)) => match header.split_once(FINGERPRINT_MARKER) {
+            Some((_, recorded)) => recorded
+                .split_whitespace()
+                .next()
+                .map(|recorded| recorded == fingerprint(&without_region_content(body)))
+                .unwrap_or_default(),
+            None => false,
+        },
+        None => false,
+    }
+}
+
+/// The path of the project file `to` relative to the project directory `from_dir`, using `..` to
+/// leave `from_dir`; both paths are relative to the project root.
+pub fn relative_path(from_dir: &Path, to: &Path) -> PathBuf {
+    let from: Vec<_> = from_dir.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(from, to)| from == to)
+        .count();
+    std::iter::repeat_n(Component::ParentDir, from.len() - common)
+        .chain(to[common..].iter().cloned())
+        .collect()
+}
+
+/// A unified diff, with three lines of context, from `old` to `new`; empty if they are the same.
+pub fn unified_diff(old_label: &str, new_label: &str, old: &str, new: &str) -> String {
+    // Lines keep their terminator, so that adding or removing the final newline is a change.
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let edits = diff_lines(&old_lines, &new_lines);
+    if edits.iter().all(|edit| matches!(edit, Edit::Equal(_, _))) {
+        return String::new();
+    }
+
+    // The old and new line offsets *before* each edit.
+    let mut offsets: Vec<(usize, usize)> = Vec::with_capacity(edits.len() + 1);
+    let (mut old_offset, mut new_offset) = (0, 0);
+    for edit in &edits {
+        offsets.push((old_offset, new_offset));
+        match edit {
+            Edit::Equal(_, _) => {
+                old_offset += 1;
+                new_offset += 1;
+            }
+            Edit::Delete(_) => old_offset += 1,
+            Edit::Insert(_) => new_offset += 1,
+        }
+    }
+    offsets.push((old_offset, new_offset));
+
+    let mut diff = format!("--- {}\n+++ {}\n", old_label, new_label);
+    let mut i = 0;
+    while i < edits.len() {
+        if let Edit::Equal(_, _) = edits[i] {
+            i += 1;
+            continue;
+        }
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let mut last_change = i;
+        let mut j = i;
+        while j < edits.len() && j - last_change <= 2 * DIFF_CONTEXT {
+            if !matches!(edits[j], Edit::Equal(_, _)) {
+                last_change = j;
+            }
+            j += 1;
+        }
+        let end = (last_change + DIFF_CONTEXT + 1).min(edits.len());
+        let (old_start, new_start) = offsets[start];
+        let (old_end, new_end) = offsets[end];
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_end - new_start),
+        ));
+        for edit in &edits[start..end] {
+            match edit {
+                Edit::Equal(x, _) => push_diff_line(&mut diff, ' ', old_lines[*x]),
+                Edit::Delete(x) => push_diff_line(&mut diff, '-', old_lines[*x]),
+                Edit::Insert(y) => push_diff_line(&mut diff, '+', new_lines[*y]),
+            }
+        }
+        i = end;
+    }
+    diff
+}
+
+/// The content of each protected region in `content`, by region name; a name used for more than
+/// one region is an error, as its content could not be carried over unambiguously.
+pub fn protected_regions(content: &str) -> Result<BTreeMap<String, String>> {
+    let mut regions: BTreeMap<String, String> = Default::default();
+    let mut duplicate: Option<String> = None;
+    let _ = map_region_content(content, |name, region_content| {
+        if regions
+            .insert(name.to_string(), region_content.to_string())
+            .is_some()
+            && duplicate.is_none()
+        {
+            duplicate = Some(name.to_string());
+        }
+        region_content.to_string()
+    });
+    match duplicate {
+        None => Ok(regions),
+        Some(name) => Err(ErrorKind::DuplicateRegionName(name).into()),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl DirectoryFileSet {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: PathBuf::from(root),
+        }
+    }
+
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+}
+
+impl FileSet for DirectoryFileSet {
+    fn file_paths(&self) -> Result<Vec<PathBuf>> {
+        let mut paths: Vec<PathBuf> = Default::default();
+        if self.root.is_dir() {
+            collect_file_paths(&self.root, &self.root, &mut paths)?;
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Option<String>> {
+        let path = self.root.join(path);
+        if path.is_file() {
+            Ok(Some(read_to_string(path)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn write_file(&mut self, path: &Path, content: &str) -> Result<()> {
+        let path = self.root.join(path);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        write(path, content)?;
+        Ok(())
+    }
+
+    fn remove_file(&mut self, path: &Path) -> Result<()> {
+        remove_file(self.root.join(path))?;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl OrphanedRegion {
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn content(&self) -> &String {
+        &self.content
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl WriteReport {
+    pub fn files(&self) -> &BTreeMap<PathBuf, FileStatus> {
+        &self.files
+    }
+
+    pub fn status(&self, path: &Path) -> Option<FileStatus> {
+        self.files.get(path).copied()
+    }
+
+    /// Unified diffs for each created, modified, or deleted file; only produced by a dry run.
+    pub fn diffs(&self) -> &BTreeMap<PathBuf, String> {
+        &self.diffs
+    }
+
+    pub fn diff(&self, path: &Path) -> Option<&String> {
+        self.diffs.get(path)
+    }
+
+    /// Source maps for each generated file; only produced when source maps are enabled.
+    pub fn source_maps(&self) -> &BTreeMap<PathBuf, SourceMap> {
+        &self.source_maps
+    }
+
+    pub fn source_map(&self, path: &Path) -> Option<&SourceMap> {
+        self.source_maps.get(path)
+    }
+
+    /// Regions, with content, in existing files that no longer appear in the generated files.
+    pub fn orphaned_regions(&self) -> &Vec<OrphanedRegion> {
+        &self.orphaned_regions
+    }
+
+    pub fn has_orphaned_regions(&self) -> bool {
+        !self.orphaned_regions.is_empty()
+    }
+
+    /// Existing files that `write` would refuse to replace, as their fingerprint does not match;
+    /// only produced by a dry run, as `write` fails instead.
+    pub fn blocked_files(&self) -> &Vec<PathBuf> {
+        &self.blocked_files
+    }
+
+    pub fn is_blocked(&self, path: &Path) -> bool {
+        self.blocked_files.iter().any(|blocked| blocked == path)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl MemoryFileSet {
+    pub fn files(&self) -> &BTreeMap<PathBuf, String> {
+        &self.files
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&String> {
+        self.files.get(path)
+    }
+}
+
+impl FileSet for MemoryFileSet {
+    fn file_paths(&self) -> Result<Vec<PathBuf>> {
+        Ok(self.files.keys().cloned().collect())
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Option<String>> {
+        Ok(self.files.get(path).cloned())
+    }
+
+    fn write_file(&mut self, path: &Path, content: &str) -> Result<()> {
+        let _ = self.files.insert(PathBuf::from(path), content.to_string());
+        Ok(())
+    }
+
+    fn remove_file(&mut self, path: &Path) -> Result<()> {
+        let _ = self.files.remove(path);
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<L, M> ProjectWriter<L, M>
+where
+    L: ProjectLayout,
+    M: ModuleWriter<Vec<u8>>,
+{
+    pub fn new(layout: L, module_writer: M) -> Self {
+        Self::with_policy(layout, module_writer, Default::default())
+    }
+
+    pub fn with_policy(layout: L, module_writer: M, policy: FormattingPolicy) -> Self {
+        Self {
+            layout,
+            module_writer,
+            policy,
+            write_if_changed: false,
+            fingerprints: false,
+            force: false,
+            remove_stale: false,
+            source_maps: false,
+        }
+    }
+
+    pub fn layout(&self) -> &L {
+        &self.layout
+    }
+
+    pub fn module_writer(&self) -> &M {
+        &self.module_writer
+    }
+
+    pub fn policy(&self) -> &FormattingPolicy {
+        &self.policy
+    }
+
+    pub fn write_if_changed(&self) -> bool {
+        self.write_if_changed
+    }
+
+    /// Only write files whose rendered content differs from the existing content.
+    pub fn set_write_if_changed(&mut self, write_if_changed: bool) -> &mut Self {
+        self.write_if_changed = write_if_changed;
+        self
+    }
+
+    pub fn fingerprints(&self) -> bool {
+        self.fingerprints
+    }
+
+    /// Add a content fingerprint to the header of each file, and refuse to overwrite existing
+    /// files whose fingerprint does not match their content.
+    pub fn set_fingerprints(&mut self, fingerprints: bool) -> &mut Self {
+        self.fingerprints = fingerprints;
+        self
+    }
+
+    pub fn force(&self) -> bool {
+        self.force
+    }
+
+    /// Overwrite existing files even if their fingerprint does not match.
+    pub fn set_force(&mut self, force: bool) -> &mut Self {
+        self.force = force;
+        self
+    }
+
+    pub fn remove_stale(&self) -> bool {
+        self.remove_stale
+    }
+
+    /// With fingerprints enabled, remove generated files that are no longer part of the module
+    /// tree; without this they are left in place and not reported.
+    pub fn set_remove_stale(&mut self, remove_stale: bool) -> &mut Self {
+        self.remove_stale = remove_stale;
+        self
+    }
+
+    pub fn source_maps(&self) -> bool {
+        self.source_maps
+    }
+
+    /// Record a source map for each generated file, mapping model elements to output spans.
+    pub fn set_source_maps(&mut self, source_maps: bool) -> &mut Self {
+        self.source_maps = source_maps;
+        self
+    }
+
+    /// Render `module`, and all of its sub-modules, to a map from relative file path to content.
+    pub fn render(&self, module: &Module) -> Result<BTreeMap<PathBuf, String>> {
+        let (files, _) = self.render_all(module)?;
+        Ok(files)
+    }
+
+    /// Render `module`, and all of its sub-modules, with a source map for each file.
+    #[allow(clippy::type_complexity)]
+    pub fn render_with_source_maps(
+        &self,
+        module: &Module,
+    ) -> Result<BTreeMap<PathBuf, (String, SourceMap)>> {
+        let (files, mut source_maps) = self.render_all(module)?;
+        Ok(files
+            .into_iter()
+            .map(|(path, content)| {
+                let source_map = source_maps.remove(&path).unwrap_or_default();
+                (path, (content, source_map))
+            })
+            .collect())
+    }
+
+    /// Write `module` to `file_set`, carrying over the content of protected regions in existing
+    /// files; no file is written if any existing file may not be replaced. Stale generated files
+    /// are removed only if `set_remove_stale` is enabled.
+    pub fn write(&self, module: &Module, file_set: &mut dyn FileSet) -> Result<WriteReport> {
+        let (report, contents) = self.plan(module, &*file_set, false)?;
+        for (path, content) in &contents {
+            match (report.files[path], content) {
+                (FileStatus::Deleted, _) => file_set.remove_file(path)?,
+                (FileStatus::Unchanged, _) if self.write_if_changed => {}
+                (_, Some(content)) => file_set.write_file(path, content)?,
+                (_, None) => {}
+            }
+        }
+        Ok(report)
+    }
+
+    /// Report what `write` would do, with a unified diff for each changed file, without writing;
+    /// files that `write` would refuse to replace are reported as blocked rather than failing.
+    pub fn dry_run(&self, module: &Module, file_set: &dyn FileSet) -> Result<WriteReport> {
+        let (report, _) = self.plan(module, file_set, true)?;
+        Ok(report)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    #[allow(clippy::type_complexity)]
+    fn plan(
+        &self,
+        module: &Module,
+        file_set: &dyn FileSet,
+        dry_run: bool,
+    ) -> Result<(WriteReport, BTreeMap<PathBuf, Option<String>>)> {
+        let mut report = WriteReport::default();
+        let mut contents: BTreeMap<PathBuf, Option<String>> = Default::default();
+        let (rendered, mut source_maps) = self.render_all(module)?;
+
+        let mut previous: BTreeMap<PathBuf, String> = Default::default();
+        for (path, content) in &rendered {
+            if let Some(existing) = file_set.read_file(path)? {
+                let _ = previous.insert(path.clone(), existing);
+            }
+            let _ = contents.insert(path.clone(), Some(content.clone()));
+        }
+        if self.fingerprints && self.remove_stale {
+            for path in file_set.file_paths()? {
+                if !rendered.contains_key(&path) && self.layout.is_module_file_path(&path) {
+                    // A file that cannot be read as text was not generated.
+                    if let Ok(Some(existing)) = file_set.read_file(&path) {
+                        if existing
+                            .lines()
+                            .next()
+                            .unwrap_or_default()
+                            .contains(FINGERPRINT_MARKER)
+                        {
+                            let _ = previous.insert(path.clone(), existing);
+                            let _ = contents.insert(path, None);
+                        }
+                    }
+                }
+            }
+        }
+
+        for (path, content) in contents.iter_mut() {
+            let status = match (previous.get(path), content.take()) {
+                (None, new) => {
+                    *content = new;
+                    FileStatus::Created
+                }
+                (Some(existing), new) => {
+                    let new = match new {
+                        None => None,
+                        Some(new) => {
+                            Some(self.carry_over_regions(path, new, existing, &mut report)?)
+                        }
+                    };
+                    if let (Some(new), Some(source_map)) = (&new, source_maps.get_mut(path)) {
+                        shift_for_regions(source_map, &rendered[path], new);
+                    }
+                    let status = match &new {
+                        None => FileStatus::Deleted,
+                        Some(new) if new == existing => FileStatus::Unchanged,
+                        Some(_) => FileStatus::Modified,
+                    };
+                    if status != FileStatus::Unchanged
+                        && self.fingerprints
+                        && !self.force
+                        && !has_valid_fingerprint(existing)
+                    {
+                        if !dry_run {
+                            return Err(ErrorKind::ModifiedGeneratedFile(
+                                path.display().to_string(),
+                            )
+                            .into());
+                        }
+                        report.blocked_files.push(path.clone());
+                    }
+                    *content = new;
+                    status
+                }
+            };
+            if dry_run && status != FileStatus::Unchanged {
+                let label = path.display().to_string();
+                let (old_label, new_label) = match status {
+                    FileStatus::Created => ("/dev/null".to_string(), format!("b/{}", label)),
+                    FileStatus::Deleted => (format!("a/{}", label), "/dev/null".to_string()),
+                    _ => (format!("a/{}", label), format!("b/{}", label)),
+                };
+                let diff = unified_diff(
+                    &old_label,
+                    &new_label,
+                    previous.get(path).map(String::as_str).unwrap_or_default(),
+                    content.as_deref().unwrap_or_default(),
+                );
+                let _ = report.diffs.insert(path.clone(), diff);
+            }
+            let _ = report.files.insert(path.clone(), status);
+        }
+        report.source_maps = source_maps;
+        Ok((report, contents))
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn carry_over_regions(
+        &self,
+        path: &Path,
+        content: String,
+        existing: &str,
+        report: &mut WriteReport,
+    ) -> Result<String> {
+        let existing_regions = protected_regions(existing)?;
+        if existing_regions.is_empty() {
+            return Ok(content);
+        }
+        let generated_regions = protected_regions(&content)?;
+        for (name, region_content) in &existing_regions {
+            if !generated_regions.contains_key(name) && !region_content.trim().is_empty() {
+                report.orphaned_regions.push(OrphanedRegion {
+                    path: PathBuf::from(path),
+                    name: name.clone(),
+                    content: region_content.clone(),
+                });
+            }
+        }
+        Ok(map_region_content(&content, |name, region_content| {
+            existing_regions
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| region_content.to_string())
+        }))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn render_all(
+        &self,
+        module: &Module,
+    ) -> Result<(BTreeMap<PathBuf, String>, BTreeMap<PathBuf, SourceMap>)> {
+        let mut files: BTreeMap<PathBuf, String> = Default::default();
+        let mut source_maps: BTreeMap<PathBuf, SourceMap> = Default::default();
+        self.render_module(
+            &Namespace::new(vec![module.name().clone()]),
+            module,
+            &mut files,
+            &mut source_maps,
+        )?;
+        Ok((files, source_maps))
+    }
+
+    fn render_module(
+        &self,
+        namespace: &Namespace,
+        module: &Module,
+        files: &mut BTreeMap<PathBuf, String>,
+        source_maps: &mut BTreeMap<PathBuf, SourceMap>,
+    ) -> Result<()> {
+        let path = self.layout.module_file_path(namespace);
+        let mut writer = CodeWriter::with_policy(Vec::new(), self.policy.clone());
+        writer.set_namespace(namespace.clone());
+        if self.source_maps {
+            let mut parent = namespace.path().clone();
+            let _ = parent.pop();
+            writer.enable_source_map(if parent.is_empty() {
+                None
+            } else {
+                Some(Namespace::new(parent))
+            });
+        }
+        write_module_content(&mut writer, &self.module_writer, module)?;
+        writer.flush()?;
+        let mut source_map = writer.take_source_map();
+        let mut content = String::from_utf8(writer.into_inner())?;
+        let _ = protected_regions(&content)?;
+        if self.fingerprints {
+            let fingerprint = fingerprint(&without_region_content(&content));
+            if let Some(header) = self.layout.fingerprint_comment(&fingerprint) {
+                if let Some(source_map) = &mut source_map {
+                    let header_lines = header.lines().count();
+                    source_map.map_lines(|line| line + header_lines);
+                }
+                content = format!("{}{}{}", header, self.policy.new_line(), content);
+            }
+        }
+        if files.insert(path.clone(), content).is_some() {
+            return Err(ErrorKind::DuplicateModulePath(path.display().to_string()).into());
+        }
+        if let Some(source_map) = source_map {
+            let _ = source_maps.insert(path, source_map);
+        }
+        for element in module.content() {
+            if let ModuleContent::Module(sub_module) = element {
+                self.render_module(
+                    &namespace.with(sub_module.name().clone()),
+                    sub_module,
+                    files,
+                    source_maps,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Moves the lines of `source_map`, recorded against `generated`, to account for the region
+/// content carried over into `carried`.
+///
+fn shift_for_regions(source_map: &mut SourceMap, generated: &str, carried: &str) {
+    let region_ends: Vec<(usize, usize)> = region_end_lines(generated)
+        .zip(region_end_lines(carried))
+        .collect();
+    if region_ends
+        .iter()
+        .any(|(generated, carried)| generated != carried)
+    {
+        source_map.map_lines(|line| {
+            match region_ends
+                .iter()
+                .rev()
+                .find(|(generated, _)| *generated <= line)
+            {
+                None => line,
+                Some((generated, carried)) => (line + carried).saturating_sub(*generated),
+            }
+        });
+    }
+}
+
+fn region_end_lines(content: &str) -> impl Iterator<Item = usize> + '_ {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| region_marker(line, REGION_END_MARKER).is_some())
+        .map(|(i, _)| i)
+}
+
+fn without_region_content(content: &str) -> String {
+    map_region_content(content, |_, _| String::new())
+}
+
+///
+/// Returns `content` with the content of each protected region replaced by the result of calling
+/// `f` with the region's name and current content. An unterminated region is left unchanged.
+///
+fn map_region_content<F>(content: &str, mut f: F) -> String
+where
+    F: FnMut(&str, &str) -> String,
+{
+    let mut result = String::with_capacity(content.len());
+    let mut current: Option<(String, String)> = None;
+    for line in content.split_inclusive('\n') {
+        match current.take() {
+            None => {
+                if let Some(name) = region_marker(line, REGION_BEGIN_MARKER) {
+                    current = Some((name.to_string(), String::new()));
+                }
+                result.push_str(line);
+            }
+            Some((name, mut region_content)) => {
+                if region_marker(line, REGION_END_MARKER) == Some(name.as_str()) {
+                    result.push_str(&f(&name, &region_content));
+                    result.push_str(line);
+                } else {
+                    region_content.push_str(line);
+                    current = Some((name, region_content));
+                }
+            }
+        }
+    }
+    if let Some((_, region_content)) = current {
+        result.push_str(&region_content);
+    }
+    result
+}
+
+fn region_marker<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    line.split_once(marker)
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+}
+
+///
+/// Collects the files below `directory`, without following symbolic links or descending into
+/// hidden directories, such as `.git`, that no layout would produce.
+///
+fn collect_file_paths(root: &Path, directory: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in read_dir(directory)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            if !entry.file_name().to_string_lossy().starts_with('.') {
+                collect_file_paths(root, &path, paths)?;
+            }
+        } else if file_type.is_file() {
+            if let Ok(relative) = path.strip_prefix(root) {
+                paths.push(PathBuf::from(relative));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn push_diff_line(diff: &mut String, marker: char, line: &str) {
+    diff.push(marker);
+    match line.strip_suffix('\n') {
+        Some(line) => {
+            diff.push_str(line.strip_suffix('\r').unwrap_or(line));
+            diff.push('\n');
+        }
+        None => {
+            diff.push_str(line);
+            diff.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+}
+
+fn hunk_range(start: usize, length: usize) -> String {
+    match length {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, length),
+    }
+}
+
+///
+/// The shortest edit script from `old` to `new`, using the linear space refinement of Myers' O(ND)
+/// difference algorithm so that large, heavily rewritten files need no more than O(N + M) memory.
+///
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let mut edits: Vec<Edit> = Default::default();
+    diff_ranges(old, new, (0, old.len()), (0, new.len()), &mut edits);
+    edits
+}
+
+fn diff_ranges(
+    old: &[&str],
+    new: &[&str],
+    (mut old_start, mut old_end): (usize, usize),
+    (mut new_start, mut new_end): (usize, usize),
+    edits: &mut Vec<Edit>,
+) {
+    while old_start < old_end && new_start < new_end && old[old_start] == new[new_start] {
+        edits.push(Edit::Equal(old_start, new_start));
+        old_start += 1;
+        new_start += 1;
+    }
+    let mut suffix = 0;
+    while old_start < old_end && new_start < new_end && old[old_end - 1] == new[new_end - 1] {
+        old_end -= 1;
+        new_end -= 1;
+        suffix += 1;
+    }
+
+    if old_start == old_end {
+        edits.extend((new_start..new_end).map(Edit::Insert));
+    } else if new_start == new_end {
+        edits.extend((old_start..old_end).map(Edit::Delete));
+    } else {
+        // With the common prefix and suffix removed at least two edits remain, so both sides of
+        // the split are smaller than the whole.
+        let (x, y) = match middle_snake(&old[old_start..old_end], &new[new_start..new_end]) {
+            Some(split) => split,
+            None => {
+                edits.extend((old_start..old_end).map(Edit::Delete));
+                edits.extend((new_start..new_end).map(Edit::Insert));
+                edits.extend((0..suffix).map(|i| Edit::Equal(old_end + i, new_end + i)));
+                return;
+            }
+        };
+        diff_ranges(
+            old,
+            new,
+            (old_start, old_start + x),
+            (new_start, new_start + y),
+            edits,
+        );
+        diff_ranges(
+            old,
+            new,
+            (old_start + x, old_end),
+            (new_start + y, new_end),
+            edits,
+        );
+    }
+
+    edits.extend((0..suffix).map(|i| Edit::Equal(old_end + i, new_end + i)));
+}
+
+///
+/// A point on a shortest edit path from `old` to `new`, found by searching forward from the start
+/// and backward from the end at the same time until the two searches overlap; `None` if that costs
+/// more than `DIFF_MAX_COST` edits.
+///
+fn middle_snake(old: &[&str], new: &[&str]) -> Option<(usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let delta = n - m;
+    let max = (n + m + 1) / 2;
+    let offset = max + 1;
+    // The furthest x reached on each diagonal, forward and, counting from the end, backward.
+    let mut forward = vec![0_isize; (2 * max + 3) as usize];
+    let mut backward = vec![0_isize; (2 * max + 3) as usize];
+    let furthest = |v: &[isize], d: isize, k: isize| {
+        if k == -d || (k != d && v[(offset + k - 1) as usize] < v[(offset + k + 1) as usize]) {
+            v[(offset + k + 1) as usize]
+        } else {
+            v[(offset + k - 1) as usize] + 1
+        }
+    };
+    for d in 0..=max.min(DIFF_MAX_COST) {
+        for k in (-d..=d).step_by(2) {
+            let mut x = furthest(&forward, d, k);
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[(offset + k) as usize] = x;
+            let reverse_k = delta - k;
+            if delta % 2 != 0
+                && reverse_k > -d
+                && reverse_k < d
+                && x + backward[(offset + reverse_k) as usize] >= n
+            {
+                return Some((x as usize, y as usize));
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = furthest(&backward, d, k);
+            let mut y = x - k;
+            while x < n && y < m && old[(n - x - 1) as usize] == new[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[(offset + k) as usize] = x;
+            let forward_k = delta - k;
+            if delta % 2 == 0
+                && forward_k >= -d
+                && forward_k <= d
+                && x + forward[(offset + forward_k) as usize] >= n
+            {
+                return Some(((n - x) as usize, (m - y) as usize));
+            }
+        }
+    }
+    None
+}
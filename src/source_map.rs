@@ -0,0 +1,170 @@
+/*!
+Maps model elements to the spans of generated output they produced, so that positions in generated
+code can be traced back to the schema element that defined them.
+
+Elements are keyed by their full namespace path, the module path followed by the element name;
+members such as fields, variants and methods are keyed under the path of their enclosing type.
+Positions are zero-based.
+
+# Example
+
+```rust
+use code_writer::language::rust::RustWriter;
+use code_writer::model::identity::Namespace;
+use code_writer::model::modules::Module;
+use code_writer::model::{Builder, Field, Identifier, StructuredType, ValueType};
+use code_writer::writer::{write_module_content, CodeWriter};
+
+let module = Module::new(Identifier::new("address"))
+    .add_structure(
+        StructuredType::structure(Identifier::new("Country"))
+            .add_field(Field::new(Identifier::new("code"), ValueType::string()).build())
+            .build(),
+    )
+    .build();
+
+let mut writer = CodeWriter::new(Vec::new());
+writer.enable_source_map(None);
+write_module_content(&mut writer, &RustWriter::default(), &module).unwrap();
+let source_map = writer.take_source_map().unwrap();
+
+let country = Namespace::new(vec![Identifier::new("address")]);
+let span = source_map.get(&country, &Identifier::new("Country")).unwrap();
+assert_eq!((span.start().line(), span.end().line()), (0, 2));
+
+let field = source_map.element_at(1, 4).unwrap();
+assert_eq!(field.join("::"), "address::Country::code");
+```
+*/
+
+use crate::model::identity::Namespace;
+use crate::model::Identifier;
+use std::collections::BTreeMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourcePosition {
+    line: usize,
+    column: usize,
+}
+
+/// A span of output; `end` is the position just after the last character written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SourceSpan {
+    start: SourcePosition,
+    end: SourcePosition,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SourceMap {
+    spans: BTreeMap<Namespace, SourceSpan>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<(usize, usize)> for SourcePosition {
+    fn from(v: (usize, usize)) -> Self {
+        Self::new(v.0, v.1)
+    }
+}
+
+impl SourcePosition {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl SourceSpan {
+    pub fn new(start: SourcePosition, end: SourcePosition) -> Self {
+        Self {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    pub fn start(&self) -> SourcePosition {
+        self.start
+    }
+
+    pub fn end(&self) -> SourcePosition {
+        self.end
+    }
+
+    pub fn contains(&self, position: SourcePosition) -> bool {
+        self.start <= position && position < self.end
+    }
+
+    fn map_lines<F>(&self, f: &F) -> Self
+    where
+        F: Fn(usize) -> usize,
+    {
+        Self {
+            start: SourcePosition::new(f(self.start.line), self.start.column),
+            end: SourcePosition::new(f(self.end.line), self.end.column),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl SourceMap {
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn spans(&self) -> &BTreeMap<Namespace, SourceSpan> {
+        &self.spans
+    }
+
+    pub fn get(&self, namespace: &Namespace, name: &Identifier) -> Option<&SourceSpan> {
+        self.spans.get(&namespace.with(name.clone()))
+    }
+
+    pub fn get_path(&self, path: &Namespace) -> Option<&SourceSpan> {
+        self.spans.get(path)
+    }
+
+    /// Record `span` for the element at `path`; the first span recorded for a path is kept.
+    pub fn insert(&mut self, path: Namespace, span: SourceSpan) -> &mut Self {
+        let _ = self.spans.entry(path).or_insert(span);
+        self
+    }
+
+    /// The path of the innermost element whose span contains the given position.
+    pub fn element_at(&self, line: usize, column: usize) -> Option<&Namespace> {
+        let position = SourcePosition::new(line, column);
+        self.spans
+            .iter()
+            .filter(|(_, span)| span.contains(position))
+            .max_by_key(|(path, span)| (span.start, path.path().len()))
+            .map(|(path, _)| path)
+    }
+
+    pub(crate) fn map_lines<F>(&mut self, f: F)
+    where
+        F: Fn(usize) -> usize,
+    {
+        for span in self.spans.values_mut() {
+            *span = span.map_lines(&f);
+        }
+    }
+}
@@ -9,13 +9,15 @@ More detailed description, with
 
 use crate::error::{ErrorKind, Result};
 use crate::model::identity::Namespace;
+use crate::model::visitor::{walk_module, Visitor, Walk};
 use crate::model::{
-    Comment, Enumeration, FunctionDecl, HasName, Import, Module, ModuleContent, NamedValue,
-    StructuredType, TypeAlias,
+    Comment, Enumeration, FunctionDecl, HasName, Identifier, Implementation, Import, Module,
+    NamedValue, ProtectedRegion, StructuredType, TypeAlias, ValueType,
 };
+use crate::source_map::{SourceMap, SourcePosition, SourceSpan};
 use std::cell::RefCell;
-use std::fmt::{Display, Formatter};
-use std::fs::File;
+use std::fmt::{self, Display, Formatter};
+use std::fs::{create_dir_all, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
@@ -29,19 +31,42 @@ pub enum NewLine {
     CarriageReturnLineFeed,
 }
 
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum DocCommentStyle {
+    #[default]
+    Block,
+    Line,
+}
+
 #[derive(Clone, Debug)]
-pub struct WhitespaceHandling {
+pub struct FormattingPolicy {
     indent: String,
     new_line: NewLine,
+    max_width: usize,
     trim_trailing: bool,
+    collapse_blank_lines: bool,
+    final_new_line: bool,
+    doc_comment_style: DocCommentStyle,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+///
+/// The whitespace settings are now part of the wider `FormattingPolicy`. This alias is not source
+/// compatible: `indent`, `new_line` and `trim_trailing` are now getters, replaced by `set_indent`,
+/// `set_new_line` and `set_trim_trailing`.
+///
+#[deprecated(since = "0.2.0", note = "use `FormattingPolicy` instead")]
+pub type WhitespaceHandling = FormattingPolicy;
+
+#[derive(Clone, Debug, PartialEq, Default)]
 pub enum BlockPlacement {
     Trailing,
     TrailingNewLine,
+    #[default]
     OwnLine,
     OwnLineIndented,
+    /// Opening ends the current line with the marker; closing only outdents, so the block is
+    /// delimited by indentation alone, as in Python.
+    Indentation,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -52,15 +77,45 @@ pub struct Block {
     close_placement: BlockPlacement,
 }
 
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum ListWrapping {
+    #[default]
+    OnePerLine,
+    Fill,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct List {
+    open: String,
+    close: String,
+    wrapping: ListWrapping,
+}
+
+/// Adapts a `std::fmt::Write` target, such as a `String`, for use as the sink of a `CodeWriter`.
+#[derive(Debug, Default)]
+pub struct FmtWriter<F>
+where
+    F: fmt::Write,
+{
+    inner: F,
+    incomplete: Vec<u8>,
+}
+
 pub struct CodeWriter<W>
 where
     W: Write,
 {
     writer: RefCell<W>,
     line_count: usize,
+    pending_blank_lines: usize,
     indent: usize,
-    whitespace: WhitespaceHandling,
+    policy: FormattingPolicy,
     current_line: String,
+    last_line_end: (usize, usize),
+    namespace: Option<Namespace>,
+    source_map: Option<SourceMap>,
+    source_path: Vec<Identifier>,
+    source_starts: Vec<SourcePosition>,
 }
 
 pub trait ModuleWriter<W>
@@ -124,23 +179,176 @@ where
         Err(ErrorKind::UnsupportedElementKind("function_decl".to_string()).into())
     }
 
+    #[allow(unused_variables)]
+    fn write_implementation(
+        &self,
+        writer: &mut CodeWriter<W>,
+        implementation: &Implementation,
+    ) -> Result<()> {
+        Err(ErrorKind::UnsupportedElementKind("implementation".to_string()).into())
+    }
+
     #[allow(unused_variables)]
     fn write_type_alias(&self, writer: &mut CodeWriter<W>, type_alias: &TypeAlias) -> Result<()> {
         Err(ErrorKind::UnsupportedElementKind("type_alias".to_string()).into())
     }
+
+    #[allow(unused_variables)]
+    fn write_region(&self, writer: &mut CodeWriter<W>, region: &ProtectedRegion) -> Result<()> {
+        Err(ErrorKind::UnsupportedElementKind("region".to_string()).into())
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
 // Private Types
 // ------------------------------------------------------------------------------------------------
 
+struct ModuleContentWriter<'a, W>
+where
+    W: Write,
+{
+    writer: &'a mut CodeWriter<W>,
+    module_writer: &'a dyn ModuleWriter<W>,
+}
+
+struct ModuleSinkWriter<'a, W, S>
+where
+    W: Write,
+    S: FnMut(&Namespace) -> Result<W>,
+{
+    open_sink: S,
+    module_writer: &'a dyn ModuleWriter<W>,
+    policy: FormattingPolicy,
+    sinks: Vec<(Namespace, W)>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
 
+pub const DEFAULT_MAX_WIDTH: usize = 100;
+
+/// Reflow markdown `text` to `width` columns, leaving fences, tables and headings intact. The
+/// text of a list item is reflowed with a hanging indent that aligns with the item's marker.
+pub fn reflow_markdown(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Default::default();
+    let mut paragraph: Vec<&str> = Default::default();
+    let mut fence: Option<&str> = None;
+    let mut list_item: Option<(&str, String)> = None;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            lines.push(line.trim_end().to_string());
+        } else if let Some(marker) = fence_marker(trimmed) {
+            fill_paragraph(&mut lines, &mut paragraph, width, &list_item.take());
+            fence = Some(marker);
+            lines.push(line.trim_end().to_string());
+        } else if trimmed.is_empty() {
+            fill_paragraph(&mut lines, &mut paragraph, width, &list_item.take());
+            lines.push(String::new());
+        } else if let Some(marker_len) = list_marker_len(trimmed) {
+            fill_paragraph(&mut lines, &mut paragraph, width, &list_item.take());
+            let prefix_len = line.len() - trimmed.len() + marker_len;
+            list_item = Some((
+                &line[..prefix_len],
+                " ".repeat(line[..prefix_len].chars().count()),
+            ));
+            paragraph.push(&line[prefix_len..]);
+        } else if list_item.is_some() && !is_verbatim_line(trimmed) {
+            paragraph.push(trimmed);
+        } else if list_item.is_some() || is_verbatim_line(line) {
+            fill_paragraph(&mut lines, &mut paragraph, width, &list_item.take());
+            lines.push(line.trim_end().to_string());
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    fill_paragraph(&mut lines, &mut paragraph, width, &list_item);
+    lines
+}
+
+/// Writes `module` and its content, but not the content of any sub-modules, to `writer`.
+pub fn write_module_content<W: Write>(
+    writer: &mut CodeWriter<W>,
+    module_writer: &dyn ModuleWriter<W>,
+    module: &Module,
+) -> Result<()> {
+    walk_module(
+        &mut ModuleContentWriter {
+            writer,
+            module_writer,
+        },
+        module,
+    )
+}
+
+pub const REGION_BEGIN_MARKER: &str = "@protected-region begin";
+
+pub const REGION_END_MARKER: &str = "@protected-region end";
+
 pub type NamespaceFileFn = Box<dyn Fn(&Namespace, &Path) -> PathBuf>;
 
-pub type ModuleFileWriterFn = Box<dyn Fn() -> Box<dyn ModuleWriter<File>>>;
+pub type ModuleWriterFn<W> = Box<dyn Fn() -> Box<dyn ModuleWriter<W>>>;
+
+pub type ModuleFileWriterFn = ModuleWriterFn<File>;
+
+/// Renders `module`, but not the content of any sub-modules, to a string.
+pub fn module_to_string(
+    module_writer: &dyn ModuleWriter<FmtWriter<String>>,
+    module: &Module,
+) -> Result<String> {
+    module_to_string_with_policy(module_writer, module, Default::default())
+}
+
+pub fn module_to_string_with_policy(
+    module_writer: &dyn ModuleWriter<FmtWriter<String>>,
+    module: &Module,
+    policy: FormattingPolicy,
+) -> Result<String> {
+    let mut writer = CodeWriter::from_fmt_with_policy(String::new(), policy);
+    write_module_content(&mut writer, module_writer, module)?;
+    writer.flush()?;
+    Ok(writer.into_inner().into_inner())
+}
+
+///
+/// Writes `top_module`, and all of its sub-modules, each to the sink returned by `open_sink` for
+/// the module's namespace. The flushed sinks are returned, in the order they were opened.
+///
+pub fn write_modules_to<W, S>(
+    top_module: &Module,
+    open_sink: S,
+    module_writer: &dyn ModuleWriter<W>,
+) -> Result<Vec<(Namespace, W)>>
+where
+    W: Write,
+    S: FnMut(&Namespace) -> Result<W>,
+{
+    write_modules_to_with_policy(top_module, open_sink, module_writer, Default::default())
+}
+
+pub fn write_modules_to_with_policy<W, S>(
+    top_module: &Module,
+    open_sink: S,
+    module_writer: &dyn ModuleWriter<W>,
+    policy: FormattingPolicy,
+) -> Result<Vec<(Namespace, W)>>
+where
+    W: Write,
+    S: FnMut(&Namespace) -> Result<W>,
+{
+    let mut sink_writer = ModuleSinkWriter {
+        open_sink,
+        module_writer,
+        policy,
+        sinks: Default::default(),
+    };
+    walk_module(&mut sink_writer, top_module)?;
+    Ok(sink_writer.sinks)
+}
 
 pub fn write_modules(
     top_module: Module,
@@ -148,13 +356,18 @@ pub fn write_modules(
     namespace_to_file: &impl Fn(&Namespace, &Path) -> PathBuf,
     module_writer: &impl Fn() -> Box<dyn ModuleWriter<File>>,
 ) -> Result<()> {
-    write_a_module(
-        &Namespace::new(vec![top_module.name().clone()]),
-        fs_root,
+    let _ = write_modules_to(
         &top_module,
-        &namespace_to_file,
-        &module_writer,
-    )
+        |namespace| {
+            let file_path = namespace_to_file(namespace, fs_root);
+            if let Some(parent) = file_path.parent() {
+                create_dir_all(parent)?;
+            }
+            Ok(File::create(&file_path)?)
+        },
+        module_writer().as_ref(),
+    )?;
+    Ok(())
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -179,7 +392,7 @@ impl Display for NewLine {
             "{}",
             match self {
                 NewLine::LineFeed => "\n",
-                NewLine::CarriageReturnLineFeed => "\r'n",
+                NewLine::CarriageReturnLineFeed => "\r\n",
             }
         )
     }
@@ -187,43 +400,87 @@ impl Display for NewLine {
 
 // ------------------------------------------------------------------------------------------------
 
-impl Default for WhitespaceHandling {
+impl Default for FormattingPolicy {
     fn default() -> Self {
         Self {
             indent: "    ".to_string(),
             new_line: Default::default(),
+            max_width: DEFAULT_MAX_WIDTH,
             trim_trailing: true,
+            collapse_blank_lines: false,
+            final_new_line: true,
+            doc_comment_style: Default::default(),
         }
     }
 }
 
-impl WhitespaceHandling {
-    pub fn indent(&mut self, indent: &str) -> &mut Self {
+impl FormattingPolicy {
+    pub fn indent(&self) -> &String {
+        &self.indent
+    }
+
+    pub fn set_indent(&mut self, indent: &str) -> &mut Self {
         self.indent = indent.to_string();
         self
     }
 
-    pub fn new_line(&mut self, new_line: NewLine) -> &mut Self {
+    pub fn new_line(&self) -> &NewLine {
+        &self.new_line
+    }
+
+    pub fn set_new_line(&mut self, new_line: NewLine) -> &mut Self {
         self.new_line = new_line;
         self
     }
 
-    pub fn trim_trailing(&mut self) -> &mut Self {
-        self.trim_trailing = true;
+    pub fn max_width(&self) -> usize {
+        self.max_width
+    }
+
+    pub fn set_max_width(&mut self, max_width: usize) -> &mut Self {
+        self.max_width = max_width;
         self
     }
 
+    pub fn trim_trailing(&self) -> bool {
+        self.trim_trailing
+    }
+
+    pub fn set_trim_trailing(&mut self, trim_trailing: bool) -> &mut Self {
+        self.trim_trailing = trim_trailing;
+        self
+    }
+
+    #[deprecated(since = "0.2.0", note = "use `set_trim_trailing(false)` instead")]
     pub fn no_trim_trailing(&mut self) -> &mut Self {
-        self.trim_trailing = false;
+        self.set_trim_trailing(false)
+    }
+
+    pub fn collapse_blank_lines(&self) -> bool {
+        self.collapse_blank_lines
+    }
+
+    pub fn set_collapse_blank_lines(&mut self, collapse_blank_lines: bool) -> &mut Self {
+        self.collapse_blank_lines = collapse_blank_lines;
         self
     }
-}
 
-// ------------------------------------------------------------------------------------------------
+    pub fn final_new_line(&self) -> bool {
+        self.final_new_line
+    }
 
-impl Default for BlockPlacement {
-    fn default() -> Self {
-        Self::OwnLine
+    pub fn set_final_new_line(&mut self, final_new_line: bool) -> &mut Self {
+        self.final_new_line = final_new_line;
+        self
+    }
+
+    pub fn doc_comment_style(&self) -> &DocCommentStyle {
+        &self.doc_comment_style
+    }
+
+    pub fn set_doc_comment_style(&mut self, doc_comment_style: DocCommentStyle) -> &mut Self {
+        self.doc_comment_style = doc_comment_style;
+        self
     }
 }
 
@@ -244,6 +501,16 @@ impl Block {
         }
     }
 
+    /// A block opened by `open`, such as `":"`, with no closing marker.
+    pub fn indentation(open: &str) -> Self {
+        Self::new(
+            open,
+            BlockPlacement::Indentation,
+            "",
+            BlockPlacement::Indentation,
+        )
+    }
+
     pub fn open_with(&self) -> (&String, &BlockPlacement) {
         (&self.open, &self.open_placement)
     }
@@ -255,20 +522,138 @@ impl Block {
 
 // ------------------------------------------------------------------------------------------------
 
+impl List {
+    pub fn new(open: &str, close: &str, wrapping: ListWrapping) -> Self {
+        Self {
+            open: open.to_string(),
+            close: close.to_string(),
+            wrapping,
+        }
+    }
+
+    pub fn open_with(&self) -> &String {
+        &self.open
+    }
+
+    pub fn close_with(&self) -> &String {
+        &self.close
+    }
+
+    pub fn wrapping(&self) -> &ListWrapping {
+        &self.wrapping
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<F> FmtWriter<F>
+where
+    F: fmt::Write,
+{
+    pub fn new(inner: F) -> Self {
+        Self {
+            inner,
+            incomplete: Default::default(),
+        }
+    }
+
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
+
+impl<F> Write for FmtWriter<F>
+where
+    F: fmt::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.incomplete.extend_from_slice(buf);
+        let valid = match std::str::from_utf8(&self.incomplete) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                self.incomplete.clear();
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e));
+            }
+        };
+        let text = std::str::from_utf8(&self.incomplete[..valid]).unwrap_or_default();
+        self.inner.write_str(text).map_err(std::io::Error::other)?;
+        let _ = self.incomplete.drain(..valid);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<F> CodeWriter<FmtWriter<F>>
+where
+    F: fmt::Write,
+{
+    /// A writer for a `std::fmt::Write` target, such as a `String`.
+    pub fn from_fmt(w: F) -> Self {
+        Self::from_fmt_with_policy(w, Default::default())
+    }
+
+    pub fn from_fmt_with_policy(w: F, policy: FormattingPolicy) -> Self {
+        Self::with_policy(FmtWriter::new(w), policy)
+    }
+}
+
 impl<W> CodeWriter<W>
 where
     W: Write,
 {
     pub fn new(w: W) -> Self {
+        Self::with_policy(w, Default::default())
+    }
+
+    pub fn with_policy(w: W, policy: FormattingPolicy) -> Self {
         Self {
             writer: RefCell::new(w),
             line_count: 0,
+            pending_blank_lines: 0,
             indent: 0,
-            whitespace: Default::default(),
+            policy,
             current_line: String::default(),
+            last_line_end: (0, 0),
+            namespace: None,
+            source_map: None,
+            source_path: Default::default(),
+            source_starts: Default::default(),
         }
     }
 
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    pub fn policy(&self) -> &FormattingPolicy {
+        &self.policy
+    }
+
+    pub fn max_width(&self) -> usize {
+        self.policy.max_width
+    }
+
+    /// Sets `max_width` on this writer's policy; equivalent to `FormattingPolicy::set_max_width`.
+    pub fn set_max_width(&mut self, max_width: usize) {
+        self.policy.max_width = max_width;
+    }
+
+    /// The namespace of the module being written, if known; module writers use this to resolve
+    /// imports relative to the current module.
+    pub fn namespace(&self) -> &Option<Namespace> {
+        &self.namespace
+    }
+
+    pub fn set_namespace(&mut self, namespace: Namespace) {
+        self.namespace = Some(namespace);
+    }
+
     pub fn indent(&mut self) {
         self.indent += 1;
     }
@@ -302,6 +687,11 @@ where
                 self.new_line()?;
                 self.indent();
             }
+            BlockPlacement::Indentation => {
+                self.write_str(block_marker)?;
+                self.new_line()?;
+                self.indent();
+            }
         }
         Ok(())
     }
@@ -338,6 +728,17 @@ where
                 self.new_line()?;
                 self.outdent();
             }
+            BlockPlacement::Indentation => {
+                if !self.current_line.trim().is_empty() {
+                    self.new_line()?;
+                }
+                self.current_line.clear();
+                self.outdent();
+                if !block_marker.is_empty() {
+                    self.write_str(block_marker)?;
+                    self.new_line()?;
+                }
+            }
         }
         Ok(())
     }
@@ -348,18 +749,85 @@ where
         self.write_str(block_marker)
     }
 
+    /// `true` if `text` can be appended to the current line within `max_width`.
+    pub fn fits(&self, text: &str) -> bool {
+        self.current_position().1 + text.chars().count() <= self.policy.max_width
+    }
+
+    pub fn write_list<S: AsRef<str>>(&mut self, style: &List, items: &[S]) -> Result<()> {
+        self.write_list_then(style, items, "")
+    }
+
+    /// Writes `open items close then`, wrapping `items` if the line would exceed `max_width`.
+    pub fn write_list_then<S: AsRef<str>>(
+        &mut self,
+        style: &List,
+        items: &[S],
+        then: &str,
+    ) -> Result<()> {
+        let items: Vec<(Option<&Identifier>, &str)> =
+            items.iter().map(|item| (None, item.as_ref())).collect();
+        self.write_items_then(style, &items, then)
+    }
+
+    /// As `write_list_then`, but also records the span of each item that has a name, such as a
+    /// parameter, as an element.
+    pub fn write_element_list_then<S: AsRef<str>>(
+        &mut self,
+        style: &List,
+        items: &[(Option<&Identifier>, S)],
+        then: &str,
+    ) -> Result<()> {
+        let items: Vec<(Option<&Identifier>, &str)> = items
+            .iter()
+            .map(|(name, item)| (*name, item.as_ref()))
+            .collect();
+        self.write_items_then(style, &items, then)
+    }
+
+    /// Writes documentation `text`, reflowed to fit within `max_width`, with `prefix` on each line.
+    pub fn write_doc_text(&mut self, prefix: &str, text: &str) -> Result<()> {
+        let width = self
+            .policy
+            .max_width
+            .saturating_sub(self.current_position().1 + prefix.chars().count());
+        for line in reflow_markdown(text, width) {
+            if line.is_empty() {
+                self.write_str(prefix.trim_end())?;
+            } else {
+                self.write_str(prefix)?;
+                self.write_str(&line)?;
+            }
+            self.new_line()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the begin and end markers of an, initially empty, protected region as comments.
+    pub fn write_region_markers(&mut self, comment_prefix: &str, name: &str) -> Result<()> {
+        self.write_str(&format!(
+            "{}{} {}",
+            comment_prefix, REGION_BEGIN_MARKER, name
+        ))?;
+        self.new_line()?;
+        self.write_str(&format!("{}{} {}", comment_prefix, REGION_END_MARKER, name))?;
+        self.new_line()
+    }
+
     pub fn current_line_len(&mut self) -> usize {
-        self.current_line.len()
+        self.current_line.chars().count()
     }
 
     pub fn write_str(&mut self, text: &str) -> Result<()> {
         if !text.is_empty() {
-            let lines: Vec<&str> = text.split(&self.whitespace.new_line.to_string()).collect();
+            let lines: Vec<&str> = text.split('\n').collect();
             let end = lines.len() - 1;
             for (i, s) in lines.iter().enumerate() {
-                self.write_no_newline(s);
-                if i < end || (i == end && text.ends_with(&self.whitespace.new_line.to_string())) {
+                if i < end {
+                    self.write_no_newline(s.strip_suffix('\r').unwrap_or(s));
                     self.new_line()?;
+                } else {
+                    self.write_no_newline(s);
                 }
             }
         }
@@ -371,36 +839,178 @@ where
     }
 
     pub fn blank_line(&mut self) -> Result<()> {
-        if !self.current_line.trim().is_empty() {
-            self.write_current_line()?;
+        if self.current_line.trim().is_empty() {
+            self.current_line.clear();
         }
-        write!(self.writer.borrow_mut(), "{}", self.whitespace.new_line)?;
-        Ok(())
+        self.new_line()
     }
 
     pub fn new_line(&mut self) -> Result<()> {
-        self.write_current_line()?;
-        write!(self.writer.borrow_mut(), "{}", self.whitespace.new_line)?;
-        self.line_count += 1;
-        Ok(())
+        if self.policy.trim_trailing {
+            self.current_line
+                .truncate(self.current_line.trim_end().len());
+        }
+        if self.current_line.is_empty() {
+            self.pending_blank_lines += 1;
+            Ok(())
+        } else {
+            self.write_current_line()?;
+            self.write_new_line()
+        }
     }
 
+    /// Writes any partial line; with `final_new_line` it is terminated and trailing blank
+    /// lines are dropped.
     pub fn flush(&mut self) -> Result<()> {
-        self.write_current_line()
+        if self.policy.final_new_line {
+            if !self.current_line.is_empty() {
+                self.new_line()?;
+            }
+        } else {
+            self.write_pending_blank_lines()?;
+            self.write_current_line()?;
+        }
+        self.writer.borrow_mut().flush()?;
+        Ok(())
+    }
+
+    /// Record the span of each element written between `begin_element` and `end_element`,
+    /// keyed by its path below `parent`, if any.
+    pub fn enable_source_map(&mut self, parent: Option<Namespace>) {
+        self.source_map = Some(Default::default());
+        self.source_path = parent.map(|p| p.path().clone()).unwrap_or_default();
+        self.source_starts.clear();
+    }
+
+    pub fn source_map(&self) -> Option<&SourceMap> {
+        self.source_map.as_ref()
+    }
+
+    pub fn take_source_map(&mut self) -> Option<SourceMap> {
+        self.source_map.take()
+    }
+
+    /// Start the span for the element `name`, nested within any element already begun.
+    pub fn begin_element(&mut self, name: &Identifier) {
+        if self.source_map.is_some() {
+            self.source_path.push(name.clone());
+            self.source_starts.push(self.current_position().into());
+        }
+    }
+
+    /// End the span for the element most recently begun.
+    pub fn end_element(&mut self) {
+        if self.source_map.is_some() {
+            if let Some(start) = self.source_starts.pop() {
+                let end = if self.current_line.is_empty() {
+                    self.last_line_end
+                } else {
+                    self.current_position()
+                };
+                if let Some(source_map) = &mut self.source_map {
+                    let _ = source_map.insert(
+                        Namespace::new(self.source_path.clone()),
+                        SourceSpan::new(start, end.into()),
+                    );
+                }
+                let _ = self.source_path.pop();
+            }
+        }
     }
 
     pub fn current_position(&self) -> (usize, usize) {
         (
-            self.line_count,
-            (self.indent * self.whitespace.indent.len()) + self.current_line.len(),
+            self.line_count + self.blank_lines_to_write(),
+            (self.indent * self.policy.indent.chars().count()) + self.current_line.chars().count(),
         )
     }
 
     // --------------------------------------------------------------------------------------------
 
+    fn write_items_then(
+        &mut self,
+        style: &List,
+        items: &[(Option<&Identifier>, &str)],
+        then: &str,
+    ) -> Result<()> {
+        let single_line = format!(
+            "{}{}{}",
+            style.open,
+            items
+                .iter()
+                .map(|(_, item)| *item)
+                .collect::<Vec<&str>>()
+                .join(", "),
+            style.close
+        );
+        if items.is_empty() || self.fits(&format!("{}{}", single_line, then)) {
+            self.write_str(&style.open)?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    self.write_str(", ")?;
+                }
+                self.write_item(item)?;
+            }
+            self.write_str(&style.close)?;
+            return self.write_str(then);
+        }
+        self.write_str(&style.open)?;
+        self.new_line()?;
+        self.indent();
+        match style.wrapping {
+            ListWrapping::OnePerLine => {
+                for item in items {
+                    self.write_item(item)?;
+                    self.write_str(",")?;
+                    self.new_line()?;
+                }
+            }
+            ListWrapping::Fill => {
+                let last = items.len() - 1;
+                for (i, item) in items.iter().enumerate() {
+                    let separator = if i < last { "," } else { "" };
+                    if !self.current_line.is_empty() {
+                        if self.fits(&format!(" {}{}", item.1, separator)) {
+                            self.space();
+                        } else {
+                            self.new_line()?;
+                        }
+                    }
+                    self.write_item(item)?;
+                    self.write_str(separator)?;
+                }
+                self.new_line()?;
+            }
+        }
+        self.outdent();
+        self.write_str(&style.close)?;
+        self.write_str(then)
+    }
+
+    fn write_item(&mut self, (name, item): &(Option<&Identifier>, &str)) -> Result<()> {
+        if let Some(name) = name {
+            self.begin_element(name);
+        }
+        self.write_str(item)?;
+        if name.is_some() {
+            self.end_element();
+        }
+        Ok(())
+    }
+
     fn write_current_line(&mut self) -> Result<()> {
-        self.write_current_indentation()?;
-        self.write_current_line_no_indent()
+        if !self.current_line.is_empty() {
+            self.write_pending_blank_lines()?;
+            self.write_current_indentation()?;
+            write!(self.writer.borrow_mut(), "{}", self.current_line)?;
+            self.last_line_end = (
+                self.line_count,
+                (self.indent * self.policy.indent.chars().count())
+                    + self.current_line.chars().count(),
+            );
+            self.current_line.clear();
+        }
+        Ok(())
     }
 
     fn write_no_newline(&mut self, text: &str) {
@@ -409,83 +1019,261 @@ where
 
     fn write_current_indentation(&mut self) -> Result<()> {
         for _ in 0..self.indent {
-            write!(self.writer.borrow_mut(), "{}", self.whitespace.indent)?;
+            write!(self.writer.borrow_mut(), "{}", self.policy.indent)?;
+        }
+        Ok(())
+    }
+
+    fn write_new_line(&mut self) -> Result<()> {
+        write!(self.writer.borrow_mut(), "{}", self.policy.new_line)?;
+        self.line_count += 1;
+        Ok(())
+    }
+
+    fn blank_lines_to_write(&self) -> usize {
+        if self.line_count == 0 {
+            0
+        } else if self.policy.collapse_blank_lines {
+            self.pending_blank_lines.min(1)
+        } else {
+            self.pending_blank_lines
+        }
+    }
+
+    fn write_pending_blank_lines(&mut self) -> Result<()> {
+        for _ in 0..self.blank_lines_to_write() {
+            self.write_new_line()?;
+        }
+        self.pending_blank_lines = 0;
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<W> Visitor for ModuleContentWriter<'_, W>
+where
+    W: Write,
+{
+    fn enter_module(&mut self, namespace: &Namespace, module: &Module) -> Result<Walk> {
+        self.writer.begin_element(module.name());
+        if namespace.path().len() == 1 {
+            self.module_writer.write_module(self.writer, module)?;
+            self.writer.blank_line()?;
+            Ok(Walk::Continue)
+        } else {
+            self.module_writer.write_sub_module(self.writer, module)?;
+            Ok(Walk::SkipChildren)
+        }
+    }
+
+    fn exit_module(&mut self, namespace: &Namespace, _: &Module) -> Result<()> {
+        self.writer.end_element();
+        if namespace.path().len() > 1 {
+            self.writer.blank_line()?;
         }
         Ok(())
     }
 
-    fn write_current_line_no_indent(&mut self) -> Result<()> {
-        if self.whitespace.trim_trailing {
-            let _ = self.current_line.trim_end();
+    fn visit_import(&mut self, import: &Import) -> Result<()> {
+        let position = self.writer.current_position();
+        self.module_writer.write_import(self.writer, import)?;
+        // Writers that group imports in the module header write nothing here.
+        if self.writer.current_position() != position {
+            self.writer.blank_line()?;
         }
-        write!(self.writer.borrow_mut(), "{}", self.current_line)?;
-        self.current_line.clear();
         Ok(())
     }
+
+    fn visit_comment(&mut self, comment: &Comment) -> Result<()> {
+        self.module_writer.write_comment(self.writer, comment)?;
+        self.writer.blank_line()
+    }
+
+    fn visit_region(&mut self, region: &ProtectedRegion) -> Result<()> {
+        self.module_writer.write_region(self.writer, region)?;
+        self.writer.blank_line()
+    }
+
+    fn visit_constant(&mut self, constant: &NamedValue) -> Result<()> {
+        self.writer.begin_element(constant.name());
+        self.module_writer.write_constant(self.writer, constant)?;
+        self.end_element()
+    }
+
+    fn visit_variable(&mut self, variable: &NamedValue) -> Result<()> {
+        self.writer.begin_element(variable.name());
+        self.module_writer.write_variable(self.writer, variable)?;
+        self.end_element()
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) -> Result<()> {
+        self.writer.begin_element(type_alias.name());
+        self.module_writer
+            .write_type_alias(self.writer, type_alias)?;
+        self.end_element()
+    }
+
+    fn enter_structured_type(&mut self, structured_type: &StructuredType) -> Result<Walk> {
+        self.writer.begin_element(structured_type.name());
+        self.module_writer
+            .write_structured_type(self.writer, structured_type)?;
+        Ok(Walk::SkipChildren)
+    }
+
+    fn exit_structured_type(&mut self, _: &StructuredType) -> Result<()> {
+        self.end_element()
+    }
+
+    fn enter_enumeration(&mut self, enumeration: &Enumeration) -> Result<Walk> {
+        self.writer.begin_element(enumeration.name());
+        self.module_writer
+            .write_enumeration(self.writer, enumeration)?;
+        Ok(Walk::SkipChildren)
+    }
+
+    fn exit_enumeration(&mut self, _: &Enumeration) -> Result<()> {
+        self.end_element()
+    }
+
+    fn enter_implementation(&mut self, implementation: &Implementation) -> Result<Walk> {
+        if let Some(name) = implementation_element_name(implementation) {
+            self.writer.begin_element(&name);
+        }
+        self.module_writer
+            .write_implementation(self.writer, implementation)?;
+        Ok(Walk::SkipChildren)
+    }
+
+    fn exit_implementation(&mut self, implementation: &Implementation) -> Result<()> {
+        if implementation_element_name(implementation).is_some() {
+            self.end_element()
+        } else {
+            self.writer.blank_line()
+        }
+    }
+
+    fn enter_function(&mut self, function: &FunctionDecl) -> Result<Walk> {
+        self.writer.begin_element(function.name());
+        self.module_writer
+            .write_function_decl(self.writer, function)?;
+        Ok(Walk::SkipChildren)
+    }
+
+    fn exit_function(&mut self, _: &FunctionDecl) -> Result<()> {
+        self.end_element()
+    }
+}
+
+impl<W> ModuleContentWriter<'_, W>
+where
+    W: Write,
+{
+    fn end_element(&mut self) -> Result<()> {
+        self.writer.end_element();
+        self.writer.blank_line()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<W, S> Visitor for ModuleSinkWriter<'_, W, S>
+where
+    W: Write,
+    S: FnMut(&Namespace) -> Result<W>,
+{
+    fn enter_module(&mut self, namespace: &Namespace, module: &Module) -> Result<Walk> {
+        let mut writer = CodeWriter::with_policy((self.open_sink)(namespace)?, self.policy.clone());
+        writer.set_namespace(namespace.clone());
+        write_module_content(&mut writer, self.module_writer, module)?;
+        writer.flush()?;
+        self.sinks.push((namespace.clone(), writer.into_inner()));
+        Ok(Walk::Continue)
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
-fn write_a_module(
-    current_namespace: &Namespace,
-    current_directory: &Path,
-    module: &Module,
-    namespace_to_file: &impl Fn(&Namespace, &Path) -> PathBuf,
-    module_writer: &impl Fn() -> Box<dyn ModuleWriter<File>>,
-) -> Result<()> {
-    let file_path = namespace_to_file(current_namespace, current_directory);
-    let file = File::open(&file_path)?;
-    let mut writer = CodeWriter::new(file);
-    let visitor = module_writer();
-    visitor.write_module(&mut writer, &module)?;
-    writer.blank_line()?;
-    for element in module.content() {
-        match element {
-            ModuleContent::Import(v) => {
-                visitor.write_import(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Comment(v) => {
-                visitor.write_comment(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Structure(v) => {
-                visitor.write_structured_type(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Constant(v) => {
-                visitor.write_constant(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Variable(v) => {
-                visitor.write_variable(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Function(v) => {
-                visitor.write_function_decl(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Alias(v) => {
-                visitor.write_type_alias(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Module(v) => {
-                visitor.write_sub_module(&mut writer, v)?;
-                writer.blank_line()?;
-                // TODO: this won't work
-                write_a_module(
-                    &current_namespace.with(v.name().clone()),
-                    &file_path,
-                    v,
-                    namespace_to_file,
-                    module_writer,
-                )
-            }
-        }?;
+///
+/// Implementations are keyed apart from the type they implement, as `impl Foo` or
+/// `impl Trait for Foo`, using the names of referenced or generic types.
+///
+fn implementation_element_name(implementation: &Implementation) -> Option<Identifier> {
+    let type_name = |value_type: &ValueType| match value_type {
+        ValueType::Reference(name) | ValueType::Generic(name, _) => Some(name.to_string()),
+        _ => None,
+    };
+    let target = type_name(implementation.target_type())?;
+    Some(Identifier::new(&match implementation.trait_type() {
+        None => format!("impl {}", target),
+        Some(trait_type) => format!("impl {} for {}", type_name(trait_type)?, target),
+    }))
+}
+
+fn fence_marker(line: &str) -> Option<&'static str> {
+    if line.starts_with("```") {
+        Some("```")
+    } else if line.starts_with("~~~") {
+        Some("~~~")
+    } else {
+        None
     }
-    Ok(())
+}
+
+fn is_verbatim_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    line.starts_with("    ")
+        || line.starts_with('\t')
+        || trimmed.starts_with('#')
+        || trimmed.starts_with('|')
+        || trimmed.starts_with('>')
+}
+
+/// The length of the list item marker, including the following space, that `line` starts with.
+fn list_marker_len(line: &str) -> Option<usize> {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return Some(2);
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && (line[digits..].starts_with(". ") || line[digits..].starts_with(") ")) {
+        Some(digits + 2)
+    } else {
+        None
+    }
+}
+
+/// Fills `paragraph` into `lines`; a list item's first line starts with its marker and any
+/// following lines with the hanging indent.
+fn fill_paragraph(
+    lines: &mut Vec<String>,
+    paragraph: &mut Vec<&str>,
+    width: usize,
+    list_item: &Option<(&str, String)>,
+) {
+    let (mut prefix, hanging) = match list_item {
+        None => ("", ""),
+        Some((marker, hanging)) => (*marker, hanging.as_str()),
+    };
+    let width = width.saturating_sub(hanging.chars().count());
+    let mut line = String::new();
+    for word in paragraph.iter().flat_map(|line| line.split_whitespace()) {
+        if line.is_empty() {
+            line.push_str(word);
+        } else if line.chars().count() + 1 + word.chars().count() <= width {
+            line.push(' ');
+            line.push_str(word);
+        } else {
+            lines.push(format!("{}{}", prefix, line));
+            prefix = hanging;
+            line = word.to_string();
+        }
+    }
+    if !line.is_empty() {
+        lines.push(format!("{}{}", prefix, line));
+    }
+    paragraph.clear();
 }
 
 // ------------------------------------------------------------------------------------------------
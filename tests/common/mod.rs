@@ -1,62 +1,66 @@
 use code_writer::error::Result;
 use code_writer::language::rust::RustWriter;
+use code_writer::model::identity::Namespace;
+use code_writer::model::visitor::{walk_module, Visitor, Walk};
 use code_writer::model::{
-    Builder, Comment, Enumeration, Field, HasDocumentation, HasVisibility, Identifier, Import,
-    IsOptional, KnownType, Module, ModuleContent, NamedValue, StructuredType, TypeAlias, ValueType,
+    Builder, Comment, Enumeration, Field, HasDocumentation, HasFieldId, HasVisibility, Identifier,
+    Import, IsOptional, KnownType, Module, NamedValue, StructuredType, TypeAlias, ValueType,
     Visibility,
 };
-use code_writer::writer::{CodeWriter, ModuleWriter};
+use code_writer::writer::{write_module_content, CodeWriter, ModuleWriter};
+use std::io::Write;
 
 pub fn print_module(module: &Module) -> Result<()> {
     let mut out = std::io::stdout();
     let mut writer = CodeWriter::new(&mut out);
+    walk_module(
+        &mut ModulePrinter {
+            writer: &mut writer,
+            module_writer: RustWriter::default(),
+        },
+        module,
+    )?;
+    writer.flush()
+}
 
-    let visitor = RustWriter::default();
+struct ModulePrinter<'a, W: Write> {
+    writer: &'a mut CodeWriter<W>,
+    module_writer: RustWriter,
+}
 
-    visitor.write_module(&mut writer, &module)?;
-    writer.blank_line()?;
+impl<W: Write> Visitor for ModulePrinter<'_, W> {
+    fn enter_module(&mut self, namespace: &Namespace, module: &Module) -> Result<Walk> {
+        if namespace.path().len() > 1 {
+            self.writer.write_str(SEPARATOR)?;
+            self.writer.blank_line()?;
+        }
+        write_module_content(self.writer, &self.module_writer, module)?;
+        Ok(Walk::Continue)
+    }
 
-    for element in module.content() {
-        match element {
-            ModuleContent::Import(v) => {
-                visitor.write_import(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Comment(v) => {
-                visitor.write_comment(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Structure(v) => {
-                visitor.write_structured_type(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Constant(v) => {
-                visitor.write_constant(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Variable(v) => {
-                visitor.write_variable(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Function(v) => {
-                visitor.write_function_decl(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Alias(v) => {
-                visitor.write_type_alias(&mut writer, v)?;
-                writer.blank_line()
-            }
-            ModuleContent::Module(v) => {
-                visitor.write_sub_module(&mut writer, v)?;
-                writer.blank_line()?;
-                writer.write_str("------------------------------------------------------------------------------------------------")?;
-                print_module(v)?;
-                writer.write_str("------------------------------------------------------------------------------------------------")?;
-                writer.blank_line()
-            }
-        }?;
+    fn exit_module(&mut self, namespace: &Namespace, _: &Module) -> Result<()> {
+        if namespace.path().len() > 1 {
+            self.writer.write_str(SEPARATOR)?;
+            self.writer.blank_line()?;
+        }
+        Ok(())
+    }
+}
+
+const SEPARATOR: &str =
+    "------------------------------------------------------------------------------------------------";
+
+pub fn write_to_string<F>(f: F) -> String
+where
+    F: FnOnce(&mut CodeWriter<&mut Vec<u8>>) -> Result<()>,
+{
+    let mut buffer: Vec<u8> = Default::default();
+    {
+        let mut writer = CodeWriter::new(&mut buffer);
+        f(&mut writer).unwrap();
+        writer.flush().unwrap();
     }
-    Ok(())
+    String::from_utf8(buffer).unwrap()
 }
 
 pub fn write_code_model<W>(code: &mut CodeWriter<W>, writer: Box<dyn ModuleWriter<W>>)
@@ -100,6 +104,7 @@ where
                         Identifier::new("line_one"),
                         ValueType::Known(KnownType::String),
                     )
+                    .set_field_id(1)
                     .required()
                     .build(),
                 )
@@ -108,11 +113,13 @@ where
                         Identifier::new("line_two"),
                         ValueType::Known(KnownType::String),
                     )
+                    .set_field_id(2)
                     .optional()
                     .build(),
                 )
                 .add_field(
                     Field::new(Identifier::new("city"), ValueType::Known(KnownType::String))
+                        .set_field_id(3)
                         .required()
                         .build(),
                 )
@@ -121,11 +128,13 @@ where
                         Identifier::new("state"),
                         ValueType::Known(KnownType::String),
                     )
+                    .set_field_id(4)
                     .required()
                     .build(),
                 )
                 .add_field(
                     Field::new(Identifier::new("zip"), ValueType::Known(KnownType::String))
+                        .set_field_id(5)
                         .required()
                         .build(),
                 ),
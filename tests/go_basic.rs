@@ -0,0 +1,301 @@
+use code_writer::language::go::{go_formatting_policy, GoLayout, GoWriter};
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    Builder, Enumeration, EnumerationVariant, Field, FunctionDecl, GenericParameter,
+    HasDocumentation, HasGenericParameters, HasOptionalType, HasVisibility, Identifier, Import,
+    IsOptional, Module, NamedValue, Parameter, StructuredType, TypeAlias, Value, ValueType,
+    Visibility,
+};
+use code_writer::project::ProjectLayout;
+use code_writer::writer::{
+    module_to_string, module_to_string_with_policy, CodeWriter, ModuleWriter,
+};
+use std::path::PathBuf;
+
+pub mod common;
+
+#[test]
+fn test_struct() {
+    let structure = StructuredType::structure(Identifier::new("Page"))
+        .set_visibility(Visibility::Public)
+        .set_documentation("Page is a page of results.")
+        .add_generic_parameter(GenericParameter::with_bounds(
+            Identifier::new("T"),
+            vec![ValueType::Reference(Identifier::new("Entity"))],
+        ))
+        .add_extend(ValueType::Reference(Identifier::new("Paged")))
+        .add_field(
+            Field::new(
+                Identifier::new("items"),
+                ValueType::array_of(ValueType::Reference(Identifier::new("T"))),
+            )
+            .set_visibility(Visibility::Public)
+            .build(),
+        )
+        .add_field(
+            Field::new(Identifier::new("next_token"), ValueType::string())
+                .set_visibility(Visibility::Public)
+                .optional()
+                .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("totals"),
+                ValueType::map_of(ValueType::string(), ValueType::u64()),
+            )
+            .set_visibility(Visibility::Public)
+            .set_documentation("Totals by category.")
+            .optional()
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("tags"),
+                ValueType::set_of(ValueType::string()),
+            )
+            .set_visibility(Visibility::Public)
+            .build(),
+        )
+        .add_field(Field::new(Identifier::new("cursor"), ValueType::i64()).build())
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        GoWriter::default().write_structured_type(writer, &structure)
+    });
+    assert_eq!(
+        result,
+        r#"// Page is a page of results.
+type Page[T Entity] struct {
+    Paged
+
+    Items     []T     `json:"items"`
+    NextToken *string `json:"next_token,omitempty"`
+
+    // Totals by category.
+    Totals map[string]uint64   `json:"totals,omitempty"`
+    Tags   map[string]struct{} `json:"tags"`
+    cursor int64
+}
+"#
+    );
+}
+
+#[test]
+fn test_interface() {
+    let interface = StructuredType::interface(Identifier::new("Store"))
+        .set_visibility(Visibility::Public)
+        .add_extend(ValueType::Reference(Identifier::new("io.Closer")))
+        .add_method(
+            FunctionDecl::with_visibility(Visibility::Public, Identifier::new("find_user"))
+                .set_documentation("FindUser returns the user with the given id.")
+                .add_parameter(Parameter::new(Identifier::new("user_id"), ValueType::u64()))
+                .add_parameter(
+                    Parameter::new(Identifier::new("cached"), ValueType::boolean())
+                        .optional()
+                        .build(),
+                )
+                .set_value_type(ValueType::Reference(Identifier::new("User")))
+                .add_throws(Parameter::new(
+                    Identifier::new("e"),
+                    ValueType::Reference(Identifier::new("NotFound")),
+                ))
+                .build(),
+        )
+        .add_method(FunctionDecl::new(Identifier::new("reset")).build())
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        GoWriter::default().write_structured_type(writer, &interface)
+    });
+    assert_eq!(
+        result,
+        r#"type Store interface {
+    io.Closer
+    // FindUser returns the user with the given id.
+    FindUser(userId uint64, cached *bool) (User, error)
+    reset()
+}
+"#
+    );
+}
+
+#[test]
+fn test_enumerations() {
+    let iota = Enumeration::new(Identifier::new("Color"))
+        .set_visibility(Visibility::Public)
+        .add_named_variant(Identifier::new("red"))
+        .add_variant(
+            EnumerationVariant::new(Identifier::new("dark_green"))
+                .set_documentation("A darker green.")
+                .build(),
+        )
+        .build();
+    let strings = Enumeration::new(Identifier::new("level"))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("quiet"),
+            Value::from("q"),
+        ))
+        .add_named_variant(Identifier::new("LOUD"))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let go_writer = GoWriter::default();
+        go_writer.write_enumeration(writer, &iota)?;
+        go_writer.write_enumeration(writer, &strings)
+    });
+    assert_eq!(
+        result,
+        r#"type Color int
+
+const (
+    ColorRed Color = iota
+
+    // A darker green.
+    ColorDarkGreen
+)
+type level string
+
+const (
+    levelQuiet level = "q"
+    levelLOUD  level = "LOUD"
+)
+"#
+    );
+}
+
+#[test]
+fn test_enumeration_implicit_values() {
+    let status = Enumeration::new(Identifier::new("status"))
+        .add_named_variant(Identifier::new("unknown"))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("active"),
+            Value::from(10u32),
+        ))
+        .add_named_variant(Identifier::new("disabled"))
+        .build();
+    let mixed = Enumeration::new(Identifier::new("mixed"))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("a"),
+            Value::from("a"),
+        ))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("b"),
+            Value::from(1u32),
+        ))
+        .build();
+
+    assert_eq!(
+        common::write_to_string(|writer| GoWriter::default().write_enumeration(writer, &status)),
+        r#"type status int
+
+const (
+    statusUnknown  status = 0
+    statusActive   status = 10
+    statusDisabled status = 11
+)
+"#
+    );
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(GoWriter::default()
+        .write_enumeration(&mut writer, &mixed)
+        .is_err());
+}
+
+#[test]
+fn test_go_formatting_policy() {
+    let module = Module::new(Identifier::new("address"))
+        .add_structure(
+            StructuredType::structure(Identifier::new("Country"))
+                .add_field(Field::new(Identifier::new("code"), ValueType::string()).build())
+                .build(),
+        )
+        .build();
+    assert_eq!(
+        module_to_string_with_policy(&GoWriter::default(), &module, go_formatting_policy())
+            .unwrap(),
+        "package address\n\ntype country struct {\n\tcode string\n}\n"
+    );
+}
+
+#[test]
+fn test_union_unsupported() {
+    let union = StructuredType::union(Identifier::new("Contact"))
+        .add_field(Field::new(Identifier::new("email"), ValueType::string()).build())
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(GoWriter::default()
+        .write_structured_type(&mut writer, &union)
+        .is_err());
+}
+
+#[test]
+fn test_module_items() {
+    let module = Module::new(Identifier::new("users"))
+        .set_documentation("Package users holds user accounts.")
+        .add_import(
+            Import::new(Namespace::new(vec![
+                Identifier::new("common"),
+                Identifier::new("address"),
+            ]))
+            .item(Identifier::new("Country"))
+            .build(),
+        )
+        .add_import(Import::new(Namespace::new(vec![Identifier::new("util")])))
+        .add_alias(
+            TypeAlias::new(Identifier::new("user_id"), ValueType::string())
+                .set_visibility(Visibility::Public)
+                .set_documentation("UserId identifies a user.")
+                .build(),
+        )
+        .add_constant(
+            NamedValue::new(
+                Identifier::new("MAX_USERS"),
+                ValueType::u32(),
+                Value::from(100u32),
+            )
+            .set_visibility(Visibility::Public)
+            .set_documentation("MaxUsers is the most users allowed.")
+            .build(),
+        )
+        .add_variable(NamedValue::new(
+            Identifier::new("default_roles"),
+            ValueType::array_of(ValueType::string()),
+            Value::Values(vec![Value::from("reader"), Value::from("writer")]),
+        ))
+        .add_sub_module(Module::new(Identifier::new("roles")).build())
+        .build();
+
+    let mut go_writer = GoWriter::default();
+    let _ = go_writer.set_module_path("example.com/app/");
+    assert_eq!(
+        module_to_string(&go_writer, &module).unwrap(),
+        r#"// Package users holds user accounts.
+package users
+
+import (
+    "example.com/app/common/address"
+    "example.com/app/util"
+)
+
+// UserId identifies a user.
+type UserId = string
+
+// MaxUsers is the most users allowed.
+const MaxUsers uint32 = 100
+
+var defaultRoles []string = []string{"reader", "writer"}
+"#
+    );
+}
+
+#[test]
+fn test_layout() {
+    let namespace = Namespace::new(vec![Identifier::new("api"), Identifier::new("users")]);
+    assert_eq!(
+        GoLayout::default().module_file_path(&namespace),
+        PathBuf::from("api/users/users.go")
+    );
+}
@@ -0,0 +1,232 @@
+use code_writer::language::json_schema::{JsonSchemaLayout, JsonSchemaWriter};
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    Builder, Enumeration, EnumerationVariant, Field, FunctionDecl, HasDocumentation, Identifier,
+    Import, IsOptional, Module, StructuredType, TypeAlias, Value, ValueType,
+};
+use code_writer::project::{FileSet, MemoryFileSet, ProjectWriter};
+use code_writer::writer::module_to_string;
+use std::path::Path;
+
+#[test]
+fn test_structure_and_references() {
+    let module = Module::new(Identifier::new("users"))
+        .set_documentation("User accounts.")
+        .add_import(
+            Import::new(Namespace::new(vec![Identifier::new("address")]))
+                .item_with_alias(Identifier::new("Address"), Identifier::new("PostalAddress"))
+                .build(),
+        )
+        .add_alias(TypeAlias::new(Identifier::new("UserId"), ValueType::u64()))
+        .add_structure(
+            StructuredType::structure(Identifier::new("User"))
+                .set_documentation("A user.")
+                .add_field(
+                    Field::new(
+                        Identifier::new("id"),
+                        ValueType::Reference(Identifier::new("UserId")),
+                    )
+                    .build(),
+                )
+                .add_field(
+                    Field::new(
+                        Identifier::new("home"),
+                        ValueType::optional(ValueType::Reference(Identifier::new("PostalAddress"))),
+                    )
+                    .set_documentation("Where the user lives.")
+                    .build(),
+                )
+                .add_field(
+                    Field::new(
+                        Identifier::new("labels"),
+                        ValueType::map_of(
+                            ValueType::string(),
+                            ValueType::set_of(ValueType::string()),
+                        ),
+                    )
+                    .optional()
+                    .build(),
+                )
+                .add_field(
+                    Field::with_value(
+                        Identifier::new("active"),
+                        ValueType::boolean(),
+                        Value::from(true),
+                    )
+                    .build(),
+                )
+                .build(),
+        )
+        .build();
+
+    assert_eq!(
+        module_to_string(&JsonSchemaWriter::default(), &module).unwrap(),
+        r##"{
+    "$schema": "https://json-schema.org/draft/2020-12/schema",
+    "$id": "users.schema.json",
+    "title": "users",
+    "description": "User accounts.",
+    "$defs": {
+        "UserId": {
+            "type": "integer",
+            "minimum": 0
+        },
+        "User": {
+            "description": "A user.",
+            "type": "object",
+            "properties": {
+                "id": {
+                    "$ref": "#/$defs/UserId"
+                },
+                "home": {
+                    "description": "Where the user lives.",
+                    "$ref": "address.schema.json#/$defs/Address"
+                },
+                "labels": {
+                    "type": "object",
+                    "additionalProperties": {
+                        "type": "array",
+                        "items": {
+                            "type": "string"
+                        },
+                        "uniqueItems": true
+                    }
+                },
+                "active": {
+                    "type": "boolean",
+                    "default": true
+                }
+            },
+            "required": ["id", "active"]
+        }
+    }
+}
+"##
+    );
+}
+
+#[test]
+fn test_enumerations() {
+    let module = Module::new(Identifier::new("shapes"))
+        .add_enumeration(
+            Enumeration::new(Identifier::new("Color"))
+                .add_named_variant(Identifier::new("red"))
+                .add_named_variant(Identifier::new("green"))
+                .build(),
+        )
+        .add_enumeration(
+            Enumeration::new(Identifier::new("Level"))
+                .set_documentation("How loud.")
+                .add_variant(
+                    EnumerationVariant::with_value(Identifier::new("Quiet"), Value::from(1))
+                        .set_documentation("Barely \"audible\".")
+                        .build(),
+                )
+                .add_variant(EnumerationVariant::with_value(
+                    Identifier::new("Loud"),
+                    Value::from(11),
+                ))
+                .build(),
+        )
+        .add_structure(
+            StructuredType::union(Identifier::new("Shape"))
+                .add_field(Field::new(Identifier::new("circle"), ValueType::f64()).build())
+                .add_field(
+                    Field::new(
+                        Identifier::new("polygon"),
+                        ValueType::tuple_of(vec![ValueType::i32(), ValueType::i32()]),
+                    )
+                    .build(),
+                )
+                .build(),
+        )
+        .build();
+
+    assert_eq!(
+        module_to_string(&JsonSchemaWriter::default(), &module).unwrap(),
+        r##"{
+    "$schema": "https://json-schema.org/draft/2020-12/schema",
+    "$id": "shapes.schema.json",
+    "title": "shapes",
+    "$defs": {
+        "Color": {
+            "enum": ["red", "green"]
+        },
+        "Level": {
+            "description": "How loud.",
+            "oneOf": [
+                {
+                    "const": 1,
+                    "description": "Barely \"audible\"."
+                },
+                {
+                    "const": 11
+                }
+            ]
+        },
+        "Shape": {
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        "circle": {
+                            "type": "number"
+                        }
+                    },
+                    "required": ["circle"]
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "polygon": {
+                            "type": "array",
+                            "prefixItems": [
+                                {
+                                    "type": "integer"
+                                },
+                                {
+                                    "type": "integer"
+                                }
+                            ],
+                            "items": false
+                        }
+                    },
+                    "required": ["polygon"]
+                }
+            ]
+        }
+    }
+}
+"##
+    );
+}
+
+#[test]
+fn test_function_unsupported() {
+    let module = Module::new(Identifier::new("users"))
+        .add_function(FunctionDecl::new(Identifier::new("lookup")).build())
+        .build();
+    assert!(module_to_string(&JsonSchemaWriter::default(), &module).is_err());
+}
+
+#[test]
+fn test_project_without_fingerprint() {
+    let module = Module::new(Identifier::new("api"))
+        .add_sub_module(Module::new(Identifier::new("users")).build())
+        .build();
+    let mut project_writer =
+        ProjectWriter::new(JsonSchemaLayout::default(), JsonSchemaWriter::default());
+    let _ = project_writer.set_fingerprints(true);
+
+    let mut files = MemoryFileSet::default();
+    let _ = project_writer.write(&module, &mut files).unwrap();
+    let content = files
+        .read_file(Path::new("users.schema.json"))
+        .unwrap()
+        .unwrap();
+    assert!(content.starts_with('{'));
+    assert!(files
+        .get(Path::new("api.schema.json"))
+        .unwrap()
+        .ends_with("}\n"));
+}
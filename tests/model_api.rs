@@ -1,7 +1,8 @@
 use code_writer::model::modules::Module;
 use code_writer::model::{
-    Builder, Field, HasDocumentation, HasProperties, HasVisibility, Identifier, Import, IsOptional,
-    Property, StructuredType, Value, ValueType,
+    Builder, Enumeration, EnumerationVariant, Field, HasDocumentation, HasOptionalType,
+    HasProperties, HasVisibility, Identifier, Import, IsOptional, Property, StructuredType, Value,
+    ValueType, VariantPayload,
 };
 
 pub mod common;
@@ -83,6 +84,13 @@ fn make_a_model() {
                 )
                 .build(),
         )
+        .add_enumeration(
+            Enumeration::new(Identifier::new("AddressKind"))
+                .make_public()
+                .add_named_variant(Identifier::new("Residential"))
+                .add_named_variant(Identifier::new("Commercial"))
+                .build(),
+        )
         .add_sub_module(
             Module::new(Identifier::new("countries"))
                 .make_public()
@@ -92,3 +100,20 @@ fn make_a_model() {
 
     common::print_module(&module).unwrap();
 }
+
+#[test]
+fn variant_value_type_is_single_tuple_payload() {
+    let mut variant = EnumerationVariant::new(Identifier::new("Circle"));
+    let _ = variant.set_value_type(ValueType::f64());
+    assert!(matches!(variant.payload(), Some(VariantPayload::Tuple(types)) if types.len() == 1));
+    assert!(variant.value_type().is_some());
+
+    let _ = variant.set_payload(VariantPayload::Tuple(vec![
+        ValueType::f64(),
+        ValueType::f64(),
+    ]));
+    assert!(variant.value_type().is_none());
+
+    let _ = variant.set_value_type(ValueType::u32()).unset_value_type();
+    assert!(variant.payload().is_none());
+}
@@ -0,0 +1,617 @@
+use code_writer::error::{ErrorKind, Result};
+use code_writer::language::protobuf::{ProtobufLayout, ProtobufWriter};
+use code_writer::language::rust::{
+    namespace_to_file_path, rust_module_writer, RustLayout, RustWriter,
+};
+use code_writer::language::thrift::{ThriftLayout, ThriftWriter};
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    Builder, Comment, Field, HasFieldId, HasVisibility, Identifier, Implementation, Import, Module,
+    ProtectedRegion, StructuredType, ValueType,
+};
+use code_writer::project::{
+    fingerprint, has_valid_fingerprint, protected_regions, relative_path, unified_diff,
+    DirectoryFileSet, FileSet, FileStatus, MemoryFileSet, ProjectWriter, FINGERPRINT_MARKER,
+};
+use code_writer::writer::{
+    write_modules, write_modules_to, write_modules_to_with_policy, FormattingPolicy,
+};
+use std::path::{Path, PathBuf};
+
+fn make_project() -> Module {
+    Module::new(Identifier::new("address"))
+        .add_sub_module(
+            Module::new(Identifier::new("countries"))
+                .make_public()
+                .add_structure(
+                    StructuredType::structure(Identifier::new("Country"))
+                        .add_field(
+                            Field::new(Identifier::new("code"), ValueType::string())
+                                .set_field_id(1)
+                                .build(),
+                        )
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn test_rust_named_files() {
+    let mut files = MemoryFileSet::default();
+    ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default())
+        .write(&make_project(), &mut files)
+        .unwrap();
+
+    assert_eq!(
+        files.files().keys().collect::<Vec<&PathBuf>>(),
+        vec![
+            &PathBuf::from("address/countries.rs"),
+            &PathBuf::from("address.rs")
+        ]
+    );
+    assert_eq!(
+        files.get(Path::new("address.rs")).unwrap(),
+        "pub mod countries;\n"
+    );
+    assert_eq!(
+        files.get(Path::new("address/countries.rs")).unwrap(),
+        "struct Country {\n    code: String,\n}\n"
+    );
+}
+
+#[test]
+fn test_rust_mod_files() {
+    let files = ProjectWriter::new(RustLayout::ModFiles, RustWriter::default())
+        .render(&make_project())
+        .unwrap();
+
+    assert_eq!(
+        files.keys().collect::<Vec<&PathBuf>>(),
+        vec![
+            &PathBuf::from("address/countries/mod.rs"),
+            &PathBuf::from("address/mod.rs")
+        ]
+    );
+}
+
+#[test]
+fn test_thrift_includes() {
+    let files = ProjectWriter::new(ThriftLayout::default(), ThriftWriter::default())
+        .render(&make_project())
+        .unwrap();
+
+    assert_eq!(
+        files.get(Path::new("address.thrift")).unwrap(),
+        "include \"address/countries.thrift\"\n"
+    );
+    assert_eq!(
+        files.get(Path::new("address/countries.thrift")).unwrap(),
+        "struct Country {\n    1: required string code,\n}\n"
+    );
+}
+
+#[test]
+fn test_thrift_includes_between_directories() {
+    let module = Module::new(Identifier::new("api"))
+        .add_sub_module(
+            Module::new(Identifier::new("users"))
+                .add_import(
+                    Import::new(Namespace::new(vec![
+                        Identifier::new("api"),
+                        Identifier::new("common"),
+                        Identifier::new("types"),
+                    ]))
+                    .item(Identifier::new("UserId"))
+                    .build(),
+                )
+                .build(),
+        )
+        .add_sub_module(
+            Module::new(Identifier::new("common"))
+                .add_sub_module(Module::new(Identifier::new("types")))
+                .build(),
+        )
+        .build();
+    let files = ProjectWriter::new(ThriftLayout::default(), ThriftWriter::default())
+        .render(&module)
+        .unwrap();
+
+    assert_eq!(
+        files.get(Path::new("api/users.thrift")).unwrap(),
+        "include \"common/types.thrift\"\n"
+    );
+    assert_eq!(
+        files.get(Path::new("api/common.thrift")).unwrap(),
+        "include \"common/types.thrift\"\n"
+    );
+    assert!(files.contains_key(Path::new("api/common/types.thrift")));
+}
+
+#[test]
+fn test_protobuf_packages_and_imports() {
+    let module = Module::new(Identifier::new("api"))
+        .add_sub_module(
+            Module::new(Identifier::new("v1"))
+                .add_import(
+                    Import::new(Namespace::new(vec![
+                        Identifier::new("api"),
+                        Identifier::new("common"),
+                    ]))
+                    .item(Identifier::new("UserId"))
+                    .build(),
+                )
+                .build(),
+        )
+        .add_sub_module(Module::new(Identifier::new("common")))
+        .build();
+    let files = ProjectWriter::new(ProtobufLayout::default(), ProtobufWriter::default())
+        .render(&module)
+        .unwrap();
+
+    assert_eq!(
+        files.get(Path::new("api.proto")).unwrap(),
+        "syntax = \"proto3\";\n\npackage api;\n\nimport \"api/v1.proto\";\n\nimport \"api/common.proto\";\n"
+    );
+    assert_eq!(
+        files.get(Path::new("api/v1.proto")).unwrap(),
+        "syntax = \"proto3\";\n\npackage api.v1;\n\nimport \"api/common.proto\";\n"
+    );
+    assert!(files.contains_key(Path::new("api/common.proto")));
+}
+
+#[test]
+fn test_relative_path() {
+    assert_eq!(
+        relative_path(Path::new("api/common"), Path::new("api/users.thrift")),
+        PathBuf::from("../users.thrift")
+    );
+    assert_eq!(
+        relative_path(Path::new(""), Path::new("api/users.thrift")),
+        PathBuf::from("api/users.thrift")
+    );
+}
+
+#[test]
+fn test_thrift_duplicate_file() {
+    let module = Module::new(Identifier::new("api"))
+        .add_sub_module(Module::new(Identifier::new("common")))
+        .add_sub_module(Module::new(Identifier::new("common")))
+        .build();
+    let result =
+        ProjectWriter::new(ThriftLayout::default(), ThriftWriter::default()).render(&module);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::DuplicateModulePath(_)
+    ));
+}
+
+#[test]
+fn test_directory_file_set() {
+    let root = std::env::temp_dir().join(format!("code_writer-project-{}", std::process::id()));
+    let mut files = DirectoryFileSet::new(&root);
+    ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default())
+        .write(&make_project(), &mut files)
+        .unwrap();
+
+    assert!(root.join("address/countries.rs").is_file());
+    assert_eq!(
+        files.read_file(Path::new("address.rs")).unwrap(),
+        Some("pub mod countries;\n".to_string())
+    );
+    assert_eq!(files.read_file(Path::new("missing.rs")).unwrap(), None);
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_directory_stale_files() {
+    let root = std::env::temp_dir().join(format!("code_writer-stale-{}", std::process::id()));
+    let outside = root.with_extension("outside");
+    let mut project_writer = ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default());
+    let _ = project_writer.set_fingerprints(true).set_remove_stale(true);
+    let mut files = DirectoryFileSet::new(&root);
+    let _ = project_writer.write(&make_project(), &mut files).unwrap();
+
+    let generated = std::fs::read_to_string(root.join("address/countries.rs")).unwrap();
+    std::fs::write(root.join("notes.txt"), &generated).unwrap();
+    std::fs::create_dir_all(root.join(".cache")).unwrap();
+    std::fs::write(root.join(".cache/old.rs"), &generated).unwrap();
+    std::fs::write(root.join("binary.rs"), [0xff_u8, 0xfe, 0x00]).unwrap();
+    std::fs::create_dir_all(&outside).unwrap();
+    std::fs::write(outside.join("linked.rs"), &generated).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+    let mut removed = make_project();
+    removed.set_content(vec![]);
+    let report = project_writer.write(&removed, &mut files).unwrap();
+    assert_eq!(
+        report.status(Path::new("address/countries.rs")),
+        Some(FileStatus::Deleted)
+    );
+    assert_eq!(report.files().len(), 2);
+    assert!(!root.join("address/countries.rs").exists());
+    assert!(root.join("notes.txt").is_file());
+    assert!(root.join(".cache/old.rs").is_file());
+    assert!(root.join("binary.rs").is_file());
+    assert!(outside.join("linked.rs").is_file());
+    std::fs::remove_dir_all(root).unwrap();
+    std::fs::remove_dir_all(outside).unwrap();
+}
+
+#[test]
+fn test_write_modules() {
+    let root = std::env::temp_dir().join(format!("code_writer-modules-{}", std::process::id()));
+    write_modules(
+        make_project(),
+        &root,
+        &namespace_to_file_path,
+        &rust_module_writer,
+    )
+    .unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(root.join("address.rs")).unwrap(),
+        "pub mod countries;\n"
+    );
+    assert!(root.join("address/countries.rs").is_file());
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_write_modules_to_buffers() {
+    let sinks = write_modules_to(
+        &make_project(),
+        |_| Ok(Vec::new()),
+        &ThriftWriter::default(),
+    )
+    .unwrap();
+
+    let files: Vec<(String, String)> = sinks
+        .into_iter()
+        .map(|(namespace, buffer)| (namespace.join("."), String::from_utf8(buffer).unwrap()))
+        .collect();
+    assert_eq!(
+        files,
+        vec![
+            (
+                "address".to_string(),
+                "include \"address/countries.thrift\"\n".to_string()
+            ),
+            (
+                "address.countries".to_string(),
+                "struct Country {\n    1: required string code,\n}\n".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_write_modules_to_with_policy() {
+    let mut policy = FormattingPolicy::default();
+    let _ = policy.set_indent("\t");
+    let sinks = write_modules_to_with_policy(
+        &make_project(),
+        |_| Ok(Vec::new()),
+        &ThriftWriter::default(),
+        policy,
+    )
+    .unwrap();
+
+    let (namespace, buffer) = &sinks[1];
+    assert_eq!(namespace.join("."), "address.countries");
+    assert_eq!(
+        String::from_utf8(buffer.clone()).unwrap(),
+        "struct Country {\n\t1: required string code,\n}\n"
+    );
+}
+
+#[derive(Default)]
+struct CountingFileSet {
+    inner: MemoryFileSet,
+    writes: usize,
+}
+
+impl FileSet for CountingFileSet {
+    fn read_file(&self, path: &Path) -> Result<Option<String>> {
+        self.inner.read_file(path)
+    }
+
+    fn write_file(&mut self, path: &Path, content: &str) -> Result<()> {
+        self.writes += 1;
+        self.inner.write_file(path, content)
+    }
+}
+
+#[test]
+fn test_write_if_changed() {
+    let mut project_writer = ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default());
+    let _ = project_writer.set_write_if_changed(true);
+    let mut files = CountingFileSet::default();
+
+    let report = project_writer.write(&make_project(), &mut files).unwrap();
+    assert!(report.files().values().all(|s| *s == FileStatus::Created));
+    assert_eq!(files.writes, 2);
+
+    let report = project_writer.write(&make_project(), &mut files).unwrap();
+    assert!(report.files().values().all(|s| *s == FileStatus::Unchanged));
+    assert_eq!(files.writes, 2);
+
+    let mut changed = make_project();
+    let _ = changed.add_comment(Comment::line("changed"));
+    let report = project_writer.write(&changed, &mut files).unwrap();
+    assert_eq!(
+        report.status(Path::new("address.rs")),
+        Some(FileStatus::Modified)
+    );
+    assert_eq!(
+        report.status(Path::new("address/countries.rs")),
+        Some(FileStatus::Unchanged)
+    );
+    assert_eq!(files.writes, 3);
+}
+
+#[test]
+fn test_fingerprints() {
+    let mut project_writer = ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default());
+    let _ = project_writer.set_fingerprints(true);
+    let mut files = MemoryFileSet::default();
+    let _ = project_writer.write(&make_project(), &mut files).unwrap();
+
+    let content = files.get(Path::new("address.rs")).unwrap().clone();
+    assert_eq!(
+        content,
+        format!(
+            "// {} {}\npub mod countries;\n",
+            FINGERPRINT_MARKER,
+            fingerprint("pub mod countries;\n")
+        )
+    );
+    assert!(has_valid_fingerprint(&content));
+
+    let edited = format!("{}// hand-written\n", content);
+    assert!(!has_valid_fingerprint(&edited));
+    files.write_file(Path::new("address.rs"), &edited).unwrap();
+
+    let result = project_writer.write(&make_project(), &mut files);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::ModifiedGeneratedFile(_)
+    ));
+    assert_eq!(files.get(Path::new("address.rs")).unwrap(), &edited);
+
+    let report = project_writer.dry_run(&make_project(), &files).unwrap();
+    assert!(report.is_blocked(Path::new("address.rs")));
+    assert_eq!(report.blocked_files().len(), 1);
+    assert_eq!(
+        report.status(Path::new("address.rs")),
+        Some(FileStatus::Modified)
+    );
+    assert!(report.diff(Path::new("address.rs")).is_some());
+
+    let report = project_writer
+        .set_force(true)
+        .write(&make_project(), &mut files)
+        .unwrap();
+    assert_eq!(
+        report.status(Path::new("address.rs")),
+        Some(FileStatus::Modified)
+    );
+    assert_eq!(files.get(Path::new("address.rs")).unwrap(), &content);
+}
+
+fn make_project_with_regions(region_name: &str) -> Module {
+    Module::new(Identifier::new("shapes"))
+        .add_region(ProtectedRegion::new(Identifier::new("imports")))
+        .add_implementation(
+            Implementation::new(ValueType::Reference(Identifier::new("Circle")))
+                .add_region(ProtectedRegion::new(Identifier::new(region_name)))
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn test_protected_regions() {
+    let mut project_writer = ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default());
+    let _ = project_writer.set_fingerprints(true);
+    let mut files = MemoryFileSet::default();
+    let _ = project_writer
+        .write(&make_project_with_regions("methods"), &mut files)
+        .unwrap();
+
+    let path = Path::new("shapes.rs");
+    let generated = files.get(path).unwrap().clone();
+    assert!(generated.ends_with(
+        r#"// @protected-region begin imports
+// @protected-region end imports
+
+impl Circle {
+    // @protected-region begin methods
+    // @protected-region end methods
+}
+"#
+    ));
+
+    let edited = generated
+        .replace(
+            "// @protected-region end imports",
+            "use std::f64::consts::PI;\n// @protected-region end imports",
+        )
+        .replace(
+            "    // @protected-region end methods",
+            "    pub fn area(&self) -> f64 {\n        PI * self.r * self.r\n    }\n    // @protected-region end methods",
+        );
+    assert!(has_valid_fingerprint(&edited));
+    files.write_file(path, &edited).unwrap();
+
+    let report = project_writer
+        .write(&make_project_with_regions("methods"), &mut files)
+        .unwrap();
+    assert_eq!(report.status(path), Some(FileStatus::Unchanged));
+    assert!(!report.has_orphaned_regions());
+    assert_eq!(files.get(path).unwrap(), &edited);
+
+    let report = project_writer
+        .write(&make_project_with_regions("members"), &mut files)
+        .unwrap();
+    assert_eq!(report.status(path), Some(FileStatus::Modified));
+    assert_eq!(report.orphaned_regions().len(), 1);
+    let orphan = &report.orphaned_regions()[0];
+    assert_eq!(orphan.name(), "methods");
+    assert!(orphan.content().contains("pub fn area"));
+    assert!(files
+        .get(path)
+        .unwrap()
+        .contains("use std::f64::consts::PI;\n"));
+}
+
+#[test]
+fn test_duplicate_region_names() {
+    let content = "// @protected-region begin body\na\n// @protected-region end body\n\
+                   // @protected-region begin body\nb\n// @protected-region end body\n";
+    assert!(matches!(
+        protected_regions(content).unwrap_err().kind(),
+        ErrorKind::DuplicateRegionName(name) if name == "body"
+    ));
+
+    let result = ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default())
+        .render(&make_project_with_regions("imports"));
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::DuplicateRegionName(name) if name == "imports"
+    ));
+}
+
+#[test]
+fn test_unified_diff() {
+    assert_eq!(unified_diff("a/x", "b/x", "one\ntwo\n", "one\ntwo\n"), "");
+    assert_eq!(
+        unified_diff(
+            "a/x",
+            "b/x",
+            "1\n2\n3\n4\n5\n6\n7\n8\n9\n",
+            "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n"
+        ),
+        r#"--- a/x
++++ b/x
+@@ -2,8 +2,9 @@
+ 2
+ 3
+ 4
+-5
++five
+ 6
+ 7
+ 8
+ 9
++10
+"#
+    );
+    assert_eq!(
+        unified_diff("/dev/null", "b/x", "", "one\n"),
+        "--- /dev/null\n+++ b/x\n@@ -0,0 +1 @@\n+one\n"
+    );
+}
+
+#[test]
+fn test_unified_diff_final_newline() {
+    assert_eq!(
+        unified_diff("a/x", "b/x", "one\ntwo", "one\ntwo\n"),
+        "--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n one\n-two\n\\ No newline at end of file\n+two\n"
+    );
+    assert_eq!(
+        unified_diff("a/x", "b/x", "one\n", "two"),
+        "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-one\n+two\n\\ No newline at end of file\n"
+    );
+}
+
+#[test]
+fn test_unified_diff_rewritten_file() {
+    let old: String = (0..5000).map(|i| format!("old {}\n", i)).collect();
+    let new: String = (0..5000).map(|i| format!("new {}\n", i)).collect();
+    let diff = unified_diff("a/x", "b/x", &old, &new);
+    assert!(diff.starts_with("--- a/x\n+++ b/x\n@@ -1,5000 +1,5000 @@\n-old 0\n"));
+    assert_eq!(
+        diff.lines()
+            .filter(|line| line.starts_with("-old "))
+            .count(),
+        5000
+    );
+    assert_eq!(
+        diff.lines()
+            .filter(|line| line.starts_with("+new "))
+            .count(),
+        5000
+    );
+}
+
+#[test]
+fn test_dry_run() {
+    let mut project_writer = ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default());
+    let _ = project_writer.set_fingerprints(true).set_remove_stale(true);
+    let mut files = MemoryFileSet::default();
+
+    let report = project_writer.dry_run(&make_project(), &files).unwrap();
+    assert!(report.files().values().all(|s| *s == FileStatus::Created));
+    assert!(files.files().is_empty());
+    assert!(report
+        .diff(Path::new("address.rs"))
+        .unwrap()
+        .starts_with("--- /dev/null\n+++ b/address.rs\n@@ -0,0 +1,2 @@\n"));
+
+    let _ = project_writer.write(&make_project(), &mut files).unwrap();
+    let before = files.clone();
+
+    let mut changed = make_project();
+    let _ = changed.add_comment(Comment::line("changed"));
+    let _ = changed.add_sub_module(Module::new(Identifier::new("regions")).build());
+    let report = project_writer.dry_run(&changed, &files).unwrap();
+    assert_eq!(files, before);
+    assert_eq!(
+        report.status(Path::new("address.rs")),
+        Some(FileStatus::Modified)
+    );
+    assert_eq!(
+        report.status(Path::new("address/countries.rs")),
+        Some(FileStatus::Unchanged)
+    );
+    assert_eq!(
+        report.status(Path::new("address/regions.rs")),
+        Some(FileStatus::Created)
+    );
+    let diff = report.diff(Path::new("address.rs")).unwrap();
+    assert!(diff.starts_with("--- a/address.rs\n+++ b/address.rs\n"));
+    assert!(diff.contains("\n+// changed\n"));
+    assert!(report.diff(Path::new("address/countries.rs")).is_none());
+
+    let mut removed = make_project();
+    removed.set_content(vec![]);
+    let report = project_writer.dry_run(&removed, &files).unwrap();
+    assert_eq!(
+        report.status(Path::new("address/countries.rs")),
+        Some(FileStatus::Deleted)
+    );
+    assert!(report
+        .diff(Path::new("address/countries.rs"))
+        .unwrap()
+        .starts_with("--- a/address/countries.rs\n+++ /dev/null\n"));
+
+    let _ = project_writer.write(&removed, &mut files).unwrap();
+    assert!(files.get(Path::new("address/countries.rs")).is_none());
+}
+
+#[test]
+fn test_stale_files_kept_by_default() {
+    let mut project_writer = ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default());
+    let _ = project_writer.set_fingerprints(true);
+    let mut files = MemoryFileSet::default();
+    let _ = project_writer.write(&make_project(), &mut files).unwrap();
+
+    let mut removed = make_project();
+    removed.set_content(vec![]);
+    let report = project_writer.write(&removed, &mut files).unwrap();
+    assert_eq!(report.status(Path::new("address/countries.rs")), None);
+    assert!(files.get(Path::new("address/countries.rs")).is_some());
+}
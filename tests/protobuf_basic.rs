@@ -0,0 +1,263 @@
+use code_writer::error::ErrorKind;
+use code_writer::language::protobuf::ProtobufWriter;
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    Builder, Enumeration, EnumerationVariant, Field, FunctionDecl, HasDocumentation, HasFieldId,
+    HasOptionalType, Identifier, IsOptional, Module, Parameter, StructuredType, Value, ValueType,
+};
+use code_writer::writer::{module_to_string, CodeWriter, ModuleWriter};
+
+pub mod common;
+
+#[test]
+fn test_package() {
+    let module = Module::new(Identifier::new("address"))
+        .set_documentation("Postal addresses.")
+        .add_sub_module(Module::new(Identifier::new("countries")).build())
+        .build();
+    let mut protobuf_writer = ProtobufWriter::default();
+    let _ = protobuf_writer.set_package_prefix(Namespace::new(vec![
+        Identifier::new("com"),
+        Identifier::new("example"),
+    ]));
+
+    assert_eq!(
+        module_to_string(&protobuf_writer, &module).unwrap(),
+        r#"/**
+Postal addresses.
+*/
+syntax = "proto3";
+
+package com.example.address;
+
+import "countries.proto";
+"#
+    );
+}
+
+#[test]
+fn test_message() {
+    let structure = StructuredType::structure(Identifier::new("User"))
+        .set_documentation("A user.")
+        .add_field(
+            Field::new(Identifier::new("id"), ValueType::i64())
+                .set_field_id(1)
+                .build(),
+        )
+        .add_field(
+            Field::new(Identifier::new("email"), ValueType::string())
+                .optional()
+                .set_field_id(2)
+                .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("nickname"),
+                ValueType::optional(ValueType::string()),
+            )
+            .set_field_id(3)
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("roles"),
+                ValueType::array_of(ValueType::Reference(Identifier::new("Role"))),
+            )
+            .set_field_id(4)
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("attributes"),
+                ValueType::map_of(ValueType::string(), ValueType::u32()),
+            )
+            .set_field_id(5)
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("avatar"),
+                ValueType::array_of(ValueType::u8()),
+            )
+            .set_field_id(6)
+            .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        ProtobufWriter::default().write_structured_type(writer, &structure)
+    });
+    assert_eq!(
+        result,
+        r#"/**
+A user.
+*/
+message User {
+    int64 id = 1;
+    optional string email = 2;
+    optional string nickname = 3;
+    repeated Role roles = 4;
+    map<string, uint32> attributes = 5;
+    bytes avatar = 6;
+}
+"#
+    );
+}
+
+#[test]
+fn test_union_as_oneof() {
+    let union = StructuredType::union(Identifier::new("Contact"))
+        .add_field(
+            Field::new(Identifier::new("email"), ValueType::string())
+                .set_field_id(1)
+                .build(),
+        )
+        .add_field(
+            Field::new(Identifier::new("phone"), ValueType::string())
+                .set_field_id(2)
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        ProtobufWriter::default().write_structured_type(writer, &union)
+    });
+    assert_eq!(
+        result,
+        r#"message Contact {
+    oneof value {
+        string email = 1;
+        string phone = 2;
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn test_field_ids() {
+    let structure = StructuredType::structure(Identifier::new("User"))
+        .add_field(Field::new(Identifier::new("id"), ValueType::i64()))
+        .build();
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    let result = ProtobufWriter::default().write_structured_type(&mut writer, &structure);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::MissingFieldId(_, _)
+    ));
+
+    let structure = StructuredType::structure(Identifier::new("User"))
+        .add_field(
+            Field::new(Identifier::new("id"), ValueType::i64())
+                .set_field_id(19_500)
+                .build(),
+        )
+        .build();
+    let result =
+        ProtobufWriter::with_positional_field_ids().write_structured_type(&mut writer, &structure);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::InvalidFieldId(_, 19_500)
+    ));
+}
+
+#[test]
+fn test_enumeration_zero_value() {
+    let enumeration = Enumeration::new(Identifier::new("Color"))
+        .add_named_variant(Identifier::new("RED"))
+        .add_named_variant(Identifier::new("GREEN"))
+        .build();
+    let status = Enumeration::new(Identifier::new("HttpStatus"))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("OK"),
+            Value::from(200),
+        ))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("NOT_FOUND"),
+            Value::from(404),
+        ))
+        .build();
+    let priority = Enumeration::new(Identifier::new("Priority"))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("HIGH"),
+            Value::from(1),
+        ))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("NONE"),
+            Value::from(0),
+        ))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let protobuf_writer = ProtobufWriter::default();
+        protobuf_writer.write_enumeration(writer, &enumeration)?;
+        protobuf_writer.write_enumeration(writer, &status)?;
+        protobuf_writer.write_enumeration(writer, &priority)
+    });
+    assert_eq!(
+        result,
+        r#"enum Color {
+    RED = 0;
+    GREEN = 1;
+}
+enum HttpStatus {
+    HTTP_STATUS_UNSPECIFIED = 0;
+    OK = 200;
+    NOT_FOUND = 404;
+}
+enum Priority {
+    NONE = 0;
+    HIGH = 1;
+}
+"#
+    );
+}
+
+#[test]
+fn test_service() {
+    let service = StructuredType::service(Identifier::new("UserService"))
+        .add_method(
+            FunctionDecl::new(Identifier::new("GetUser"))
+                .set_documentation("Look up a user.")
+                .add_parameter(Parameter::new(
+                    Identifier::new("request"),
+                    ValueType::Reference(Identifier::new("GetUserRequest")),
+                ))
+                .set_value_type(ValueType::Reference(Identifier::new("User")))
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        ProtobufWriter::default().write_structured_type(writer, &service)
+    });
+    assert_eq!(
+        result,
+        r#"service UserService {
+    /**
+    Look up a user.
+    */
+    rpc GetUser(GetUserRequest) returns (User);
+}
+"#
+    );
+}
+
+#[test]
+fn test_rpc_signature_unsupported() {
+    let service = StructuredType::service(Identifier::new("UserService"))
+        .add_method(
+            FunctionDecl::new(Identifier::new("GetUser"))
+                .add_parameter(Parameter::new(Identifier::new("id"), ValueType::i64()))
+                .set_value_type(ValueType::Reference(Identifier::new("User")))
+                .build(),
+        )
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(ProtobufWriter::default()
+        .write_structured_type(&mut writer, &service)
+        .is_err());
+}
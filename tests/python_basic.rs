@@ -0,0 +1,303 @@
+use code_writer::language::python::{PythonLayout, PythonWriter};
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    Builder, Enumeration, EnumerationVariant, Field, FunctionDecl, GenericParameter,
+    HasDocumentation, HasGenericParameters, HasOptionalType, Identifier, Import, IsOptional,
+    Module, NamedValue, Parameter, Receiver, StructuredType, TypeAlias, Value, ValueType,
+    VariantPayload,
+};
+use code_writer::project::ProjectLayout;
+use code_writer::writer::{module_to_string, CodeWriter, ModuleWriter};
+use std::path::PathBuf;
+
+pub mod common;
+
+#[test]
+fn test_dataclass() {
+    let structure = StructuredType::class(Identifier::new("Page"))
+        .set_documentation("A page of results.\n\nPages are never empty.")
+        .add_generic_parameter(GenericParameter::with_bounds(
+            Identifier::new("T"),
+            vec![ValueType::Reference(Identifier::new("Entity"))],
+        ))
+        .add_field(
+            Field::with_value(
+                Identifier::new("items"),
+                ValueType::array_of(ValueType::Reference(Identifier::new("T"))),
+                Value::Values(vec![]),
+            )
+            .set_documentation("The items on this page.")
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("totals"),
+                ValueType::map_of(ValueType::string(), ValueType::u64()),
+            )
+            .optional()
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("range"),
+                ValueType::tuple_of(vec![ValueType::u32(), ValueType::f64()]),
+            )
+            .build(),
+        )
+        .add_method(
+            FunctionDecl::new(Identifier::new("get"))
+                .set_receiver(Receiver::Reference)
+                .add_parameter(Parameter::new(Identifier::new("index"), ValueType::u32()))
+                .set_value_type(ValueType::optional(ValueType::Reference(Identifier::new(
+                    "T",
+                ))))
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        PythonWriter::default().write_structured_type(writer, &structure)
+    });
+    assert_eq!(
+        result,
+        r#"T = TypeVar("T", bound=Entity)
+
+@dataclass(kw_only=True)
+class Page(Generic[T]):
+    """A page of results.
+
+    Pages are never empty.
+    """
+
+    #: The items on this page.
+    items: list[T] = field(default_factory=list)
+    totals: Optional[dict[str, int]] = None
+    range: tuple[int, float]
+
+    def get(self, index: int) -> Optional[T]:
+        ...
+"#
+    );
+}
+
+#[test]
+fn test_docstring_escapes() {
+    let quoted = StructuredType::exception(Identifier::new("Greeting"))
+        .set_documentation("Says \"hi\"")
+        .build();
+    let pattern = StructuredType::exception(Identifier::new("Mismatch"))
+        .set_documentation("Does not match \\d+, \"\" or \"x\".")
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let python_writer = PythonWriter::default();
+        python_writer.write_structured_type(writer, &quoted)?;
+        python_writer.write_structured_type(writer, &pattern)
+    });
+    assert_eq!(
+        result,
+        r#"@dataclass
+class Greeting(Exception):
+    """Says "hi\""""
+@dataclass
+class Mismatch(Exception):
+    """Does not match \\d+, "" or "x"."""
+"#
+    );
+}
+
+#[test]
+fn test_union_exception_and_protocol() {
+    let union = StructuredType::union(Identifier::new("Contact"))
+        .add_field(Field::new(Identifier::new("email"), ValueType::string()).build())
+        .add_field(
+            Field::new(
+                Identifier::new("photo"),
+                ValueType::array_of(ValueType::u8()),
+            )
+            .build(),
+        )
+        .build();
+    let exception = StructuredType::exception(Identifier::new("NotFound"))
+        .set_documentation("Nothing \"\"\"here\"\"\".")
+        .build();
+    let protocol = StructuredType::interface(Identifier::new("Named"))
+        .add_field(Field::new(Identifier::new("name"), ValueType::string()).build())
+        .add_method(
+            FunctionDecl::new(Identifier::new("rename"))
+                .set_documentation("Change the name.")
+                .add_parameter(Parameter::new(Identifier::new("name"), ValueType::string()))
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let python_writer = PythonWriter::default();
+        python_writer.write_structured_type(writer, &union)?;
+        python_writer.write_structured_type(writer, &exception)?;
+        python_writer.write_structured_type(writer, &protocol)
+    });
+    assert_eq!(
+        result,
+        r#"@dataclass
+class Contact:
+    email: Optional[str] = None
+    photo: Optional[bytes] = None
+@dataclass
+class NotFound(Exception):
+    """Nothing \"\"\"here\"\"\"."""
+class Named(Protocol):
+    name: str
+
+    @staticmethod
+    def rename(name: str) -> None:
+        """Change the name."""
+        ...
+"#
+    );
+}
+
+#[test]
+fn test_enumeration() {
+    let enumeration = Enumeration::new(Identifier::new("Color"))
+        .set_documentation("Primary colors.")
+        .add_variant(
+            EnumerationVariant::new(Identifier::new("RED"))
+                .set_documentation("The color red.")
+                .build(),
+        )
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("GREEN"),
+            Value::from("green"),
+        ))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        PythonWriter::default().write_enumeration(writer, &enumeration)
+    });
+    assert_eq!(
+        result,
+        r#"class Color(Enum):
+    """Primary colors."""
+
+    #: The color red.
+    RED = auto()
+    GREEN = "green"
+"#
+    );
+}
+
+#[test]
+fn test_payload_variant_unsupported() {
+    let enumeration = Enumeration::new(Identifier::new("Shape"))
+        .add_variant(
+            EnumerationVariant::new(Identifier::new("Circle"))
+                .set_payload(VariantPayload::Tuple(vec![ValueType::f64()]))
+                .build(),
+        )
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(PythonWriter::default()
+        .write_enumeration(&mut writer, &enumeration)
+        .is_err());
+}
+
+#[test]
+fn test_module_items() {
+    let module = Module::new(Identifier::new("users"))
+        .set_documentation("User accounts.")
+        .add_import(
+            Import::new(Namespace::new(vec![
+                Identifier::new("common"),
+                Identifier::new("address"),
+            ]))
+            .item(Identifier::new("Country"))
+            .item_with_alias(Identifier::new("Address"), Identifier::new("PostalAddress"))
+            .build(),
+        )
+        .add_import(Import::new(Namespace::new(vec![Identifier::new("util")])))
+        .add_alias(TypeAlias::new(
+            Identifier::new("UserId"),
+            ValueType::string(),
+        ))
+        .add_constant(
+            NamedValue::new(
+                Identifier::new("MAX_USERS"),
+                ValueType::u32(),
+                Value::from(100u32),
+            )
+            .set_documentation("The most users allowed.")
+            .build(),
+        )
+        .add_function(
+            FunctionDecl::new(Identifier::new("find_user"))
+                .set_documentation("Find a user.")
+                .add_parameter(
+                    Parameter::new(
+                        Identifier::new("id"),
+                        ValueType::Reference(Identifier::new("UserId")),
+                    )
+                    .set_documentation("The user to find.")
+                    .build(),
+                )
+                .add_parameter(
+                    Parameter::new(Identifier::new("cached"), ValueType::boolean())
+                        .optional()
+                        .build(),
+                )
+                .add_parameter(Parameter::new(Identifier::new("retries"), ValueType::u8()))
+                .set_value_type(ValueType::Reference(Identifier::new("User")))
+                .add_throws(Parameter::new(
+                    Identifier::new("e"),
+                    ValueType::Reference(Identifier::new("NotFound")),
+                ))
+                .build(),
+        )
+        .add_sub_module(Module::new(Identifier::new("roles")).build())
+        .build();
+
+    assert_eq!(
+        module_to_string(&PythonWriter::default(), &module).unwrap(),
+        r#""""User accounts."""
+
+from __future__ import annotations
+
+from typing import Final, Optional, TypeAlias
+
+from common.address import Country, Address as PostalAddress
+
+import util
+
+UserId: TypeAlias = str
+
+#: The most users allowed.
+MAX_USERS: Final[int] = 100
+
+def find_user(id: UserId, *, cached: Optional[bool] = None, retries: int) -> User:
+    """Find a user.
+
+    Args:
+        id: The user to find.
+
+    Raises:
+        NotFound
+    """
+    ...
+
+from . import roles
+"#
+    );
+}
+
+#[test]
+fn test_layout() {
+    let namespace = Namespace::new(vec![Identifier::new("api"), Identifier::new("users")]);
+    let layout = PythonLayout::default();
+    assert_eq!(
+        layout.module_file_path(&namespace),
+        PathBuf::from("api/users/__init__.py")
+    );
+    assert!(layout.fingerprint_comment("abc").unwrap().starts_with("# "));
+}
@@ -1,5 +1,13 @@
 use code_writer::language::rust::RustWriter;
-use code_writer::writer::CodeWriter;
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    AssociatedConstant, AssociatedType, BinaryOperator, Builder, Enumeration, EnumerationVariant,
+    Expression, Field, FunctionDecl, GenericParameter, HasDocumentation, HasGenericParameters,
+    HasOptionalType, HasProperties, HasVisibility, Identifier, Implementation, Import, IsOptional,
+    MatchArm, Module, ModuleContent, Parameter, Property, Receiver, Statement, StatementBlock,
+    StructuredType, TypeAlias, Value, ValueType, WherePredicate,
+};
+use code_writer::writer::{CodeWriter, DocCommentStyle, FormattingPolicy, ModuleWriter};
 
 pub mod common;
 
@@ -10,3 +18,967 @@ fn test_generate() {
     let rust_writer = RustWriter::default();
     common::write_code_model(&mut writer, Box::new(rust_writer));
 }
+
+#[test]
+fn test_enumeration_variants() {
+    let enumeration = Enumeration::new(Identifier::new("Shape"))
+        .make_public()
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("Empty"),
+            Value::from(0),
+        ))
+        .add_variant(EnumerationVariant::tuple(
+            Identifier::new("Point"),
+            vec![ValueType::i32(), ValueType::i32()],
+        ))
+        .add_variant(
+            EnumerationVariant::structure(
+                Identifier::new("Circle"),
+                vec![Field::new(Identifier::new("radius"), ValueType::f64())],
+            )
+            .set_documentation("A circle.")
+            .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        RustWriter::default().write_enumeration(writer, &enumeration)
+    });
+    assert_eq!(
+        result,
+        r##"#[repr(isize)]
+pub enum Shape {
+    Empty = 0,
+    Point(i32, i32),
+    /**
+    A circle.
+    */
+    Circle {
+        radius: f64,
+    },
+}
+"##
+    );
+}
+
+#[test]
+fn test_enumeration_discriminants() {
+    let status = Enumeration::new(Identifier::new("Status"))
+        .add_property(Property::with_value(
+            Identifier::new("repr"),
+            vec![Value::from(Identifier::new("u8"))].into(),
+        ))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("Idle"),
+            Value::from(1u8),
+        ))
+        .add_variant(EnumerationVariant::tuple(
+            Identifier::new("Busy"),
+            vec![ValueType::u32()],
+        ))
+        .build();
+    let float = Enumeration::new(Identifier::new("Ratio"))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("Half"),
+            Value::from(0.5f64),
+        ))
+        .build();
+
+    assert_eq!(
+        common::write_to_string(|writer| {
+            RustWriter::default().write_enumeration(writer, &status)
+        }),
+        r#"#[repr(u8)]
+enum Status {
+    Idle = 1,
+    Busy(u32),
+}
+"#
+    );
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(RustWriter::default()
+        .write_enumeration(&mut writer, &float)
+        .is_err());
+}
+
+#[test]
+fn test_module_with_enumeration() {
+    let module = Module::new(Identifier::new("colors"))
+        .add_enumeration(
+            Enumeration::new(Identifier::new("Color"))
+                .add_named_variant(Identifier::new("Red"))
+                .add_named_variant(Identifier::new("Green"))
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let rust_writer = RustWriter::default();
+        rust_writer.write_module(writer, &module)?;
+        for content in module.content() {
+            if let ModuleContent::Enumeration(v) = content {
+                rust_writer.write_enumeration(writer, v)?;
+            }
+        }
+        Ok(())
+    });
+    assert_eq!(
+        result,
+        r#"enum Color {
+    Red,
+    Green,
+}
+"#
+    );
+}
+
+#[test]
+fn test_function_body() {
+    let function = FunctionDecl::new(Identifier::new("classify"))
+        .make_public()
+        .add_parameter(Parameter::new(Identifier::new("n"), ValueType::i32()))
+        .set_value_type(ValueType::string())
+        .set_body(
+            StatementBlock::new()
+                .add_statement(Statement::let_mut_binding(
+                    Identifier::new("total"),
+                    Expression::binary(
+                        Identifier::new("n").into(),
+                        BinaryOperator::Multiply,
+                        Value::from(2).into(),
+                    ),
+                ))
+                .add_statement(Statement::While(
+                    Expression::binary(
+                        Identifier::new("total").into(),
+                        BinaryOperator::Greater,
+                        Value::from(100).into(),
+                    ),
+                    vec![Statement::assign(
+                        Identifier::new("total").into(),
+                        Expression::binary(
+                            Identifier::new("total").into(),
+                            BinaryOperator::Divide,
+                            Value::from(2).into(),
+                        ),
+                    )]
+                    .into(),
+                ))
+                .add_statement(Statement::if_then_else(
+                    Expression::binary(
+                        Identifier::new("total").into(),
+                        BinaryOperator::Equal,
+                        Value::from(0).into(),
+                    ),
+                    vec![Statement::return_value(Expression::method_call(
+                        Value::from("zero").into(),
+                        Identifier::new("to_string"),
+                        vec![],
+                    ))]
+                    .into(),
+                    vec![Statement::if_then(
+                        Expression::binary(
+                            Identifier::new("total").into(),
+                            BinaryOperator::Less,
+                            Value::from(0).into(),
+                        ),
+                        vec![Statement::return_value(Expression::call(
+                            Expression::Path(
+                                vec![Identifier::new("String"), Identifier::new("from")].into(),
+                            ),
+                            vec![Value::from("negative").into()],
+                        ))]
+                        .into(),
+                    )]
+                    .into(),
+                ))
+                .add_statement(Statement::match_on(
+                    Identifier::new("total").into(),
+                    vec![
+                        MatchArm::new(
+                            Value::from(1).into(),
+                            vec![Statement::return_value(Expression::call(
+                                Identifier::new("one").into(),
+                                vec![],
+                            ))]
+                            .into(),
+                        ),
+                        MatchArm::new(Identifier::new("_").into(), StatementBlock::new()),
+                    ],
+                ))
+                .add_statement(Statement::Expression(Expression::method_call(
+                    Expression::struct_literal(
+                        Identifier::new("Counter"),
+                        vec![(Identifier::new("value"), Identifier::new("total").into())],
+                    ),
+                    Identifier::new("format"),
+                    vec![],
+                )))
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        RustWriter::default().write_function_decl(writer, &function)
+    });
+    assert_eq!(
+        result,
+        r#"pub fn classify(n: i32) -> String {
+    let mut total = n * 2;
+    while total > 100 {
+        total = total / 2;
+    }
+    if total == 0 {
+        return "zero".to_string();
+    } else if total < 0 {
+        return String::from("negative");
+    }
+    match total {
+        1 => {
+            return one();
+        }
+        _ => {}
+    }
+    Counter { value: total }.format();
+}
+"#
+    );
+}
+
+#[test]
+fn test_implementation() {
+    let implementation = Implementation::of_trait(
+        ValueType::Reference(Identifier::new("Display")),
+        ValueType::Generic(
+            Identifier::new("Wrapper"),
+            vec![ValueType::Reference(Identifier::new("T"))],
+        ),
+    )
+    .add_generic_parameter(GenericParameter::with_bounds(
+        Identifier::new("T"),
+        vec![ValueType::Reference(Identifier::new("Display"))],
+    ))
+    .add_method(
+        FunctionDecl::new(Identifier::new("fmt"))
+            .make_public()
+            .set_receiver(Receiver::Reference)
+            .add_parameter(Parameter::new(
+                Identifier::new("f"),
+                ValueType::Reference(Identifier::new("&mut Formatter<'_>")),
+            ))
+            .set_value_type(ValueType::Reference(Identifier::new("std::fmt::Result")))
+            .set_body(
+                vec![Statement::Expression(Expression::call(
+                    Identifier::new("write!").into(),
+                    vec![
+                        Identifier::new("f").into(),
+                        Value::from("{}").into(),
+                        Expression::field(Identifier::new("self").into(), Identifier::new("0")),
+                    ],
+                ))]
+                .into(),
+            )
+            .build(),
+    )
+    .build();
+
+    let result = common::write_to_string(|writer| {
+        RustWriter::default().write_implementation(writer, &implementation)
+    });
+    assert_eq!(
+        result,
+        r#"impl<T: Display> Display for Wrapper<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0);
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn test_structure_methods() {
+    let structure = StructuredType::structure(Identifier::new("Counter"))
+        .add_field(Field::new(Identifier::new("count"), ValueType::u32()))
+        .add_method(
+            FunctionDecl::new(Identifier::new("count"))
+                .make_public()
+                .set_receiver(Receiver::Reference)
+                .set_value_type(ValueType::u32())
+                .build(),
+        )
+        .add_method(
+            FunctionDecl::new(Identifier::new("reset"))
+                .make_public()
+                .set_receiver(Receiver::MutableReference)
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        RustWriter::default().write_structured_type(writer, &structure)
+    });
+    assert_eq!(
+        result,
+        r#"struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn count(&self) -> u32;
+
+    pub fn reset(&mut self);
+}
+"#
+    );
+}
+
+#[test]
+fn test_generic_declarations() {
+    let t = || ValueType::Reference(Identifier::new("T"));
+    let structure = StructuredType::structure(Identifier::new("Cache"))
+        .make_public()
+        .add_generic_parameter(GenericParameter::with_bounds(
+            Identifier::new("K"),
+            vec![
+                ValueType::Reference(Identifier::new("Eq")),
+                ValueType::Reference(Identifier::new("Hash")),
+            ],
+        ))
+        .add_generic_parameter(GenericParameter::with_default(
+            Identifier::new("T"),
+            ValueType::string(),
+        ))
+        .add_where_predicate(WherePredicate::new(
+            t(),
+            vec![ValueType::Reference(Identifier::new("Clone"))],
+        ))
+        .add_field(Field::new(
+            Identifier::new("entries"),
+            ValueType::map_of(ValueType::Reference(Identifier::new("K")), t()),
+        ))
+        .build();
+    let function = FunctionDecl::new(Identifier::new("first"))
+        .add_generic_parameter(GenericParameter::new(Identifier::new("T")))
+        .add_parameter(Parameter::new(
+            Identifier::new("values"),
+            ValueType::array_of(t()),
+        ))
+        .set_value_type(t())
+        .add_where_predicate(WherePredicate::new(
+            t(),
+            vec![ValueType::Reference(Identifier::new("Clone"))],
+        ))
+        .set_body(
+            vec![Statement::Expression(Expression::method_call(
+                Expression::variable(Identifier::new("values")),
+                Identifier::new("remove"),
+                vec![Value::from(0).into()],
+            ))]
+            .into(),
+        )
+        .build();
+    let type_alias = TypeAlias::new(Identifier::new("List"), ValueType::array_of(t()))
+        .add_generic_parameter(GenericParameter::new(Identifier::new("T")))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let rust_writer = RustWriter::default();
+        rust_writer.write_structured_type(writer, &structure)?;
+        rust_writer.write_function_decl(writer, &function)?;
+        rust_writer.write_type_alias(writer, &type_alias)
+    });
+    assert_eq!(
+        result,
+        r#"pub struct Cache<K: Eq + Hash, T = String>
+where
+    T: Clone,
+{
+    entries: HashMap<K, T>,
+}
+fn first<T>(values: Vec<T>) -> T
+where
+    T: Clone,
+{
+    values.remove(0);
+}
+type List<T> = Vec<T>;
+"#
+    );
+}
+
+#[test]
+fn test_impl_headers_omit_generic_defaults() {
+    let defaulted = || GenericParameter::with_default(Identifier::new("T"), ValueType::string());
+    let structure = StructuredType::structure(Identifier::new("Holder"))
+        .add_generic_parameter(defaulted())
+        .add_field(Field::with_value(
+            Identifier::new("size"),
+            ValueType::u32(),
+            Value::from(8u32),
+        ))
+        .add_method(FunctionDecl::new(Identifier::new("clear")).build())
+        .build();
+    let implementation = Implementation::of_trait(
+        ValueType::Reference(Identifier::new("Marker")),
+        ValueType::Reference(Identifier::new("Holder<T>")),
+    )
+    .add_generic_parameter(defaulted())
+    .build();
+
+    let result = common::write_to_string(|writer| {
+        let rust_writer = RustWriter::default();
+        rust_writer.write_structured_type(writer, &structure)?;
+        rust_writer.write_implementation(writer, &implementation)
+    });
+    assert_eq!(
+        result,
+        r#"struct Holder<T = String> {
+    size: u32,
+}
+
+impl<T> Holder<T> {
+    fn clear();
+}
+
+impl<T> Default for Holder<T> {
+    fn default() -> Self {
+        Self {
+            size: 8,
+        }
+    }
+}
+impl<T> Marker for Holder<T> {}
+"#
+    );
+}
+
+#[test]
+fn test_generic_default_on_function_unsupported() {
+    let function = FunctionDecl::new(Identifier::new("make"))
+        .add_generic_parameter(GenericParameter::with_default(
+            Identifier::new("T"),
+            ValueType::string(),
+        ))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(RustWriter::default()
+        .write_function_decl(&mut writer, &function)
+        .is_err());
+}
+
+#[test]
+fn test_indirection_types() {
+    let structure = StructuredType::structure(Identifier::new("Parser"))
+        .add_generic_parameter(GenericParameter::lifetime(Identifier::new("a")))
+        .add_field(Field::new(
+            Identifier::new("input"),
+            ValueType::borrowed_for(Identifier::new("a"), ValueType::string_slice()),
+        ))
+        .add_field(Field::new(
+            Identifier::new("buffer"),
+            ValueType::borrowed_mut_for(Identifier::new("a"), ValueType::slice_of(ValueType::u8())),
+        ))
+        .add_field(Field::new(
+            Identifier::new("name"),
+            ValueType::Generic(
+                Identifier::new("Cow"),
+                vec![
+                    ValueType::lifetime(Identifier::new("a")),
+                    ValueType::string_slice(),
+                ],
+            ),
+        ))
+        .add_field(Field::new(
+            Identifier::new("raw"),
+            ValueType::pointer_to(ValueType::u8()),
+        ))
+        .add_field(Field::new(
+            Identifier::new("raw_mut"),
+            ValueType::pointer_mut_to(ValueType::u8()),
+        ))
+        .add_field(Field::new(
+            Identifier::new("next"),
+            ValueType::boxed(ValueType::Reference(Identifier::new("Node"))),
+        ))
+        .build();
+    let function = FunctionDecl::new(Identifier::new("label"))
+        .add_parameter(Parameter::new(
+            Identifier::new("value"),
+            ValueType::str_ref(),
+        ))
+        .add_parameter(Parameter::new(
+            Identifier::new("out"),
+            ValueType::borrowed_mut(ValueType::string()),
+        ))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let rust_writer = RustWriter::default();
+        rust_writer.write_structured_type(writer, &structure)?;
+        rust_writer.write_function_decl(writer, &function)
+    });
+    assert_eq!(
+        result,
+        r#"struct Parser<'a> {
+    input: &'a str,
+    buffer: &'a mut [u8],
+    name: Cow<'a, str>,
+    raw: *const u8,
+    raw_mut: *mut u8,
+    next: Box<Node>,
+}
+fn label(value: &str, out: &mut String);
+"#
+    );
+}
+
+#[test]
+fn test_composite_types() {
+    let function = FunctionDecl::new(Identifier::new("parse"))
+        .add_parameter(Parameter::new(
+            Identifier::new("values"),
+            ValueType::array_of(ValueType::optional(ValueType::string())),
+        ))
+        .add_parameter(Parameter::new(
+            Identifier::new("key"),
+            ValueType::fixed_array_of(ValueType::u8(), 32),
+        ))
+        .add_parameter(Parameter::new(
+            Identifier::new("pair"),
+            ValueType::tuple_of(vec![ValueType::i32(), ValueType::boolean()]),
+        ))
+        .add_parameter(Parameter::new(
+            Identifier::new("single"),
+            ValueType::tuple_of(vec![ValueType::i32()]),
+        ))
+        .set_value_type(ValueType::result_of(
+            ValueType::optional(ValueType::unit()),
+            ValueType::Reference(Identifier::new("Error")),
+        ))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        RustWriter::default().write_function_decl(writer, &function)
+    });
+    assert_eq!(
+        result,
+        r#"fn parse(
+    values: Vec<Option<String>>,
+    key: [u8; 32],
+    pair: (i32, bool),
+    single: (i32,),
+) -> Result<Option<()>, Error>;
+"#
+    );
+}
+
+#[test]
+fn test_interface_as_trait() {
+    let interface = StructuredType::interface(Identifier::new("Plugin"))
+        .make_public()
+        .set_documentation("A loadable plugin.")
+        .add_extend(ValueType::Reference(Identifier::new("Send")))
+        .add_extend(ValueType::Reference(Identifier::new("Sync")))
+        .add_associated_type(AssociatedType::with_bounds(
+            Identifier::new("Config"),
+            vec![ValueType::Reference(Identifier::new("Default"))],
+        ))
+        .add_associated_constant(AssociatedConstant::new(
+            Identifier::new("NAME"),
+            ValueType::borrowed_for(Identifier::new("static"), ValueType::string_slice()),
+        ))
+        .add_associated_constant(AssociatedConstant::with_value(
+            Identifier::new("VERSION"),
+            ValueType::u32(),
+            Value::from(1u32),
+        ))
+        .add_method(
+            FunctionDecl::new(Identifier::new("configure"))
+                .set_receiver(Receiver::MutableReference)
+                .add_parameter(Parameter::new(
+                    Identifier::new("config"),
+                    ValueType::Reference(Identifier::new("Self::Config")),
+                ))
+                .build(),
+        )
+        .add_method(
+            FunctionDecl::new(Identifier::new("enabled"))
+                .set_receiver(Receiver::Reference)
+                .set_value_type(ValueType::boolean())
+                .set_body(vec![Statement::return_value(Value::from(true).into())].into())
+                .build(),
+        )
+        .build();
+    let implementation = Implementation::of_trait(
+        ValueType::Reference(Identifier::new("Plugin")),
+        ValueType::Reference(Identifier::new("Logger")),
+    )
+    .add_associated_type(AssociatedType::with_type(
+        Identifier::new("Config"),
+        ValueType::Reference(Identifier::new("LogConfig")),
+    ))
+    .add_associated_constant(AssociatedConstant::with_value(
+        Identifier::new("NAME"),
+        ValueType::borrowed_for(Identifier::new("static"), ValueType::string_slice()),
+        Value::from("logger"),
+    ))
+    .build();
+
+    let result = common::write_to_string(|writer| {
+        let rust_writer = RustWriter::default();
+        rust_writer.write_structured_type(writer, &interface)?;
+        rust_writer.write_implementation(writer, &implementation)
+    });
+    assert_eq!(
+        result,
+        r#"/**
+A loadable plugin.
+*/
+pub trait Plugin: Send + Sync {
+    type Config: Default;
+
+    const NAME: &'static str;
+    const VERSION: u32 = 1;
+
+    fn configure(&mut self, config: Self::Config);
+
+    fn enabled(&self) -> bool {
+        return true;
+    }
+}
+impl Plugin for Logger {
+    type Config = LogConfig;
+
+    const NAME: &'static str = "logger";
+}
+"#
+    );
+}
+
+#[test]
+fn test_interface_with_fields_unsupported() {
+    let interface = StructuredType::interface(Identifier::new("Plugin"))
+        .add_field(Field::new(Identifier::new("name"), ValueType::string()))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(RustWriter::default()
+        .write_structured_type(&mut writer, &interface)
+        .is_err());
+}
+
+#[test]
+fn test_inherent_associated_type_writes_nothing() {
+    let structure = StructuredType::structure(Identifier::new("Parser"))
+        .add_field(Field::new(Identifier::new("input"), ValueType::string()))
+        .add_associated_type(AssociatedType::new(Identifier::new("Output")))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    {
+        let mut writer = CodeWriter::new(&mut buffer);
+        assert!(RustWriter::default()
+            .write_structured_type(&mut writer, &structure)
+            .is_err());
+    }
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_default_impl_from_field_values() {
+    let structure = StructuredType::structure(Identifier::new("Settings"))
+        .make_public()
+        .add_field(
+            Field::with_value(Identifier::new("retries"), ValueType::i32(), Value::from(3))
+                .make_public()
+                .build(),
+        )
+        .add_field(Field::with_value(
+            Identifier::new("host"),
+            ValueType::string(),
+            Value::from("localhost"),
+        ))
+        .add_field(
+            Field::with_value(
+                Identifier::new("port"),
+                ValueType::u16(),
+                Value::from(80u16),
+            )
+            .optional()
+            .build(),
+        )
+        .add_field(Field::new(
+            Identifier::new("tags"),
+            ValueType::array_of(ValueType::string()),
+        ))
+        .add_field(Field::with_value(
+            Identifier::new("hosts"),
+            ValueType::array_of(ValueType::string()),
+            Value::Values(vec![Value::from("a"), Value::from("b")]),
+        ))
+        .add_field(Field::with_value(
+            Identifier::new("limits"),
+            ValueType::map_of(ValueType::string(), ValueType::u32()),
+            Value::NamedValues(Default::default()),
+        ))
+        .add_field(Field::with_value(
+            Identifier::new("label"),
+            ValueType::boxed(ValueType::optional(ValueType::string())),
+            Value::from("main"),
+        ))
+        .add_field(Field::with_value(
+            Identifier::new("scale"),
+            ValueType::f64(),
+            Value::from(3.0f64),
+        ))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        RustWriter::default().write_structured_type(writer, &structure)
+    });
+    assert_eq!(
+        result,
+        r#"pub struct Settings {
+    pub retries: i32,
+    host: String,
+    port: Option<u16>,
+    tags: Vec<String>,
+    hosts: Vec<String>,
+    limits: HashMap<String, u32>,
+    label: Box<Option<String>>,
+    scale: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            host: "localhost".to_string(),
+            port: Some(80),
+            tags: Default::default(),
+            hosts: vec!["a".to_string(), "b".to_string()],
+            limits: HashMap::new(),
+            label: Box::new(Some("main".to_string())),
+            scale: 3.0,
+        }
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn test_default_impl_mismatched_value_unsupported() {
+    for field in [
+        Field::with_value(
+            Identifier::new("hosts"),
+            ValueType::array_of(ValueType::string()),
+            Value::from("localhost"),
+        ),
+        Field::with_value(Identifier::new("scale"), ValueType::f64(), Value::from(2)),
+        Field::with_value(
+            Identifier::new("retries"),
+            ValueType::u8(),
+            Value::from(300),
+        ),
+    ] {
+        let structure = StructuredType::structure(Identifier::new("Settings"))
+            .add_field(field)
+            .build();
+
+        let mut buffer: Vec<u8> = Default::default();
+        {
+            let mut writer = CodeWriter::new(&mut buffer);
+            assert!(RustWriter::default()
+                .write_structured_type(&mut writer, &structure)
+                .is_err());
+        }
+        assert!(buffer.is_empty());
+    }
+}
+
+#[test]
+fn test_wrapped_derives_and_imports() {
+    let module = Module::new(Identifier::new("wrapped"))
+        .add_import(Import::with_items(
+            Identifier::new("serde").into(),
+            vec![
+                Identifier::new("Deserialize").into(),
+                Identifier::new("Deserializer").into(),
+                Identifier::new("Serialize").into(),
+                Identifier::new("Serializer").into(),
+            ],
+        ))
+        .add_structure(
+            StructuredType::structure(Identifier::new("Wrapped"))
+                .add_property(Property::with_value(
+                    Identifier::new("derive"),
+                    vec![
+                        Value::from("Clone"),
+                        Value::from("Debug"),
+                        Value::from("PartialEq"),
+                        Value::from("Deserialize"),
+                        Value::from("Serialize"),
+                    ]
+                    .into(),
+                ))
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        writer.set_max_width(40);
+        let rust_writer = RustWriter::default();
+        for content in module.content() {
+            match content {
+                ModuleContent::Import(v) => rust_writer.write_import(writer, v)?,
+                ModuleContent::Structure(v) => rust_writer.write_structured_type(writer, v)?,
+                _ => unreachable!(),
+            }
+        }
+        Ok(())
+    });
+    assert_eq!(
+        result,
+        r#"use serde::{
+    Deserialize, Deserializer,
+    Serialize, Serializer
+};
+#[derive(
+    Clone, Debug, PartialEq,
+    Deserialize, Serialize
+)]
+struct Wrapped {}
+"#
+    );
+}
+
+#[test]
+fn test_whole_module_import() {
+    let import = Import::new(Namespace::new(vec![
+        Identifier::new("std"),
+        Identifier::new("fmt"),
+    ]));
+    let result =
+        common::write_to_string(|writer| RustWriter::default().write_import(writer, &import));
+    assert_eq!(result, "use std::fmt;\n");
+
+    let mut namespace = Namespace::new(vec![Identifier::new("std")]);
+    let _ = namespace.pop();
+    let mut buffer = Vec::new();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(RustWriter::default()
+        .write_import(&mut writer, &Import::new(namespace))
+        .is_err());
+}
+
+#[test]
+fn test_wrapped_generic_parameters() {
+    let structure = StructuredType::structure(Identifier::new("Cache"))
+        .add_generic_parameter(GenericParameter::with_bounds(
+            Identifier::new("K"),
+            vec![
+                ValueType::Reference(Identifier::new("Hash")),
+                ValueType::Reference(Identifier::new("Eq")),
+            ],
+        ))
+        .add_generic_parameter(GenericParameter::with_bounds(
+            Identifier::new("V"),
+            vec![ValueType::Reference(Identifier::new("Clone"))],
+        ))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        writer.set_max_width(24);
+        RustWriter::default().write_structured_type(writer, &structure)
+    });
+    assert_eq!(
+        result,
+        r#"struct Cache<
+    K: Hash + Eq,
+    V: Clone,
+> {}
+"#
+    );
+}
+
+#[test]
+fn test_wrapped_type_arguments() {
+    let structure = StructuredType::structure(Identifier::new("Index"))
+        .add_field(Field::new(
+            Identifier::new("entries"),
+            ValueType::Generic(
+                Identifier::new("BTreeMap"),
+                vec![
+                    ValueType::Reference(Identifier::new("CustomerIdentifier")),
+                    ValueType::array_of(ValueType::Reference(Identifier::new("OrderSummary"))),
+                ],
+            ),
+        ))
+        .build();
+    let type_alias = TypeAlias::new(
+        Identifier::new("Lookup"),
+        ValueType::map_of(
+            ValueType::Reference(Identifier::new("CustomerIdentifier")),
+            ValueType::Reference(Identifier::new("OrderSummary")),
+        ),
+    );
+
+    let result = common::write_to_string(|writer| {
+        writer.set_max_width(48);
+        let rust_writer = RustWriter::default();
+        rust_writer.write_structured_type(writer, &structure)?;
+        rust_writer.write_type_alias(writer, &type_alias)
+    });
+    assert_eq!(
+        result,
+        r#"struct Index {
+    entries: BTreeMap<
+        CustomerIdentifier,
+        Vec<OrderSummary>,
+    >,
+}
+type Lookup = HashMap<
+    CustomerIdentifier,
+    OrderSummary,
+>;
+"#
+    );
+}
+
+#[test]
+fn test_line_doc_comments() {
+    let structure = StructuredType::structure(Identifier::new("Point"))
+        .set_documentation("A point in space.")
+        .add_field(
+            Field::new(Identifier::new("x"), ValueType::i32())
+                .set_documentation("The x coordinate.")
+                .build(),
+        )
+        .build();
+
+    let mut policy = FormattingPolicy::default();
+    policy.set_doc_comment_style(DocCommentStyle::Line);
+    let mut buffer: Vec<u8> = Default::default();
+    {
+        let mut writer = CodeWriter::with_policy(&mut buffer, policy);
+        RustWriter::default()
+            .write_structured_type(&mut writer, &structure)
+            .unwrap();
+        writer.flush().unwrap();
+    }
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        r#"/// A point in space.
+struct Point {
+    /// The x coordinate.
+    x: i32,
+}
+"#
+    );
+}
@@ -0,0 +1,197 @@
+use code_writer::language::rust::{RustLayout, RustWriter};
+use code_writer::language::thrift::ThriftWriter;
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    Builder, Enumeration, Field, FunctionDecl, HasDocumentation, Identifier, Implementation,
+    Module, Parameter, ProtectedRegion, StructuredType, ValueType,
+};
+use code_writer::project::{FileSet, MemoryFileSet, ProjectWriter};
+use code_writer::source_map::{SourceMap, SourcePosition};
+use code_writer::writer::{write_module_content, CodeWriter, ModuleWriter};
+use std::path::Path;
+
+fn make_module() -> Module {
+    Module::new(Identifier::new("shapes"))
+        .add_structure(
+            StructuredType::structure(Identifier::new("Circle"))
+                .set_documentation("A circle.")
+                .add_field(Field::new(Identifier::new("r"), ValueType::f64()).build())
+                .build(),
+        )
+        .add_enumeration(
+            Enumeration::new(Identifier::new("Color"))
+                .add_named_variant(Identifier::new("Red"))
+                .add_named_variant(Identifier::new("Green"))
+                .build(),
+        )
+        .build()
+}
+
+fn source_map_for(module_writer: &dyn ModuleWriter<Vec<u8>>, module: &Module) -> SourceMap {
+    let mut writer = CodeWriter::new(Vec::new());
+    writer.enable_source_map(None);
+    write_module_content(&mut writer, module_writer, module).unwrap();
+    writer.take_source_map().unwrap()
+}
+
+fn path(names: &[&str]) -> Namespace {
+    Namespace::new(names.iter().map(|name| Identifier::new(name)).collect())
+}
+
+fn span_of(source_map: &SourceMap, names: &[&str]) -> ((usize, usize), (usize, usize)) {
+    let span = source_map.get_path(&path(names)).unwrap();
+    (
+        (span.start().line(), span.start().column()),
+        (span.end().line(), span.end().column()),
+    )
+}
+
+#[test]
+fn test_rust_source_map() {
+    let source_map = source_map_for(&RustWriter::default(), &make_module());
+
+    assert_eq!(source_map.len(), 6);
+    assert_eq!(span_of(&source_map, &["shapes"]), ((0, 0), (10, 1)));
+    assert_eq!(
+        span_of(&source_map, &["shapes", "Circle"]),
+        ((0, 0), (5, 1))
+    );
+    assert_eq!(
+        span_of(&source_map, &["shapes", "Circle", "r"]),
+        ((4, 4), (4, 11))
+    );
+    assert_eq!(
+        span_of(&source_map, &["shapes", "Color"]),
+        ((7, 0), (10, 1))
+    );
+    assert_eq!(
+        span_of(&source_map, &["shapes", "Color", "Green"]),
+        ((9, 4), (9, 10))
+    );
+
+    assert_eq!(
+        source_map.element_at(4, 6),
+        Some(&path(&["shapes", "Circle", "r"]))
+    );
+    assert_eq!(
+        source_map.element_at(1, 0),
+        Some(&path(&["shapes", "Circle"]))
+    );
+    assert_eq!(source_map.element_at(20, 0), None);
+    assert!(source_map
+        .get(&path(&["shapes", "Color"]), &Identifier::new("Red"))
+        .unwrap()
+        .contains(SourcePosition::new(8, 4)));
+}
+
+#[test]
+fn test_thrift_source_map() {
+    let module = Module::new(Identifier::new("users"))
+        .add_structure(
+            StructuredType::service(Identifier::new("UserService"))
+                .add_method(FunctionDecl::new(Identifier::new("ping")).build())
+                .build(),
+        )
+        .build();
+    let source_map = source_map_for(&ThriftWriter::default(), &module);
+
+    assert_eq!(
+        span_of(&source_map, &["users", "UserService", "ping"]),
+        ((1, 4), (1, 16))
+    );
+}
+
+#[test]
+fn test_source_map_disabled() {
+    let mut writer = CodeWriter::new(Vec::new());
+    write_module_content(&mut writer, &RustWriter::default(), &make_module()).unwrap();
+    assert!(writer.source_map().is_none());
+}
+
+#[test]
+fn test_project_source_maps() {
+    let module = Module::new(Identifier::new("geometry"))
+        .add_sub_module(
+            Module::new(Identifier::new("shapes"))
+                .add_region(ProtectedRegion::new(Identifier::new("imports")))
+                .add_implementation(
+                    Implementation::new(ValueType::Reference(Identifier::new("Circle")))
+                        .add_method(FunctionDecl::new(Identifier::new("area")).build())
+                        .build(),
+                )
+                .build(),
+        )
+        .build();
+    let mut project_writer = ProjectWriter::new(RustLayout::NamedFiles, RustWriter::default());
+    let _ = project_writer.set_fingerprints(true).set_source_maps(true);
+
+    let file = Path::new("geometry/shapes.rs");
+    let rendered = project_writer.render_with_source_maps(&module).unwrap();
+    let (content, source_map) = &rendered[file];
+    assert_eq!(content.lines().nth(5).unwrap(), "    fn area();");
+    assert_eq!(
+        span_of(source_map, &["geometry", "shapes", "impl Circle", "area"]),
+        ((5, 4), (5, 14))
+    );
+
+    let mut files = MemoryFileSet::default();
+    let _ = project_writer.write(&module, &mut files).unwrap();
+    let edited = files.get(file).unwrap().replace(
+        "// @protected-region end imports",
+        "use std::f64::consts::PI;\nuse std::fmt;\n// @protected-region end imports",
+    );
+    files.write_file(file, &edited).unwrap();
+
+    let report = project_writer.dry_run(&module, &files).unwrap();
+    assert_eq!(
+        span_of(
+            report.source_map(file).unwrap(),
+            &["geometry", "shapes", "impl Circle", "area"]
+        ),
+        ((7, 4), (7, 14))
+    );
+    assert!(report.source_map(Path::new("geometry.rs")).is_some());
+}
+
+#[test]
+fn test_implementation_and_parameter_spans() {
+    let module = Module::new(Identifier::new("shapes"))
+        .add_structure(
+            StructuredType::structure(Identifier::new("Circle"))
+                .add_field(Field::new(Identifier::new("r"), ValueType::f64()).build())
+                .build(),
+        )
+        .add_implementation(
+            Implementation::new(ValueType::Reference(Identifier::new("Circle")))
+                .add_method(
+                    FunctionDecl::new(Identifier::new("scale"))
+                        .add_parameter(Parameter::new(Identifier::new("by"), ValueType::f64()))
+                        .build(),
+                )
+                .build(),
+        )
+        .add_implementation(
+            Implementation::of_trait(
+                ValueType::Reference(Identifier::new("Shape")),
+                ValueType::Reference(Identifier::new("Circle")),
+            )
+            .add_method(FunctionDecl::new(Identifier::new("area")).build())
+            .build(),
+        )
+        .build();
+    let source_map = source_map_for(&RustWriter::default(), &module);
+
+    assert!(source_map
+        .get_path(&path(&["shapes", "Circle", "r"]))
+        .is_some());
+    assert!(source_map
+        .get_path(&path(&["shapes", "impl Circle", "scale"]))
+        .is_some());
+    assert!(source_map
+        .get_path(&path(&["shapes", "impl Shape for Circle", "area"]))
+        .is_some());
+    assert_eq!(
+        span_of(&source_map, &["shapes", "impl Circle", "scale", "by"]),
+        ((5, 13), (5, 20))
+    );
+}
@@ -1,5 +1,13 @@
+use code_writer::error::ErrorKind;
 use code_writer::language::thrift::ThriftWriter;
-use code_writer::writer::CodeWriter;
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    Builder, Enumeration, EnumerationVariant, Field, FunctionDecl, GenericParameter,
+    HasDocumentation, HasFieldId, HasGenericParameters, HasOptionalType, Identifier,
+    Implementation, Import, IsOptional, Parameter, Statement, StructuredType, TypeAlias, Value,
+    ValueType,
+};
+use code_writer::writer::{CodeWriter, ModuleWriter};
 
 pub mod common;
 
@@ -10,3 +18,443 @@ fn test_generate() {
     let thrift_writer = ThriftWriter::default();
     common::write_code_model(&mut writer, Box::new(thrift_writer));
 }
+
+#[test]
+fn test_enumeration_values() {
+    let enumeration = Enumeration::new(Identifier::new("Status"))
+        .set_documentation("Request status.")
+        .add_variant(
+            EnumerationVariant::with_value(Identifier::new("OK"), Value::from(0))
+                .set_documentation("All good.")
+                .build(),
+        )
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("FAILED"),
+            Value::from(10),
+        ))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        ThriftWriter::default().write_enumeration(writer, &enumeration)
+    });
+    assert_eq!(
+        result,
+        r#"/**
+Request status.
+*/
+enum Status {
+    /**
+    All good.
+    */
+    OK = 0,
+    FAILED = 10,
+}
+"#
+    );
+}
+
+#[test]
+fn test_enumeration_payload_unsupported() {
+    let enumeration = Enumeration::new(Identifier::new("Shape"))
+        .add_variant(EnumerationVariant::tuple(
+            Identifier::new("Point"),
+            vec![ValueType::i32()],
+        ))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(ThriftWriter::default()
+        .write_enumeration(&mut writer, &enumeration)
+        .is_err());
+}
+
+#[test]
+fn test_function_body_unsupported() {
+    let function = FunctionDecl::new(Identifier::new("ping"))
+        .set_body(vec![Statement::Return(None)].into())
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(ThriftWriter::default()
+        .write_function_decl(&mut writer, &function)
+        .is_err());
+}
+
+#[test]
+fn test_implementation_unsupported() {
+    let implementation = Implementation::new(ValueType::Reference(Identifier::new("Address")));
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(ThriftWriter::default()
+        .write_implementation(&mut writer, &implementation)
+        .is_err());
+}
+
+#[test]
+fn test_generics_unsupported() {
+    let structure = StructuredType::structure(Identifier::new("Box"))
+        .add_generic_parameter(GenericParameter::new(Identifier::new("T")))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(ThriftWriter::default()
+        .write_structured_type(&mut writer, &structure)
+        .is_err());
+}
+
+#[test]
+fn test_indirection_types() {
+    let type_alias = TypeAlias::new(
+        Identifier::new("Bytes"),
+        ValueType::borrowed(ValueType::slice_of(ValueType::u8())),
+    );
+    let function = FunctionDecl::new(Identifier::new("lookup"))
+        .add_parameter(
+            Parameter::new(Identifier::new("key"), ValueType::str_ref())
+                .set_field_id(1)
+                .build(),
+        )
+        .set_value_type(ValueType::boxed(ValueType::Reference(Identifier::new(
+            "Entry",
+        ))))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let thrift_writer = ThriftWriter::default();
+        thrift_writer.write_type_alias(writer, &type_alias)?;
+        thrift_writer.write_function_decl(writer, &function)
+    });
+    assert_eq!(
+        result,
+        r#"typedef list<byte> Bytes
+Entry lookup(1: string key);
+"#
+    );
+}
+
+#[test]
+fn test_lifetime_unsupported() {
+    let type_alias = TypeAlias::new(
+        Identifier::new("Static"),
+        ValueType::lifetime(Identifier::new("static")),
+    );
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(ThriftWriter::default()
+        .write_type_alias(&mut writer, &type_alias)
+        .is_err());
+}
+
+#[test]
+fn test_composite_types() {
+    let structure = StructuredType::structure(Identifier::new("Record"))
+        .add_field(Field::new(
+            Identifier::new("tags"),
+            ValueType::array_of(ValueType::string()),
+        ))
+        .add_field(Field::new(
+            Identifier::new("checksum"),
+            ValueType::fixed_array_of(ValueType::u8(), 16),
+        ))
+        .add_field(Field::new(
+            Identifier::new("note"),
+            ValueType::optional(ValueType::string()),
+        ))
+        .build();
+    let function = FunctionDecl::new(Identifier::new("ping"))
+        .set_value_type(ValueType::unit())
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let thrift_writer = ThriftWriter::with_positional_field_ids();
+        thrift_writer.write_structured_type(writer, &structure)?;
+        thrift_writer.write_function_decl(writer, &function)
+    });
+    assert_eq!(
+        result,
+        r#"struct Record {
+    1: required list<string> tags,
+    2: required list<byte> checksum,
+    3: optional string note,
+}
+void ping();
+"#
+    );
+}
+
+#[test]
+fn test_tuple_unsupported() {
+    let type_alias = TypeAlias::new(
+        Identifier::new("Pair"),
+        ValueType::tuple_of(vec![ValueType::i32(), ValueType::i32()]),
+    );
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(ThriftWriter::default()
+        .write_type_alias(&mut writer, &type_alias)
+        .is_err());
+}
+
+#[test]
+fn test_optional_element_and_unit_unsupported() {
+    let structure = StructuredType::structure(Identifier::new("Record"))
+        .add_field(Field::new(
+            Identifier::new("tags"),
+            ValueType::array_of(ValueType::optional(ValueType::string())),
+        ))
+        .build();
+    let type_alias = TypeAlias::new(Identifier::new("Nothing"), ValueType::unit());
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    let thrift_writer = ThriftWriter::with_positional_field_ids();
+    assert!(thrift_writer
+        .write_structured_type(&mut writer, &structure)
+        .is_err());
+    assert!(thrift_writer
+        .write_type_alias(&mut writer, &type_alias)
+        .is_err());
+}
+
+#[test]
+fn test_default_values() {
+    let structure = StructuredType::structure(Identifier::new("Settings"))
+        .add_field(
+            Field::with_value(Identifier::new("retries"), ValueType::i32(), Value::from(3))
+                .optional()
+                .set_field_id(1)
+                .build(),
+        )
+        .add_field(
+            Field::with_value(
+                Identifier::new("host"),
+                ValueType::string(),
+                Value::from("C:\\tmp \"x\"\n"),
+            )
+            .set_field_id(2)
+            .build(),
+        )
+        .build();
+    let function = FunctionDecl::new(Identifier::new("connect"))
+        .add_parameter(
+            Parameter::with_value(
+                Identifier::new("timeout"),
+                ValueType::i32(),
+                Value::from(30),
+            )
+            .set_field_id(1)
+            .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        let thrift_writer = ThriftWriter::default();
+        thrift_writer.write_structured_type(writer, &structure)?;
+        thrift_writer.write_function_decl(writer, &function)
+    });
+    assert_eq!(
+        result,
+        r#"struct Settings {
+    1: optional i32 retries = 3,
+    2: required string host = "C:\\tmp \"x\"\n",
+}
+void connect(1: i32 timeout = 30);
+"#
+    );
+}
+
+#[test]
+fn test_explicit_field_ids() {
+    let structure = StructuredType::structure(Identifier::new("User"))
+        .add_field(
+            Field::new(Identifier::new("name"), ValueType::string())
+                .set_field_id(2)
+                .build(),
+        )
+        .add_field(
+            Field::new(Identifier::new("id"), ValueType::i64())
+                .set_field_id(1)
+                .build(),
+        )
+        .add_field(
+            Field::new(Identifier::new("email"), ValueType::string())
+                .optional()
+                .set_field_id(5)
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        ThriftWriter::default().write_structured_type(writer, &structure)
+    });
+    assert_eq!(
+        result,
+        r#"struct User {
+    2: required string name,
+    1: required i64 id,
+    5: optional string email,
+}
+"#
+    );
+}
+
+#[test]
+fn test_missing_field_id() {
+    let structure = StructuredType::structure(Identifier::new("User"))
+        .add_field(
+            Field::new(Identifier::new("id"), ValueType::i64())
+                .set_field_id(1)
+                .build(),
+        )
+        .add_field(Field::new(Identifier::new("name"), ValueType::string()))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    let result = ThriftWriter::default().write_structured_type(&mut writer, &structure);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::MissingFieldId(_, _)
+    ));
+}
+
+#[test]
+fn test_duplicate_field_id() {
+    let structure = StructuredType::structure(Identifier::new("User"))
+        .add_field(
+            Field::new(Identifier::new("id"), ValueType::i64())
+                .set_field_id(2)
+                .build(),
+        )
+        .add_field(Field::new(Identifier::new("name"), ValueType::string()))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    let result =
+        ThriftWriter::with_positional_field_ids().write_structured_type(&mut writer, &structure);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        ErrorKind::DuplicateFieldId(_, 2)
+    ));
+}
+
+#[test]
+fn test_service() {
+    let service = StructuredType::service(Identifier::new("UserService"))
+        .add_extend(ValueType::Reference(Identifier::new("BaseService")))
+        .add_method(
+            FunctionDecl::new(Identifier::new("get_user"))
+                .add_parameter(
+                    Parameter::new(Identifier::new("id"), ValueType::i64())
+                        .set_field_id(1)
+                        .build(),
+                )
+                .set_value_type(ValueType::Reference(Identifier::new("User")))
+                .add_throws(
+                    Parameter::new(
+                        Identifier::new("e"),
+                        ValueType::Reference(Identifier::new("NotFound")),
+                    )
+                    .set_field_id(1)
+                    .build(),
+                )
+                .build(),
+        )
+        .add_method(
+            FunctionDecl::new(Identifier::new("ping"))
+                .set_oneway(true)
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        ThriftWriter::default().write_structured_type(writer, &service)
+    });
+    assert_eq!(
+        result,
+        r#"service UserService extends BaseService {
+    User get_user(1: i64 id) throws (1: NotFound e),
+    oneway void ping(),
+}
+"#
+    );
+}
+
+#[test]
+fn test_oneway_with_result() {
+    let service = StructuredType::service(Identifier::new("UserService"))
+        .add_method(
+            FunctionDecl::new(Identifier::new("count"))
+                .set_value_type(ValueType::i32())
+                .set_oneway(true)
+                .build(),
+        )
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    let result = ThriftWriter::default().write_structured_type(&mut writer, &service);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_service_fields_error() {
+    let service = StructuredType::service(Identifier::new("UserService"))
+        .add_field(Field::new(Identifier::new("id"), ValueType::i64()))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    let result = ThriftWriter::default().write_structured_type(&mut writer, &service);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_wrapped_parameters() {
+    let function = FunctionDecl::new(Identifier::new("find_users"))
+        .add_parameter(
+            Parameter::new(Identifier::new("name"), ValueType::string())
+                .set_field_id(1)
+                .build(),
+        )
+        .add_parameter(
+            Parameter::new(Identifier::new("limit"), ValueType::i32())
+                .set_field_id(2)
+                .build(),
+        )
+        .set_value_type(ValueType::array_of(ValueType::Reference(Identifier::new(
+            "User",
+        ))))
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        writer.set_max_width(40);
+        ThriftWriter::default().write_function_decl(writer, &function)
+    });
+    assert_eq!(
+        result,
+        r#"list<User> find_users(
+    1: string name,
+    2: i32 limit,
+);
+"#
+    );
+}
+
+#[test]
+fn test_import_without_items() {
+    let import = Import::new(Namespace::new(vec![
+        Identifier::new("shared"),
+        Identifier::new("types"),
+    ]));
+    let result =
+        common::write_to_string(|writer| ThriftWriter::default().write_import(writer, &import));
+    assert_eq!(result, "include \"shared/types.thrift\"\n");
+}
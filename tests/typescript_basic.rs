@@ -0,0 +1,345 @@
+use code_writer::language::typescript::{EnumerationStyle, TypeScriptLayout, TypeScriptWriter};
+use code_writer::model::identity::Namespace;
+use code_writer::model::{
+    Builder, Enumeration, EnumerationVariant, Field, FunctionDecl, GenericParameter,
+    HasDocumentation, HasGenericParameters, HasOptionalType, HasVisibility, Identifier, Import,
+    IsOptional, Module, NamedValue, Parameter, StructuredType, TypeAlias, Value, ValueType,
+    Visibility,
+};
+use code_writer::project::{ProjectLayout, ProjectWriter};
+use code_writer::writer::{module_to_string, CodeWriter, ModuleWriter};
+use std::path::{Path, PathBuf};
+
+pub mod common;
+
+fn make_enumeration() -> Enumeration {
+    Enumeration::new(Identifier::new("Color"))
+        .set_visibility(Visibility::Public)
+        .add_variant(
+            EnumerationVariant::new(Identifier::new("Red"))
+                .set_documentation("The color red.")
+                .build(),
+        )
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("Green"),
+            Value::from("green"),
+        ))
+        .build()
+}
+
+#[test]
+fn test_interface() {
+    let structure = StructuredType::structure(Identifier::new("Page"))
+        .set_visibility(Visibility::Public)
+        .set_documentation("A page of results.\n\nPages are never empty.")
+        .add_generic_parameter(GenericParameter::with_bounds(
+            Identifier::new("T"),
+            vec![ValueType::Reference(Identifier::new("Entity"))],
+        ))
+        .add_extend(ValueType::Reference(Identifier::new("Paged")))
+        .add_field(
+            Field::new(
+                Identifier::new("items"),
+                ValueType::array_of(ValueType::Reference(Identifier::new("T"))),
+            )
+            .set_documentation("The items on this page.")
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("next"),
+                ValueType::optional(ValueType::string()),
+            )
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("totals"),
+                ValueType::map_of(ValueType::string(), ValueType::u64()),
+            )
+            .optional()
+            .build(),
+        )
+        .add_field(
+            Field::new(
+                Identifier::new("range"),
+                ValueType::tuple_of(vec![
+                    ValueType::u32(),
+                    ValueType::optional(ValueType::u32()),
+                ]),
+            )
+            .build(),
+        )
+        .add_method(
+            FunctionDecl::new(Identifier::new("get"))
+                .add_parameter(Parameter::new(Identifier::new("index"), ValueType::u32()))
+                .set_value_type(ValueType::optional(ValueType::Reference(Identifier::new(
+                    "T",
+                ))))
+                .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        TypeScriptWriter::default().write_structured_type(writer, &structure)
+    });
+    assert_eq!(
+        result,
+        r#"/**
+ * A page of results.
+ *
+ * Pages are never empty.
+ */
+export interface Page<T extends Entity> extends Paged {
+    /** The items on this page. */
+    items: T[];
+    next?: string;
+    totals?: Record<string, number>;
+    range: [number, number | undefined];
+    get(index: number): T | undefined;
+}
+"#
+    );
+}
+
+#[test]
+fn test_union() {
+    let union = StructuredType::union(Identifier::new("Contact"))
+        .add_field(Field::new(Identifier::new("email"), ValueType::string()).build())
+        .add_field(
+            Field::new(
+                Identifier::new("phone"),
+                ValueType::array_of(ValueType::optional(ValueType::string())),
+            )
+            .build(),
+        )
+        .build();
+
+    let result = common::write_to_string(|writer| {
+        TypeScriptWriter::default().write_structured_type(writer, &union)
+    });
+    assert_eq!(
+        result,
+        r#"type Contact = { email: string } | { phone: (string | undefined)[] };
+"#
+    );
+}
+
+#[test]
+fn test_enumeration_styles() {
+    let enumeration = make_enumeration();
+    let result = common::write_to_string(|writer| {
+        TypeScriptWriter::default().write_enumeration(writer, &enumeration)?;
+        let mut string_writer = TypeScriptWriter::declaration_file();
+        let _ = string_writer.set_enumeration_style(EnumerationStyle::StringEnum);
+        string_writer.write_enumeration(writer, &enumeration)?;
+        let mut union_writer = TypeScriptWriter::default();
+        let _ = union_writer.set_enumeration_style(EnumerationStyle::Union);
+        union_writer.write_enumeration(writer, &enumeration)
+    });
+    assert_eq!(
+        result,
+        r#"export enum Color {
+    /** The color red. */
+    Red,
+    Green = "green",
+}
+export declare enum Color {
+    /** The color red. */
+    Red = "Red",
+    Green = "green",
+}
+export type Color = "Red" | "green";
+"#
+    );
+}
+
+#[test]
+fn test_enumeration_value_unsupported() {
+    let enumeration = Enumeration::new(Identifier::new("Ratio"))
+        .add_variant(EnumerationVariant::with_value(
+            Identifier::new("Half"),
+            Value::from(0.5),
+        ))
+        .build();
+
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    assert!(TypeScriptWriter::default()
+        .write_enumeration(&mut writer, &enumeration)
+        .is_err());
+}
+
+#[test]
+fn test_module_items() {
+    let module = Module::new(Identifier::new("users"))
+        .set_documentation("User accounts.")
+        .add_import(
+            Import::new(Namespace::new(vec![
+                Identifier::new("common"),
+                Identifier::new("address"),
+            ]))
+            .item(Identifier::new("Country"))
+            .item_with_alias(Identifier::new("Address"), Identifier::new("PostalAddress"))
+            .build(),
+        )
+        .add_import(Import::new(Namespace::new(vec![Identifier::new("util")])))
+        .add_alias(
+            TypeAlias::new(Identifier::new("UserId"), ValueType::string())
+                .set_visibility(Visibility::Public)
+                .set_documentation("Identifies a user.")
+                .build(),
+        )
+        .add_constant(
+            NamedValue::new(
+                Identifier::new("MAX_USERS"),
+                ValueType::u32(),
+                Value::from(100u32),
+            )
+            .set_visibility(Visibility::Public)
+            .build(),
+        )
+        .add_function(
+            FunctionDecl::new(Identifier::new("findUser"))
+                .set_visibility(Visibility::Public)
+                .set_documentation("Find a user.")
+                .add_parameter(
+                    Parameter::new(
+                        Identifier::new("id"),
+                        ValueType::Reference(Identifier::new("UserId")),
+                    )
+                    .set_documentation("The user to find.")
+                    .build(),
+                )
+                .add_parameter(
+                    Parameter::new(Identifier::new("cached"), ValueType::boolean())
+                        .optional()
+                        .build(),
+                )
+                .set_value_type(ValueType::Reference(Identifier::new("User")))
+                .add_throws(Parameter::new(
+                    Identifier::new("e"),
+                    ValueType::Reference(Identifier::new("NotFound")),
+                ))
+                .build(),
+        )
+        .add_sub_module(Module::new(Identifier::new("roles")).build())
+        .build();
+
+    assert_eq!(
+        module_to_string(&TypeScriptWriter::default(), &module).unwrap(),
+        r#"/**
+ * User accounts.
+ *
+ * @packageDocumentation
+ */
+
+import { Country, Address as PostalAddress } from './common/address';
+
+import * as util from './util';
+
+/** Identifies a user. */
+export type UserId = string;
+
+export const MAX_USERS: number = 100;
+
+/**
+ * Find a user.
+ *
+ * @param id - The user to find.
+ * @throws {@link NotFound}
+ */
+export declare function findUser(id: UserId, cached?: boolean): User;
+
+export * as roles from './roles';
+"#
+    );
+    assert!(
+        module_to_string(&TypeScriptWriter::declaration_file(), &module)
+            .unwrap()
+            .contains("export declare const MAX_USERS: number;\n")
+    );
+}
+
+#[test]
+fn test_layout() {
+    let namespace = Namespace::new(vec![Identifier::new("api"), Identifier::new("users")]);
+    assert_eq!(
+        TypeScriptLayout::Sources.module_file_path(&namespace),
+        PathBuf::from("api/users/index.ts")
+    );
+    assert_eq!(
+        TypeScriptLayout::Declarations.module_file_path(&namespace),
+        PathBuf::from("api/users/index.d.ts")
+    );
+}
+
+#[test]
+fn test_project_imports_are_relative() {
+    let module = Module::new(Identifier::new("api"))
+        .add_sub_module(
+            Module::new(Identifier::new("users"))
+                .add_import(
+                    Import::new(Namespace::new(vec![
+                        Identifier::new("api"),
+                        Identifier::new("common"),
+                    ]))
+                    .item(Identifier::new("Country"))
+                    .build(),
+                )
+                .build(),
+        )
+        .add_sub_module(Module::new(Identifier::new("common")))
+        .build();
+    let files = ProjectWriter::new(TypeScriptLayout::Sources, TypeScriptWriter::default())
+        .render(&module)
+        .unwrap();
+
+    assert_eq!(
+        files.get(Path::new("api/users/index.ts")).unwrap(),
+        "import { Country } from '../common';\n"
+    );
+    assert_eq!(
+        files.get(Path::new("api/index.ts")).unwrap(),
+        "export * as users from './users';\n\nexport * as common from './common';\n"
+    );
+}
+
+#[test]
+fn test_empty_import_unsupported() {
+    let mut namespace = Namespace::new(vec![Identifier::new("common")]);
+    let _ = namespace.pop();
+    let module = Module::new(Identifier::new("users"))
+        .add_import(
+            Import::new(namespace)
+                .item(Identifier::new("Country"))
+                .build(),
+        )
+        .build();
+    assert!(module_to_string(&TypeScriptWriter::default(), &module).is_err());
+}
+
+#[test]
+fn test_non_finite_constants() {
+    let constants = [
+        ("BIG", Value::from(f64::INFINITY)),
+        ("SMALL", Value::from(f32::NEG_INFINITY)),
+        ("UNKNOWN", Value::from(f64::NAN)),
+        ("HALF", Value::from(0.5f32)),
+    ];
+
+    let result = common::write_to_string(|writer| {
+        for (name, value) in &constants {
+            TypeScriptWriter::default().write_constant(
+                writer,
+                &NamedValue::new(Identifier::new(name), ValueType::f64(), value.clone()).build(),
+            )?;
+        }
+        Ok(())
+    });
+    assert_eq!(
+        result,
+        "const BIG: number = Infinity;\nconst SMALL: number = -Infinity;\n\
+         const UNKNOWN: number = NaN;\nconst HALF: number = 0.5;\n"
+    );
+}
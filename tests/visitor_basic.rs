@@ -0,0 +1,195 @@
+use code_writer::error::Result;
+use code_writer::model::identity::Namespace;
+use code_writer::model::visitor::{
+    fold_content, walk_module, walk_module_mut, Fold, Visitor, VisitorMut, Walk,
+};
+use code_writer::model::{
+    Builder, Enumeration, EnumerationVariant, Field, FunctionDecl, HasName, Identifier,
+    Implementation, Module, ModuleContent, Parameter, StructuredType, ValueType, VariantPayload,
+};
+
+fn make_module() -> Module {
+    Module::new(Identifier::new("shapes"))
+        .add_structure(
+            StructuredType::structure(Identifier::new("Circle"))
+                .add_field(Field::new(Identifier::new("r"), ValueType::f64()).build())
+                .build(),
+        )
+        .add_enumeration(
+            Enumeration::new(Identifier::new("Shape"))
+                .add_variant(
+                    EnumerationVariant::new(Identifier::new("Square"))
+                        .set_payload(VariantPayload::Struct(vec![Field::new(
+                            Identifier::new("side"),
+                            ValueType::f64(),
+                        )
+                        .build()]))
+                        .build(),
+                )
+                .build(),
+        )
+        .add_implementation(
+            Implementation::new(ValueType::Reference(Identifier::new("Circle")))
+                .add_method(
+                    FunctionDecl::new(Identifier::new("scale"))
+                        .add_parameter(
+                            Parameter::new(Identifier::new("by"), ValueType::f64()).build(),
+                        )
+                        .build(),
+                )
+                .build(),
+        )
+        .add_sub_module(
+            Module::new(Identifier::new("solids"))
+                .add_function(FunctionDecl::new(Identifier::new("volume")).build())
+                .build(),
+        )
+        .build()
+}
+
+#[derive(Default)]
+struct Tracer {
+    events: Vec<String>,
+    skip_enumerations: bool,
+}
+
+impl Visitor for Tracer {
+    fn enter_module(&mut self, namespace: &Namespace, _: &Module) -> Result<Walk> {
+        self.events
+            .push(format!("enter module {}", namespace.join("::")));
+        Ok(Walk::Continue)
+    }
+
+    fn exit_module(&mut self, namespace: &Namespace, _: &Module) -> Result<()> {
+        self.events
+            .push(format!("exit module {}", namespace.join("::")));
+        Ok(())
+    }
+
+    fn enter_structured_type(&mut self, structured_type: &StructuredType) -> Result<Walk> {
+        self.events
+            .push(format!("enter type {}", structured_type.name()));
+        Ok(Walk::Continue)
+    }
+
+    fn enter_enumeration(&mut self, enumeration: &Enumeration) -> Result<Walk> {
+        self.events
+            .push(format!("enter enum {}", enumeration.name()));
+        Ok(if self.skip_enumerations {
+            Walk::SkipChildren
+        } else {
+            Walk::Continue
+        })
+    }
+
+    fn exit_enumeration(&mut self, enumeration: &Enumeration) -> Result<()> {
+        self.events
+            .push(format!("exit enum {}", enumeration.name()));
+        Ok(())
+    }
+
+    fn enter_variant(&mut self, variant: &EnumerationVariant) -> Result<Walk> {
+        self.events
+            .push(format!("enter variant {}", variant.name()));
+        Ok(Walk::Continue)
+    }
+
+    fn enter_field(&mut self, field: &Field) -> Result<()> {
+        self.events.push(format!("field {}", field.name()));
+        Ok(())
+    }
+
+    fn enter_function(&mut self, function: &FunctionDecl) -> Result<Walk> {
+        self.events
+            .push(format!("enter function {}", function.name()));
+        Ok(Walk::Continue)
+    }
+
+    fn enter_parameter(&mut self, parameter: &Parameter) -> Result<()> {
+        self.events.push(format!("parameter {}", parameter.name()));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_visitor_order() {
+    let mut tracer = Tracer::default();
+    walk_module(&mut tracer, &make_module()).unwrap();
+    assert_eq!(
+        tracer.events,
+        vec![
+            "enter module shapes",
+            "enter type Circle",
+            "field r",
+            "enter enum Shape",
+            "enter variant Square",
+            "field side",
+            "exit enum Shape",
+            "enter function scale",
+            "parameter by",
+            "enter module shapes::solids",
+            "enter function volume",
+            "exit module shapes::solids",
+            "exit module shapes",
+        ]
+    );
+}
+
+#[test]
+fn test_visitor_skip_children() {
+    let mut tracer = Tracer {
+        skip_enumerations: true,
+        ..Default::default()
+    };
+    walk_module(&mut tracer, &make_module()).unwrap();
+    assert!(tracer.events.contains(&"exit enum Shape".to_string()));
+    assert!(!tracer.events.contains(&"enter variant Square".to_string()));
+}
+
+struct Prefixer;
+
+impl VisitorMut for Prefixer {
+    fn enter_field(&mut self, field: &mut Field) -> Result<()> {
+        let name = Identifier::new(&format!("the_{}", field.name()));
+        let _ = field.set_name(name);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_visitor_mut() {
+    let mut module = make_module();
+    walk_module_mut(&mut Prefixer, &mut module).unwrap();
+
+    let mut tracer = Tracer::default();
+    walk_module(&mut tracer, &module).unwrap();
+    assert!(tracer.events.contains(&"field the_r".to_string()));
+    assert!(tracer.events.contains(&"field the_side".to_string()));
+}
+
+struct RemoveFunctions;
+
+impl Fold for RemoveFunctions {
+    fn fold_content(&mut self, content: ModuleContent) -> Result<Option<ModuleContent>> {
+        match content {
+            ModuleContent::Function(_) => Ok(None),
+            content => fold_content(self, content),
+        }
+    }
+
+    fn fold_parameter(&mut self, _: Parameter) -> Result<Option<Parameter>> {
+        Ok(None)
+    }
+}
+
+#[test]
+fn test_fold() {
+    let module = RemoveFunctions.fold_module(make_module()).unwrap();
+
+    let mut tracer = Tracer::default();
+    walk_module(&mut tracer, &module).unwrap();
+    assert!(!tracer.events.contains(&"enter function volume".to_string()));
+    assert!(tracer.events.contains(&"enter function scale".to_string()));
+    assert!(!tracer.events.contains(&"parameter by".to_string()));
+    assert_eq!(module.content().len(), 4);
+}
@@ -0,0 +1,284 @@
+use code_writer::language::rust::RustWriter;
+use code_writer::model::{Builder, Identifier, Module, StructuredType};
+use code_writer::writer::{
+    module_to_string, reflow_markdown, Block, CodeWriter, FmtWriter, FormattingPolicy, List,
+    ListWrapping, NewLine,
+};
+use std::io::Write;
+
+pub mod common;
+
+#[test]
+fn test_reflow_paragraphs() {
+    let lines = reflow_markdown(
+        "The quick brown fox\njumps over the lazy dog.\n\nA second paragraph.",
+        20,
+    );
+    assert_eq!(
+        lines,
+        vec![
+            "The quick brown fox",
+            "jumps over the lazy",
+            "dog.",
+            "",
+            "A second paragraph.",
+        ]
+    );
+}
+
+#[test]
+fn test_reflow_list_items_with_hanging_indent() {
+    let text = "Steps:\n\n1. créer le répertoire où écrire\n10. éditer\n   - détail très très long";
+    let lines = reflow_markdown(text, 20);
+    assert_eq!(
+        lines,
+        vec![
+            "Steps:",
+            "",
+            "1. créer le",
+            "   répertoire où",
+            "   écrire",
+            "10. éditer",
+            "   - détail très",
+            "     très long",
+        ]
+    );
+}
+
+#[test]
+fn test_reflow_preserves_markdown_blocks() {
+    let text = r#"# Example Heading
+
+Some text that should be joined
+together.
+
+```rust
+let x = some_function_with_a_long_name(argument_one, argument_two);
+```
+
+- first item that is long enough to wrap
+  continued here
+- second item
+
+| a | b |
+|---|---|
+| 1 | 2 |"#;
+    let lines = reflow_markdown(text, 50);
+    assert_eq!(
+        lines,
+        vec![
+            "# Example Heading",
+            "",
+            "Some text that should be joined together.",
+            "",
+            "```rust",
+            "let x = some_function_with_a_long_name(argument_one, argument_two);",
+            "```",
+            "",
+            "- first item that is long enough to wrap continued",
+            "  here",
+            "- second item",
+            "",
+            "| a | b |",
+            "|---|---|",
+            "| 1 | 2 |",
+        ]
+    );
+}
+
+#[test]
+fn test_doc_text_respects_indent() {
+    let result = common::write_to_string(|writer| {
+        writer.set_max_width(24);
+        writer.indent();
+        writer.write_doc_text("/// ", "one two three four five six\n\nseven")
+    });
+    assert_eq!(
+        result,
+        "    /// one two three\n    /// four five six\n    ///\n    /// seven\n"
+    );
+}
+
+#[test]
+fn test_list_fill() {
+    let mut buffer: Vec<u8> = Default::default();
+    {
+        let mut writer = CodeWriter::new(&mut buffer);
+        writer.set_max_width(16);
+        writer
+            .write_list_then(
+                &List::new("[", "]", ListWrapping::Fill),
+                &["one", "two", "three", "four"],
+                ";",
+            )
+            .unwrap();
+        writer.flush().unwrap();
+    }
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "[\n    one, two,\n    three, four\n];\n"
+    );
+}
+
+fn write_with_policy<F>(policy: FormattingPolicy, f: F) -> String
+where
+    F: FnOnce(&mut CodeWriter<&mut Vec<u8>>) -> code_writer::error::Result<()>,
+{
+    let mut buffer: Vec<u8> = Default::default();
+    {
+        let mut writer = CodeWriter::with_policy(&mut buffer, policy);
+        f(&mut writer).unwrap();
+        writer.flush().unwrap();
+    }
+    String::from_utf8(buffer).unwrap()
+}
+
+#[test]
+fn test_policy_crlf_and_indent() {
+    let mut policy = FormattingPolicy::default();
+    policy
+        .set_new_line(NewLine::CarriageReturnLineFeed)
+        .set_indent("\t");
+    let result = write_with_policy(policy, |writer| {
+        writer.write_str("a {\n")?;
+        writer.indent();
+        writer.write_str("b\n")?;
+        writer.outdent();
+        writer.write_str("}")
+    });
+    assert_eq!(result, "a {\r\n\tb\r\n}\r\n");
+}
+
+#[test]
+fn test_set_max_width_updates_policy() {
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    writer.set_max_width(40);
+    assert_eq!(writer.policy().max_width(), 40);
+    assert_eq!(writer.max_width(), 40);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_whitespace_handling() {
+    let mut handling = code_writer::writer::WhitespaceHandling::default();
+    let _ = handling.set_indent("\t").no_trim_trailing();
+    assert_eq!(handling.indent(), "\t");
+    assert!(!handling.trim_trailing());
+}
+
+#[test]
+fn test_current_line_len_counts_chars() {
+    let mut buffer: Vec<u8> = Default::default();
+    let mut writer = CodeWriter::new(&mut buffer);
+    writer.set_max_width(10);
+    writer.write_str("größe: ").unwrap();
+    assert_eq!(writer.current_line_len(), 7);
+    assert!(writer.fits("äöü"));
+    assert!(!writer.fits("äöüß"));
+}
+
+#[test]
+fn test_policy_trim_trailing() {
+    let result = write_with_policy(FormattingPolicy::default(), |writer| {
+        writer.indent();
+        writer.write_str("a   \n\nb\n")?;
+        writer.outdent();
+        Ok(())
+    });
+    assert_eq!(result, "    a\n\n    b\n");
+
+    let mut policy = FormattingPolicy::default();
+    policy.set_trim_trailing(false);
+    let result = write_with_policy(policy, |writer| writer.write_str("a   \nb"));
+    assert_eq!(result, "a   \nb\n");
+}
+
+#[test]
+fn test_policy_blank_lines() {
+    let mut policy = FormattingPolicy::default();
+    policy.set_collapse_blank_lines(true);
+    let result = write_with_policy(policy, |writer| {
+        writer.write_str("a")?;
+        writer.new_line()?;
+        writer.blank_line()?;
+        writer.blank_line()?;
+        writer.blank_line()?;
+        writer.write_str("b")?;
+        writer.new_line()?;
+        writer.blank_line()?;
+        writer.blank_line()
+    });
+    assert_eq!(result, "a\n\nb\n");
+
+    let mut policy = FormattingPolicy::default();
+    policy.set_final_new_line(false);
+    let result = write_with_policy(policy, |writer| {
+        writer.write_str("a")?;
+        writer.new_line()?;
+        writer.blank_line()?;
+        writer.write_str("b")
+    });
+    assert_eq!(result, "a\n\nb");
+}
+
+#[test]
+fn test_indentation_block() {
+    let block = Block::indentation(":");
+    let result = write_with_policy(FormattingPolicy::default(), |writer| {
+        writer.write_str("class Point")?;
+        writer.open_block(&block)?;
+        writer.write_str("x: int")?;
+        writer.new_line()?;
+        writer.write_str("def norm(self) -> float")?;
+        writer.open_block(&block)?;
+        writer.write_str("...")?;
+        writer.close_block(&block)?;
+        writer.close_block(&block)?;
+        writer.write_str("ORIGIN = Point(0)")
+    });
+    assert_eq!(
+        result,
+        "class Point:\n    x: int\n    def norm(self) -> float:\n        ...\nORIGIN = Point(0)\n"
+    );
+}
+
+#[test]
+fn test_fmt_writer() {
+    let mut writer = CodeWriter::from_fmt(String::new());
+    writer.write_str("fn main() {").unwrap();
+    writer.new_line().unwrap();
+    writer.indent();
+    writer.write_str("println!(\"héllo\");").unwrap();
+    writer.new_line().unwrap();
+    writer.outdent();
+    writer.write_str("}").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(
+        writer.into_inner().into_inner(),
+        "fn main() {\n    println!(\"héllo\");\n}\n"
+    );
+}
+
+#[test]
+fn test_fmt_writer_split_characters() {
+    let bytes = "é!".as_bytes();
+    let mut writer = FmtWriter::new(String::new());
+    writer.write_all(&bytes[..1]).unwrap();
+    writer.write_all(&bytes[1..]).unwrap();
+    assert_eq!(writer.into_inner(), "é!");
+
+    let mut writer = FmtWriter::new(String::new());
+    assert!(writer.write_all(&[0xff, b'a']).is_err());
+}
+
+#[test]
+fn test_module_to_string() {
+    let module = Module::new(Identifier::new("shapes"))
+        .add_structure(StructuredType::structure(Identifier::new("Circle")).build())
+        .build();
+    assert_eq!(
+        module_to_string(&RustWriter::default(), &module).unwrap(),
+        "struct Circle {}\n"
+    );
+}